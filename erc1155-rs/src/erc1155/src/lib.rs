@@ -0,0 +1,703 @@
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+#![allow(dead_code)]
+
+extern crate alloc;
+extern crate fluentbase_sdk;
+
+pub mod receiver;
+
+#[cfg(test)]
+mod testing;
+
+use alloc::{format, string::String, vec, vec::Vec};
+use alloy_sol_types::{sol, SolError, SolEvent};
+use fluentbase_sdk::derive::Contract;
+use fluentbase_sdk::{
+    basic_entrypoint,
+    derive::{constructor, router, Storage},
+    storage::{StorageAddress, StorageMap, StorageString, StorageU256},
+    Address, Bytes, ContextReader, ExitCode, FixedBytes, SharedAPI, B256, U256,
+};
+use hex_literal::hex;
+use receiver::ReceiverError;
+
+// Define the ERC-1155 events
+sol! {
+    event TransferSingle(
+        address indexed operator,
+        address indexed from,
+        address indexed to,
+        uint256 id,
+        uint256 value
+    );
+    event TransferBatch(
+        address indexed operator,
+        address indexed from,
+        address indexed to,
+        uint256[] ids,
+        uint256[] values
+    );
+    event ApprovalForAll(address indexed account, address indexed operator, bool approved);
+}
+
+// Same errors as OpenZeppelin's `IERC1155Errors`, for the calls that can revert with one
+sol! {
+    error ERC1155InsufficientBalance(address sender, uint256 balance, uint256 needed, uint256 tokenId);
+    error ERC1155InvalidSender(address sender);
+    error ERC1155InvalidReceiver(address receiver);
+    error ERC1155MissingApprovalForAll(address operator, address owner);
+    error ERC1155InvalidOperator(address operator);
+    error ERC1155InvalidArrayLength(uint256 idsLength, uint256 valuesLength);
+    error UnauthorizedMinter(address account);
+}
+
+/// ERC-165 interface ids reported by `supportsInterface`
+pub const INTERFACE_ID_ERC165: [u8; 4] = hex!("01ffc9a7");
+pub const INTERFACE_ID_ERC1155: [u8; 4] = hex!("d9b67a26");
+pub const INTERFACE_ID_ERC1155_METADATA_URI: [u8; 4] = hex!("0e89341c");
+
+fn emit_event<SDK: SharedAPI, T: SolEvent>(sdk: &mut SDK, event: T) {
+    let data = event.encode_data();
+    let topics: Vec<B256> = event
+        .encode_topics()
+        .iter()
+        .map(|v| B256::from(v.0))
+        .collect();
+    sdk.emit_log(&topics, &data);
+}
+
+// Revert with ABI-encoded custom error data, so callers can decode the reason
+fn revert<SDK: SharedAPI, T: SolError>(sdk: &mut SDK, error: T) -> ! {
+    revert_with_data(sdk, &error.abi_encode())
+}
+
+fn revert_with_data<SDK: SharedAPI>(sdk: &mut SDK, data: &[u8]) -> ! {
+    sdk.write(data);
+    sdk.exit(ExitCode::Panic)
+}
+
+// Define ERC1155 trait interface
+pub trait ERC1155Interface {
+    // ERC-165
+    fn supports_interface(&self, interface_id: FixedBytes<4>) -> bool;
+
+    // ERC-1155
+    fn balance_of(&self, account: Address, id: U256) -> U256;
+    fn balance_of_batch(&mut self, accounts: Vec<Address>, ids: Vec<U256>) -> Vec<U256>;
+    fn set_approval_for_all(&mut self, operator: Address, approved: bool);
+    fn is_approved_for_all(&self, account: Address, operator: Address) -> bool;
+    fn safe_transfer_from(
+        &mut self,
+        from: Address,
+        to: Address,
+        id: U256,
+        value: U256,
+        data: Bytes,
+    );
+    fn safe_batch_transfer_from(
+        &mut self,
+        from: Address,
+        to: Address,
+        ids: Vec<U256>,
+        values: Vec<U256>,
+        data: Bytes,
+    );
+
+    // Metadata URI
+    fn uri(&self, id: U256) -> String;
+
+    // Supply management
+    fn mint(&mut self, to: Address, id: U256, value: U256, data: Bytes);
+    fn mint_batch(&mut self, to: Address, ids: Vec<U256>, values: Vec<U256>, data: Bytes);
+    fn burn(&mut self, from: Address, id: U256, value: U256);
+}
+
+// Storage structure
+#[derive(Contract)]
+pub struct ERC1155<SDK> {
+    sdk: SDK,
+    uri_template: StorageString,
+    minter: StorageAddress,
+    // id => account => balance
+    balances: StorageMap<U256, StorageMap<Address, StorageU256>>,
+    // Non-zero when the operator is approved
+    operator_approvals: StorageMap<Address, StorageMap<Address, StorageU256>>,
+}
+
+// Shared token logic, following OpenZeppelin v5's `_update` and `_updateWithAcceptanceCheck`
+impl<SDK: SharedAPI> ERC1155<SDK> {
+    fn is_operator(&self, account: Address, operator: Address) -> bool {
+        !self
+            .operator_approvals_accessor()
+            .entry(account)
+            .entry(operator)
+            .get(&self.sdk)
+            .is_zero()
+    }
+
+    fn check_approved(&mut self, owner: Address) {
+        let operator = self.sdk.context().contract_caller();
+        if operator != owner && !self.is_operator(owner, operator) {
+            revert(
+                &mut self.sdk,
+                ERC1155MissingApprovalForAll { operator, owner },
+            );
+        }
+    }
+
+    /// Moves `values` of `ids` from `from` to `to`, minting from or burning to the zero
+    /// address, and emits `TransferSingle` for one id or `TransferBatch` for several.
+    fn update(&mut self, from: Address, to: Address, ids: &[U256], values: &[U256]) {
+        if ids.len() != values.len() {
+            revert(
+                &mut self.sdk,
+                ERC1155InvalidArrayLength {
+                    idsLength: U256::from(ids.len()),
+                    valuesLength: U256::from(values.len()),
+                },
+            );
+        }
+
+        for (&id, &value) in ids.iter().zip(values) {
+            if from != Address::ZERO {
+                let from_balance = self
+                    .balances_accessor()
+                    .entry(id)
+                    .entry(from)
+                    .get(&self.sdk);
+                if from_balance < value {
+                    revert(
+                        &mut self.sdk,
+                        ERC1155InsufficientBalance {
+                            sender: from,
+                            balance: from_balance,
+                            needed: value,
+                            tokenId: id,
+                        },
+                    );
+                }
+                self.balances_accessor()
+                    .entry(id)
+                    .entry(from)
+                    .set(&mut self.sdk, from_balance - value);
+            }
+            if to != Address::ZERO {
+                let to_balance = self.balances_accessor().entry(id).entry(to).get(&self.sdk);
+                let Some(new_balance) = to_balance.checked_add(value) else {
+                    panic!("balance overflow");
+                };
+                self.balances_accessor()
+                    .entry(id)
+                    .entry(to)
+                    .set(&mut self.sdk, new_balance);
+            }
+        }
+
+        let operator = self.sdk.context().contract_caller();
+        if ids.len() == 1 {
+            emit_event(
+                &mut self.sdk,
+                TransferSingle {
+                    operator,
+                    from,
+                    to,
+                    id: ids[0],
+                    value: values[0],
+                },
+            );
+        } else {
+            emit_event(
+                &mut self.sdk,
+                TransferBatch {
+                    operator,
+                    from,
+                    to,
+                    ids: ids.to_vec(),
+                    values: values.to_vec(),
+                },
+            );
+        }
+    }
+
+    /// [`Self::update`], then asks a receiving contract whether it accepts the tokens.
+    fn update_with_acceptance_check(
+        &mut self,
+        from: Address,
+        to: Address,
+        ids: Vec<U256>,
+        values: Vec<U256>,
+        data: Bytes,
+    ) {
+        self.update(from, to, &ids, &values);
+        if to == Address::ZERO {
+            return;
+        }
+
+        let operator = self.sdk.context().contract_caller();
+        let result = if ids.len() == 1 {
+            receiver::check_on_erc1155_received(
+                &mut self.sdk,
+                operator,
+                from,
+                to,
+                ids[0],
+                values[0],
+                data,
+            )
+        } else {
+            receiver::check_on_erc1155_batch_received(
+                &mut self.sdk,
+                operator,
+                from,
+                to,
+                ids,
+                values,
+                data,
+            )
+        };
+        match result {
+            Ok(()) => {}
+            Err(ReceiverError::Rejected) => {
+                revert(&mut self.sdk, ERC1155InvalidReceiver { receiver: to })
+            }
+            // Bubble up the receiver's reason, like Solidity does
+            Err(ReceiverError::Reverted(reason)) => revert_with_data(&mut self.sdk, &reason),
+        }
+    }
+
+    fn transfer(
+        &mut self,
+        from: Address,
+        to: Address,
+        ids: Vec<U256>,
+        values: Vec<U256>,
+        data: Bytes,
+    ) {
+        if to == Address::ZERO {
+            revert(&mut self.sdk, ERC1155InvalidReceiver { receiver: to });
+        }
+        if from == Address::ZERO {
+            revert(&mut self.sdk, ERC1155InvalidSender { sender: from });
+        }
+        self.update_with_acceptance_check(from, to, ids, values, data);
+    }
+
+    fn check_minter(&mut self) {
+        let caller = self.sdk.context().contract_caller();
+        if caller != self.minter_accessor().get(&self.sdk) {
+            revert(&mut self.sdk, UnauthorizedMinter { account: caller });
+        }
+    }
+}
+
+// Separate constructor implementation. The deployer becomes the only minter
+#[constructor(mode = "solidity")]
+impl<SDK: SharedAPI> ERC1155<SDK> {
+    pub fn constructor(&mut self, uri: String) {
+        self.uri_template_accessor().set(&mut self.sdk, &uri);
+
+        let deployer = self.sdk.context().contract_caller();
+        self.minter_accessor().set(&mut self.sdk, deployer);
+    }
+}
+
+// Router implementation for trait methods
+#[router(mode = "solidity")]
+impl<SDK: SharedAPI> ERC1155Interface for ERC1155<SDK> {
+    #[function_id("supportsInterface(bytes4)")]
+    fn supports_interface(&self, interface_id: FixedBytes<4>) -> bool {
+        [
+            INTERFACE_ID_ERC165,
+            INTERFACE_ID_ERC1155,
+            INTERFACE_ID_ERC1155_METADATA_URI,
+        ]
+        .contains(&interface_id.0)
+    }
+
+    fn balance_of(&self, account: Address, id: U256) -> U256 {
+        self.balances_accessor()
+            .entry(id)
+            .entry(account)
+            .get(&self.sdk)
+    }
+
+    // Takes `&mut self` only to write the revert data, it never writes storage
+    fn balance_of_batch(&mut self, accounts: Vec<Address>, ids: Vec<U256>) -> Vec<U256> {
+        if accounts.len() != ids.len() {
+            // Same argument order as OpenZeppelin: ids first
+            revert(
+                &mut self.sdk,
+                ERC1155InvalidArrayLength {
+                    idsLength: U256::from(ids.len()),
+                    valuesLength: U256::from(accounts.len()),
+                },
+            );
+        }
+        accounts
+            .into_iter()
+            .zip(ids)
+            .map(|(account, id)| self.balance_of(account, id))
+            .collect()
+    }
+
+    fn set_approval_for_all(&mut self, operator: Address, approved: bool) {
+        let account = self.sdk.context().contract_caller();
+        if operator == Address::ZERO {
+            revert(&mut self.sdk, ERC1155InvalidOperator { operator });
+        }
+
+        self.operator_approvals_accessor()
+            .entry(account)
+            .entry(operator)
+            .set(&mut self.sdk, U256::from(approved as u8));
+
+        emit_event(
+            &mut self.sdk,
+            ApprovalForAll {
+                account,
+                operator,
+                approved,
+            },
+        );
+    }
+
+    fn is_approved_for_all(&self, account: Address, operator: Address) -> bool {
+        self.is_operator(account, operator)
+    }
+
+    fn safe_transfer_from(
+        &mut self,
+        from: Address,
+        to: Address,
+        id: U256,
+        value: U256,
+        data: Bytes,
+    ) {
+        self.check_approved(from);
+        self.transfer(from, to, vec![id], vec![value], data);
+    }
+
+    fn safe_batch_transfer_from(
+        &mut self,
+        from: Address,
+        to: Address,
+        ids: Vec<U256>,
+        values: Vec<U256>,
+        data: Bytes,
+    ) {
+        self.check_approved(from);
+        self.transfer(from, to, ids, values, data);
+    }
+
+    // The URI template with every `{id}` replaced by the id as 64 lowercase hex digits,
+    // as EIP-1155 specifies for clients
+    fn uri(&self, id: U256) -> String {
+        let template = self.uri_template_accessor().get(&self.sdk);
+        let hex_id: String = id
+            .to_be_bytes::<32>()
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect();
+        template.replace("{id}", &hex_id)
+    }
+
+    fn mint(&mut self, to: Address, id: U256, value: U256, data: Bytes) {
+        self.check_minter();
+        if to == Address::ZERO {
+            revert(&mut self.sdk, ERC1155InvalidReceiver { receiver: to });
+        }
+        self.update_with_acceptance_check(Address::ZERO, to, vec![id], vec![value], data);
+    }
+
+    fn mint_batch(&mut self, to: Address, ids: Vec<U256>, values: Vec<U256>, data: Bytes) {
+        self.check_minter();
+        if to == Address::ZERO {
+            revert(&mut self.sdk, ERC1155InvalidReceiver { receiver: to });
+        }
+        self.update_with_acceptance_check(Address::ZERO, to, ids, values, data);
+    }
+
+    // Callable by `from` or its operators
+    fn burn(&mut self, from: Address, id: U256, value: U256) {
+        self.check_approved(from);
+        if from == Address::ZERO {
+            revert(&mut self.sdk, ERC1155InvalidSender { sender: from });
+        }
+        self.update(from, Address::ZERO, &[id], &[value]);
+    }
+}
+
+basic_entrypoint!(ERC1155);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{to_u256s, MultiTokenHarness};
+    use fluentbase_sdk::address;
+
+    const MINTER: Address = address!("1111111111111111111111111111111111111111");
+    const ALICE: Address = address!("2222222222222222222222222222222222222222");
+    const BOB: Address = address!("3333333333333333333333333333333333333333");
+    const OPERATOR: Address = address!("4444444444444444444444444444444444444444");
+    const STRANGER: Address = address!("6666666666666666666666666666666666666666");
+
+    // Game items, e.g. id 1 = coins, id 2 = gems, id 3 = a trophy
+    const COINS: u64 = 1;
+    const GEMS: u64 = 2;
+    const TROPHY: u64 = 3;
+
+    // Alice holds 1_000 coins, 50 gems and a trophy
+    fn minted() -> MultiTokenHarness {
+        let mut token = MultiTokenHarness::deploy(MINTER, "https://game.example/items/{id}.json");
+        token
+            .mint_batch(MINTER, ALICE, &[COINS, GEMS, TROPHY], &[1_000, 50, 1])
+            .unwrap();
+        token
+    }
+
+    #[test]
+    fn test_uri_substitutes_id() {
+        let mut token = minted();
+
+        assert_eq!(
+            token.uri(U256::from(0x4cce)),
+            "https://game.example/items/0000000000000000000000000000000000000000000000000000000000004cce.json"
+        );
+        assert!(token.supports_interface(INTERFACE_ID_ERC165));
+        assert!(token.supports_interface(INTERFACE_ID_ERC1155));
+        assert!(token.supports_interface(INTERFACE_ID_ERC1155_METADATA_URI));
+        assert!(!token.supports_interface([0xff; 4]));
+    }
+
+    #[test]
+    fn test_mint_single_and_batch() {
+        let mut token = minted();
+        token.mint(MINTER, BOB, COINS, 20).unwrap();
+
+        assert_eq!(
+            token
+                .balance_of_batch(&[ALICE, ALICE, ALICE, BOB], &[COINS, GEMS, TROPHY, COINS])
+                .unwrap(),
+            to_u256s(&[1_000, 50, 1, 20])
+        );
+
+        let batch = token.events::<TransferBatch>();
+        assert_eq!(batch.len(), 1);
+        assert_eq!((batch[0].operator, batch[0].from), (MINTER, Address::ZERO));
+        assert_eq!(batch[0].values, to_u256s(&[1_000, 50, 1]));
+        let single = token.events::<TransferSingle>();
+        assert_eq!((single[0].to, single[0].value), (BOB, U256::from(20)));
+
+        // Verify: Only the deployer mints
+        let revert = token.mint(STRANGER, STRANGER, COINS, 1).unwrap_err();
+        assert_eq!(revert.decode::<UnauthorizedMinter>().account, STRANGER);
+        let revert = token
+            .balance_of_batch(&[ALICE], &[COINS, GEMS])
+            .unwrap_err();
+        let error = revert.decode::<ERC1155InvalidArrayLength>();
+        assert_eq!(
+            (error.idsLength, error.valuesLength),
+            (U256::from(2), U256::from(1))
+        );
+    }
+
+    #[test]
+    fn test_safe_transfer_from() {
+        let mut token = minted();
+
+        token
+            .safe_transfer_from(ALICE, ALICE, BOB, COINS, 300)
+            .unwrap();
+
+        assert_eq!(token.balance_of(ALICE, COINS), U256::from(700));
+        assert_eq!(token.balance_of(BOB, COINS), U256::from(300));
+        let event = token.events::<TransferSingle>().pop().unwrap();
+        assert_eq!((event.operator, event.from, event.to), (ALICE, ALICE, BOB));
+        assert_eq!(
+            (event.id, event.value),
+            (U256::from(COINS), U256::from(300))
+        );
+
+        let revert = token
+            .safe_transfer_from(ALICE, ALICE, BOB, GEMS, 51)
+            .unwrap_err();
+        let error = revert.decode::<ERC1155InsufficientBalance>();
+        assert_eq!(
+            (error.balance, error.needed),
+            (U256::from(50), U256::from(51))
+        );
+        assert_eq!(error.tokenId, U256::from(GEMS));
+
+        let revert = token
+            .safe_transfer_from(ALICE, ALICE, Address::ZERO, COINS, 1)
+            .unwrap_err();
+        revert.decode::<ERC1155InvalidReceiver>();
+    }
+
+    #[test]
+    fn test_safe_batch_transfer_from() {
+        let mut token = minted();
+
+        token
+            .safe_batch_transfer_from(ALICE, ALICE, BOB, &[COINS, TROPHY], &[100, 1])
+            .unwrap();
+
+        assert_eq!(
+            token
+                .balance_of_batch(&[ALICE, ALICE, BOB, BOB], &[COINS, TROPHY, COINS, TROPHY])
+                .unwrap(),
+            to_u256s(&[900, 0, 100, 1])
+        );
+        let event = token.events::<TransferBatch>().pop().unwrap();
+        assert_eq!((event.from, event.to), (ALICE, BOB));
+        assert_eq!(event.ids, to_u256s(&[COINS, TROPHY]));
+
+        // Verify: A batch is all or nothing, and its arrays must line up
+        let revert = token
+            .safe_batch_transfer_from(ALICE, ALICE, BOB, &[COINS, TROPHY], &[1, 1])
+            .unwrap_err();
+        revert.decode::<ERC1155InsufficientBalance>();
+        let revert = token
+            .safe_batch_transfer_from(ALICE, ALICE, BOB, &[COINS, GEMS], &[1])
+            .unwrap_err();
+        let error = revert.decode::<ERC1155InvalidArrayLength>();
+        assert_eq!(
+            (error.idsLength, error.valuesLength),
+            (U256::from(2), U256::from(1))
+        );
+    }
+
+    const ACCEPTS: Address = address!("a1a1a1a1a1a1a1a1a1a1a1a1a1a1a1a1a1a1a1a1");
+    const REJECTS: Address = address!("b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2");
+    const REVERTS: Address = address!("c3c3c3c3c3c3c3c3c3c3c3c3c3c3c3c3c3c3c3c3");
+
+    // Receiver contracts on the in-memory chain: one accepts both hooks, one answers with the
+    // wrong selector and one reverts with a reason of its own
+    fn register_receivers() {
+        use alloy_sol_types::{SolCall, SolValue};
+        use contract_utils::chain;
+        use receiver::IERC1155Receiver::{onERC1155BatchReceivedCall, onERC1155ReceivedCall};
+
+        chain::register(ACCEPTS, |call| {
+            // Each hook answers with its own selector
+            let selector: [u8; 4] = call.input[..4].try_into().unwrap();
+            assert!(
+                selector == onERC1155ReceivedCall::SELECTOR
+                    || selector == onERC1155BatchReceivedCall::SELECTOR
+            );
+            (true, Bytes::from(FixedBytes::from(selector).abi_encode()))
+        });
+        chain::register(REJECTS, |_| {
+            (true, Bytes::from(FixedBytes::<4>::ZERO.abi_encode()))
+        });
+        chain::register(REVERTS, |_| {
+            let reason = UnauthorizedMinter { account: STRANGER };
+            (false, Bytes::from(reason.abi_encode()))
+        });
+    }
+
+    #[test]
+    fn test_transfers_to_receiver_contracts() {
+        let mut token = minted();
+        register_receivers();
+
+        token
+            .safe_transfer_from(ALICE, ALICE, ACCEPTS, COINS, 10)
+            .unwrap();
+        token
+            .safe_batch_transfer_from(ALICE, ALICE, ACCEPTS, &[COINS, GEMS], &[10, 1])
+            .unwrap();
+        assert_eq!(
+            token
+                .balance_of_batch(&[ACCEPTS, ACCEPTS], &[COINS, GEMS])
+                .unwrap(),
+            to_u256s(&[20, 1])
+        );
+
+        // Verify: A wrong answer reverts with the receiver, a revert bubbles up its reason
+        for receiver in [REJECTS, REVERTS] {
+            let single = token
+                .safe_transfer_from(ALICE, ALICE, receiver, COINS, 1)
+                .unwrap_err();
+            let batch = token
+                .safe_batch_transfer_from(ALICE, ALICE, receiver, &[COINS, GEMS], &[1, 1])
+                .unwrap_err();
+            for revert in [single, batch] {
+                if receiver == REJECTS {
+                    assert_eq!(revert.decode::<ERC1155InvalidReceiver>().receiver, REJECTS);
+                } else {
+                    assert_eq!(revert.decode::<UnauthorizedMinter>().account, STRANGER);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_mint_batch_to_receiver_contracts() {
+        let mut token = minted();
+        register_receivers();
+
+        token
+            .mint_batch(MINTER, ACCEPTS, &[COINS, GEMS], &[5, 5])
+            .unwrap();
+        assert_eq!(token.balance_of(ACCEPTS, GEMS), U256::from(5));
+
+        let revert = token
+            .mint_batch(MINTER, REJECTS, &[COINS, GEMS], &[5, 5])
+            .unwrap_err();
+        assert_eq!(revert.decode::<ERC1155InvalidReceiver>().receiver, REJECTS);
+        let revert = token
+            .mint_batch(MINTER, REVERTS, &[COINS, GEMS], &[5, 5])
+            .unwrap_err();
+        assert_eq!(revert.decode::<UnauthorizedMinter>().account, STRANGER);
+    }
+
+    #[test]
+    fn test_operator_approval() {
+        let mut token = minted();
+
+        let revert = token
+            .safe_transfer_from(OPERATOR, ALICE, BOB, COINS, 1)
+            .unwrap_err();
+        let error = revert.decode::<ERC1155MissingApprovalForAll>();
+        assert_eq!((error.operator, error.owner), (OPERATOR, ALICE));
+
+        token.set_approval_for_all(ALICE, OPERATOR, true).unwrap();
+        assert!(token.is_approved_for_all(ALICE, OPERATOR));
+        let event = token.events::<ApprovalForAll>().pop().unwrap();
+        assert_eq!((event.account, event.operator), (ALICE, OPERATOR));
+
+        // Execute: The operator moves Alice's items, and is recorded as the operator
+        token
+            .safe_batch_transfer_from(OPERATOR, ALICE, BOB, &[GEMS], &[10])
+            .unwrap();
+        assert_eq!(token.balance_of(BOB, GEMS), U256::from(10));
+        let event = token.events::<TransferSingle>().pop().unwrap();
+        assert_eq!(event.operator, OPERATOR);
+
+        token.set_approval_for_all(ALICE, OPERATOR, false).unwrap();
+        assert!(token
+            .safe_transfer_from(OPERATOR, ALICE, BOB, GEMS, 1)
+            .is_err());
+
+        let revert = token
+            .set_approval_for_all(ALICE, Address::ZERO, true)
+            .unwrap_err();
+        revert.decode::<ERC1155InvalidOperator>();
+    }
+
+    #[test]
+    fn test_burn() {
+        let mut token = minted();
+
+        token.burn(ALICE, ALICE, COINS, 400).unwrap();
+        assert_eq!(token.balance_of(ALICE, COINS), U256::from(600));
+        let event = token.events::<TransferSingle>().pop().unwrap();
+        assert_eq!(event.to, Address::ZERO);
+
+        let revert = token.burn(STRANGER, ALICE, COINS, 1).unwrap_err();
+        revert.decode::<ERC1155MissingApprovalForAll>();
+        let revert = token.burn(ALICE, ALICE, TROPHY, 2).unwrap_err();
+        revert.decode::<ERC1155InsufficientBalance>();
+    }
+}
@@ -0,0 +1,259 @@
+//! `onERC1155Received` and `onERC1155BatchReceived` checks for transfers to contracts.
+//!
+//! The checks make an external call, so they are written against [`CallContract`] rather than
+//! `SharedAPI`. Every `SharedAPI` context implements it, and host tests implement it to
+//! stand in for receiver contracts.
+
+use alloc::vec::Vec;
+use alloy_sol_types::{sol, SolCall};
+use contract_utils::CallContract;
+use fluentbase_sdk::{Address, Bytes, U256};
+
+sol! {
+    interface IERC1155Receiver {
+        function onERC1155Received(
+            address operator,
+            address from,
+            uint256 id,
+            uint256 value,
+            bytes data
+        ) external returns (bytes4);
+
+        function onERC1155BatchReceived(
+            address operator,
+            address from,
+            uint256[] ids,
+            uint256[] values,
+            bytes data
+        ) external returns (bytes4);
+    }
+}
+
+/// Why a receiver did not accept a transfer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ReceiverError {
+    /// The receiver returned something other than the hook's selector, or reverted without
+    /// a reason.
+    Rejected,
+    /// The receiver reverted with this reason.
+    Reverted(Bytes),
+}
+
+/// Checks that `to` accepts a single transfer, the same way OpenZeppelin's `ERC1155Utils` does.
+///
+/// Accounts without code always accept.
+pub fn check_on_erc1155_received<SDK: CallContract>(
+    sdk: &mut SDK,
+    operator: Address,
+    from: Address,
+    to: Address,
+    id: U256,
+    value: U256,
+    data: Bytes,
+) -> Result<(), ReceiverError> {
+    check_acceptance(
+        sdk,
+        to,
+        IERC1155Receiver::onERC1155ReceivedCall {
+            operator,
+            from,
+            id,
+            value,
+            data,
+        },
+    )
+}
+
+/// Checks that `to` accepts a batch transfer. Accounts without code always accept.
+pub fn check_on_erc1155_batch_received<SDK: CallContract>(
+    sdk: &mut SDK,
+    operator: Address,
+    from: Address,
+    to: Address,
+    ids: Vec<U256>,
+    values: Vec<U256>,
+    data: Bytes,
+) -> Result<(), ReceiverError> {
+    check_acceptance(
+        sdk,
+        to,
+        IERC1155Receiver::onERC1155BatchReceivedCall {
+            operator,
+            from,
+            ids,
+            values,
+            data,
+        },
+    )
+}
+
+// Both hooks accept by returning their own selector
+fn check_acceptance<SDK: CallContract, C: SolCall>(
+    sdk: &mut SDK,
+    to: Address,
+    call: C,
+) -> Result<(), ReceiverError> {
+    if !sdk.has_code(to) {
+        return Ok(());
+    }
+
+    let (success, output) = sdk.call_contract(to, &call.abi_encode(), None);
+    if !success {
+        return if output.is_empty() {
+            Err(ReceiverError::Rejected)
+        } else {
+            Err(ReceiverError::Reverted(output))
+        };
+    }
+
+    // Only the first word matters: the selector, left-aligned like any `bytes4`
+    if output.len() >= 32 && output[..4] == C::SELECTOR && output[4..32].iter().all(|b| *b == 0) {
+        Ok(())
+    } else {
+        Err(ReceiverError::Rejected)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_sol_types::{Revert, SolError, SolValue};
+    use fluentbase_sdk::{address, FixedBytes};
+
+    const OPERATOR: Address = address!("1111111111111111111111111111111111111111");
+    const FROM: Address = address!("2222222222222222222222222222222222222222");
+    const EOA: Address = address!("3333333333333333333333333333333333333333");
+    const RECEIVER: Address = address!("cccccccccccccccccccccccccccccccccccccccc");
+
+    /// A receiver contract, reduced to how it answers the hooks.
+    enum Receiver {
+        Accepts,
+        /// Answers both hooks with the single-transfer selector.
+        ReturnsSingleSelector,
+        RevertsWithReason,
+        RevertsSilently,
+    }
+
+    struct MockChain {
+        receiver: Receiver,
+        inputs: Vec<Bytes>,
+    }
+
+    impl CallContract for MockChain {
+        fn call_contract(
+            &mut self,
+            target: Address,
+            input: &[u8],
+            _fuel_limit: Option<u64>,
+        ) -> (bool, Bytes) {
+            assert_eq!(target, RECEIVER);
+            self.inputs.push(Bytes::copy_from_slice(input));
+
+            let selector: [u8; 4] = input[..4].try_into().unwrap();
+            match self.receiver {
+                Receiver::Accepts => (true, Bytes::from(FixedBytes(selector).abi_encode())),
+                Receiver::ReturnsSingleSelector => {
+                    let single = IERC1155Receiver::onERC1155ReceivedCall::SELECTOR;
+                    (true, Bytes::from(FixedBytes(single).abi_encode()))
+                }
+                Receiver::RevertsWithReason => (
+                    false,
+                    Bytes::from(Revert::from("not accepted").abi_encode()),
+                ),
+                Receiver::RevertsSilently => (false, Bytes::new()),
+            }
+        }
+
+        fn has_code(&self, account: Address) -> bool {
+            account == RECEIVER
+        }
+
+        fn send_native(
+            &mut self,
+            _to: Address,
+            _amount: U256,
+            _fuel_limit: Option<u64>,
+        ) -> (bool, Bytes) {
+            unreachable!("receiver checks never send native value")
+        }
+    }
+
+    fn chain(receiver: Receiver) -> MockChain {
+        MockChain {
+            receiver,
+            inputs: Vec::new(),
+        }
+    }
+
+    fn single(chain: &mut MockChain, to: Address) -> Result<(), ReceiverError> {
+        check_on_erc1155_received(
+            chain,
+            OPERATOR,
+            FROM,
+            to,
+            U256::from(7),
+            U256::from(50),
+            Bytes::from_static(b"hello"),
+        )
+    }
+
+    fn batch(chain: &mut MockChain, to: Address) -> Result<(), ReceiverError> {
+        check_on_erc1155_batch_received(
+            chain,
+            OPERATOR,
+            FROM,
+            to,
+            vec![U256::from(1), U256::from(2)],
+            vec![U256::from(10), U256::from(20)],
+            Bytes::new(),
+        )
+    }
+
+    #[test]
+    fn test_accepting_receiver_gets_call_details() {
+        let mut chain = chain(Receiver::Accepts);
+
+        assert_eq!(single(&mut chain, RECEIVER), Ok(()));
+        assert_eq!(batch(&mut chain, RECEIVER), Ok(()));
+
+        let call = IERC1155Receiver::onERC1155ReceivedCall::abi_decode(&chain.inputs[0]).unwrap();
+        assert_eq!((call.operator, call.from), (OPERATOR, FROM));
+        assert_eq!((call.id, call.value), (U256::from(7), U256::from(50)));
+        assert_eq!(&call.data[..], b"hello");
+
+        let call =
+            IERC1155Receiver::onERC1155BatchReceivedCall::abi_decode(&chain.inputs[1]).unwrap();
+        assert_eq!(call.ids, vec![U256::from(1), U256::from(2)]);
+        assert_eq!(call.values, vec![U256::from(10), U256::from(20)]);
+    }
+
+    #[test]
+    fn test_account_without_code_is_not_called() {
+        let mut chain = chain(Receiver::RevertsSilently);
+
+        assert_eq!(single(&mut chain, EOA), Ok(()));
+        assert_eq!(batch(&mut chain, EOA), Ok(()));
+        assert!(chain.inputs.is_empty());
+    }
+
+    #[test]
+    fn test_rejections() {
+        // Verify: Each hook must answer with its own selector
+        let mut wrong_selector = chain(Receiver::ReturnsSingleSelector);
+        assert_eq!(single(&mut wrong_selector, RECEIVER), Ok(()));
+        assert_eq!(
+            batch(&mut wrong_selector, RECEIVER),
+            Err(ReceiverError::Rejected)
+        );
+
+        let mut silent = chain(Receiver::RevertsSilently);
+        assert_eq!(single(&mut silent, RECEIVER), Err(ReceiverError::Rejected));
+
+        // Verify: A reason is kept so the token can bubble it up
+        let mut reverting = chain(Receiver::RevertsWithReason);
+        let Err(ReceiverError::Reverted(reason)) = batch(&mut reverting, RECEIVER) else {
+            panic!("expected a revert reason");
+        };
+        assert_eq!(Revert::abi_decode(&reason).unwrap().reason, "not accepted");
+    }
+}
@@ -0,0 +1,165 @@
+//! Host test harness for the multi-token.
+//!
+//! [`MultiTokenHarness`] wraps the shared `contract_utils::testing::Harness`, which owns an
+//! `ERC1155<ChainContext<HostTestingContext>>`, calls it through the router like a transaction
+//! would, and keeps every emitted log. This module only adds one typed method per router
+//! entrypoint.
+
+use crate::*;
+use contract_utils::testing::{ChainContext, Harness, HostContract};
+use core::ops::{Deref, DerefMut};
+use fluentbase_sdk::{address, codec::Encoder};
+use fluentbase_testing::HostTestingContext;
+use std::string::ToString;
+
+pub use contract_utils::testing::{CallResult, Revert};
+
+pub const TOKEN_ADDRESS: Address = address!("aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa");
+
+impl HostContract for ERC1155<ChainContext<HostTestingContext>> {
+    fn with_host(sdk: HostTestingContext) -> Self {
+        Self::new(ChainContext::new(sdk))
+    }
+
+    fn host(&self) -> &HostTestingContext {
+        &self.sdk
+    }
+
+    fn host_mut(&mut self) -> &mut HostTestingContext {
+        &mut self.sdk
+    }
+
+    fn run_deploy(&mut self) {
+        self.deploy();
+    }
+
+    fn run_main(&mut self) {
+        self.main();
+    }
+}
+
+pub struct MultiTokenHarness(Harness<ERC1155<ChainContext<HostTestingContext>>>);
+
+impl Deref for MultiTokenHarness {
+    type Target = Harness<ERC1155<ChainContext<HostTestingContext>>>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for MultiTokenHarness {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl MultiTokenHarness {
+    /// Deploys through the constructor, as `deployer`.
+    pub fn deploy(deployer: Address, uri: &str) -> Self {
+        let input = ConstructorCall::new((uri.to_string(),)).encode();
+        Self(Harness::deploy(TOKEN_ADDRESS, deployer, input))
+    }
+
+    // Typed calls, one per router entrypoint
+
+    pub fn supports_interface(&mut self, interface_id: [u8; 4]) -> bool {
+        let input = SupportsInterfaceCall::new((FixedBytes(interface_id),)).encode();
+        let output = self.call(Address::ZERO, input).unwrap();
+        SupportsInterfaceReturn::decode(&&output[..]).unwrap().0 .0
+    }
+
+    pub fn balance_of(&mut self, account: Address, id: u64) -> U256 {
+        let input = BalanceOfCall::new((account, U256::from(id))).encode();
+        let output = self.call(Address::ZERO, input).unwrap();
+        BalanceOfReturn::decode(&&output[..]).unwrap().0 .0
+    }
+
+    pub fn balance_of_batch(&mut self, accounts: &[Address], ids: &[u64]) -> CallResult<Vec<U256>> {
+        let input = BalanceOfBatchCall::new((accounts.to_vec(), to_u256s(ids))).encode();
+        let output = self.call(Address::ZERO, input)?;
+        Ok(BalanceOfBatchReturn::decode(&&output[..]).unwrap().0 .0)
+    }
+
+    pub fn set_approval_for_all(
+        &mut self,
+        account: Address,
+        operator: Address,
+        approved: bool,
+    ) -> CallResult<()> {
+        let input = SetApprovalForAllCall::new((operator, approved)).encode();
+        self.call(account, input).map(|_| ())
+    }
+
+    pub fn is_approved_for_all(&mut self, account: Address, operator: Address) -> bool {
+        let input = IsApprovedForAllCall::new((account, operator)).encode();
+        let output = self.call(Address::ZERO, input).unwrap();
+        IsApprovedForAllReturn::decode(&&output[..]).unwrap().0 .0
+    }
+
+    pub fn safe_transfer_from(
+        &mut self,
+        caller: Address,
+        from: Address,
+        to: Address,
+        id: u64,
+        value: u64,
+    ) -> CallResult<()> {
+        let input =
+            SafeTransferFromCall::new((from, to, U256::from(id), U256::from(value), Bytes::new()))
+                .encode();
+        self.call(caller, input).map(|_| ())
+    }
+
+    pub fn safe_batch_transfer_from(
+        &mut self,
+        caller: Address,
+        from: Address,
+        to: Address,
+        ids: &[u64],
+        values: &[u64],
+    ) -> CallResult<()> {
+        let input = SafeBatchTransferFromCall::new((
+            from,
+            to,
+            to_u256s(ids),
+            to_u256s(values),
+            Bytes::new(),
+        ))
+        .encode();
+        self.call(caller, input).map(|_| ())
+    }
+
+    pub fn uri(&mut self, id: U256) -> String {
+        let output = self
+            .call(Address::ZERO, UriCall::new((id,)).encode())
+            .unwrap();
+        UriReturn::decode(&&output[..]).unwrap().0 .0
+    }
+
+    pub fn mint(&mut self, caller: Address, to: Address, id: u64, value: u64) -> CallResult<()> {
+        let input = MintCall::new((to, U256::from(id), U256::from(value), Bytes::new())).encode();
+        self.call(caller, input).map(|_| ())
+    }
+
+    pub fn mint_batch(
+        &mut self,
+        caller: Address,
+        to: Address,
+        ids: &[u64],
+        values: &[u64],
+    ) -> CallResult<()> {
+        let input =
+            MintBatchCall::new((to, to_u256s(ids), to_u256s(values), Bytes::new())).encode();
+        self.call(caller, input).map(|_| ())
+    }
+
+    pub fn burn(&mut self, caller: Address, from: Address, id: u64, value: u64) -> CallResult<()> {
+        let input = BurnCall::new((from, U256::from(id), U256::from(value))).encode();
+        self.call(caller, input).map(|_| ())
+    }
+}
+
+pub fn to_u256s(values: &[u64]) -> Vec<U256> {
+    values.iter().map(|&value| U256::from(value)).collect()
+}
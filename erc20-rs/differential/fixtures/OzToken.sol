@@ -0,0 +1,27 @@
+// SPDX-License-Identifier: MIT
+pragma solidity 0.8.23;
+
+import {ERC20} from "@openzeppelin/contracts/token/ERC20/ERC20.sol";
+import {Ownable} from "@openzeppelin/contracts/access/Ownable.sol";
+import {Ownable2Step} from "@openzeppelin/contracts/access/Ownable2Step.sol";
+
+/// Reference token for the differential tests: the same external interface as the Rust
+/// ERC20 in `src/erc20`, built only from stock OpenZeppelin v5 components.
+contract OzToken is ERC20, Ownable2Step {
+    constructor(string memory name_, string memory symbol_, uint256 initialSupply)
+        ERC20(name_, symbol_)
+        Ownable(msg.sender)
+    {
+        _mint(msg.sender, initialSupply);
+    }
+
+    function mint(address to, uint256 value) external onlyOwner returns (bool) {
+        _mint(to, value);
+        return true;
+    }
+
+    function burn(uint256 value) external returns (bool) {
+        _burn(msg.sender, value);
+        return true;
+    }
+}
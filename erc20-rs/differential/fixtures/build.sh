@@ -0,0 +1,29 @@
+#!/usr/bin/env bash
+# Regenerates OzToken.bin, the creation bytecode the differential tests deploy.
+# Needs git and solc 0.8.23 (the version pinned in ../../foundry.toml) on PATH.
+set -euo pipefail
+
+OZ_VERSION="v5.0.2"
+SOLC_VERSION="0.8.23"
+
+cd "$(dirname "$0")"
+
+if ! solc --version | grep -q "Version: ${SOLC_VERSION}+"; then
+    echo "solc ${SOLC_VERSION} is required, e.g. \`solc-select use ${SOLC_VERSION}\`" >&2
+    exit 1
+fi
+
+if [ ! -d lib/openzeppelin-contracts ]; then
+    git clone --quiet --depth 1 --branch "${OZ_VERSION}" \
+        https://github.com/OpenZeppelin/openzeppelin-contracts lib/openzeppelin-contracts
+fi
+
+out="$(mktemp -d)"
+trap 'rm -rf "$out"' EXIT
+
+solc --optimize --optimize-runs 200 --bin \
+    @openzeppelin/contracts/=lib/openzeppelin-contracts/contracts/ \
+    -o "$out" OzToken.sol
+cp "$out/OzToken.bin" OzToken.bin
+
+echo "wrote $(pwd)/OzToken.bin (OpenZeppelin ${OZ_VERSION}, solc ${SOLC_VERSION})"
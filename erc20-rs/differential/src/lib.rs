@@ -0,0 +1,207 @@
+//! Differential testing of the Rust ERC20 against OpenZeppelin's.
+//!
+//! [`OzToken`] deploys the checked-in `fixtures/OzToken.bin` (OpenZeppelin v5 `ERC20` +
+//! `Ownable2Step`) into an in-process revm, and [`RustToken`] wraps the host
+//! [`TokenHarness`]. Both implement [`Token`]: they take the same ABI calldata and report an
+//! [`Outcome`], so a test can replay one call sequence on each and compare them step by step.
+
+use alloy_primitives::{keccak256, Address, Bytes, B256, U256};
+use alloy_sol_types::SolValue;
+use erc20::testing::TokenHarness;
+use revm::{
+    db::{CacheDB, EmptyDB},
+    primitives::{self as evm, ExecutionResult, Output, TxKind},
+    Evm,
+};
+
+/// Creation bytecode of `fixtures/OzToken.sol`, hex encoded; see `fixtures/build.sh`.
+pub const FIXTURE_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/OzToken.bin");
+
+// Enough for any single token call, and gas is free anyway
+const GAS_LIMIT: u64 = 30_000_000;
+
+/// A log without its emitting address, which differs between the two environments.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LogEntry {
+    pub topics: Vec<B256>,
+    pub data: Bytes,
+}
+
+/// Everything a caller can observe from one call.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Outcome {
+    pub success: bool,
+    /// Return data on success, revert data otherwise.
+    pub output: Bytes,
+    pub logs: Vec<LogEntry>,
+}
+
+pub trait Token {
+    fn call(&mut self, caller: Address, input: &[u8]) -> Outcome;
+
+    /// Reads a raw storage slot of the token.
+    fn storage(&self, slot: U256) -> U256;
+}
+
+/// Storage slot of a Solidity `mapping` entry: `keccak256(pad32(key) ++ pad32(slot))`.
+pub fn mapping_slot(key: Address, slot: U256) -> U256 {
+    let mut preimage = [0u8; 64];
+    preimage[12..32].copy_from_slice(key.as_slice());
+    preimage[32..].copy_from_slice(&slot.to_be_bytes::<32>());
+    U256::from_be_bytes(keccak256(preimage).0)
+}
+
+/// The Rust token, run through its router on the host.
+pub struct RustToken {
+    harness: TokenHarness,
+}
+
+impl RustToken {
+    /// Deploys through the constructor, returning the constructor's logs.
+    pub fn deploy(
+        deployer: Address,
+        name: &str,
+        symbol: &str,
+        initial_supply: U256,
+    ) -> (Self, Vec<LogEntry>) {
+        let mut harness = TokenHarness::deploy(deployer, name, symbol, initial_supply);
+        let logs = take_harness_logs(&mut harness);
+        (Self { harness }, logs)
+    }
+}
+
+impl Token for RustToken {
+    fn call(&mut self, caller: Address, input: &[u8]) -> Outcome {
+        let result = self.harness.call(caller, input);
+        let logs = take_harness_logs(&mut self.harness);
+        match result {
+            Ok(output) => Outcome {
+                success: true,
+                output,
+                logs,
+            },
+            // The host keeps logs emitted before a revert, the EVM discards them
+            Err(revert) => Outcome {
+                success: false,
+                output: revert.data,
+                logs: Vec::new(),
+            },
+        }
+    }
+
+    fn storage(&self, slot: U256) -> U256 {
+        self.harness.storage(slot)
+    }
+}
+
+fn take_harness_logs(harness: &mut TokenHarness) -> Vec<LogEntry> {
+    harness
+        .take_logs()
+        .into_iter()
+        .map(|log| LogEntry {
+            topics: log.topics().to_vec(),
+            data: log.data.data,
+        })
+        .collect()
+}
+
+/// The OpenZeppelin fixture, run in revm.
+pub struct OzToken {
+    db: CacheDB<EmptyDB>,
+    address: evm::Address,
+}
+
+impl OzToken {
+    /// Deploys the fixture as `deployer`, returning the constructor's logs.
+    pub fn deploy(
+        deployer: Address,
+        name: &str,
+        symbol: &str,
+        initial_supply: U256,
+    ) -> (Self, Vec<LogEntry>) {
+        let mut input = load_fixture();
+        input.extend((name.to_string(), symbol.to_string(), initial_supply).abi_encode_params());
+
+        let mut db = CacheDB::new(EmptyDB::default());
+        match transact(&mut db, deployer, TxKind::Create, input) {
+            ExecutionResult::Success {
+                output: Output::Create(_, Some(address)),
+                logs,
+                ..
+            } => (Self { db, address }, logs.iter().map(to_log_entry).collect()),
+            result => panic!("fixture deployment failed: {result:?}"),
+        }
+    }
+}
+
+impl Token for OzToken {
+    fn call(&mut self, caller: Address, input: &[u8]) -> Outcome {
+        let result = transact(
+            &mut self.db,
+            caller,
+            TxKind::Call(self.address),
+            input.to_vec(),
+        );
+        match result {
+            ExecutionResult::Success { output, logs, .. } => Outcome {
+                success: true,
+                output: Bytes::copy_from_slice(output.data()),
+                logs: logs.iter().map(to_log_entry).collect(),
+            },
+            ExecutionResult::Revert { output, .. } => Outcome {
+                success: false,
+                output: Bytes::copy_from_slice(&output),
+                logs: Vec::new(),
+            },
+            ExecutionResult::Halt { reason, .. } => panic!("fixture halted: {reason:?}"),
+        }
+    }
+
+    fn storage(&self, slot: U256) -> U256 {
+        let slot = evm::U256::from_be_bytes(slot.to_be_bytes::<32>());
+        let value = self
+            .db
+            .accounts
+            .get(&self.address)
+            .and_then(|account| account.storage.get(&slot).copied())
+            .unwrap_or_default();
+        U256::from_be_bytes(value.to_be_bytes::<32>())
+    }
+}
+
+/// Reads the fixture, panicking with regeneration steps if it has not been built.
+pub fn load_fixture() -> Vec<u8> {
+    let hex = std::fs::read_to_string(FIXTURE_PATH).unwrap_or_else(|_| {
+        panic!(
+            "{FIXTURE_PATH} is missing; run `fixtures/build.sh` (needs solc 0.8.23) and commit it"
+        )
+    });
+    alloy_primitives::hex::decode(hex.trim()).expect("fixture is not valid hex")
+}
+
+// revm pulls in its own alloy-primitives, so addresses cross over as plain bytes
+fn transact(
+    db: &mut CacheDB<EmptyDB>,
+    caller: Address,
+    kind: TxKind,
+    input: Vec<u8>,
+) -> ExecutionResult {
+    let mut evm = Evm::builder()
+        .with_db(db)
+        .modify_tx_env(|tx| {
+            tx.caller = evm::Address::from(caller.into_array());
+            tx.transact_to = kind;
+            tx.data = input.into();
+            tx.gas_limit = GAS_LIMIT;
+            tx.gas_price = evm::U256::ZERO;
+        })
+        .build();
+    evm.transact_commit().expect("revm rejected the transaction")
+}
+
+fn to_log_entry(log: &evm::Log) -> LogEntry {
+    LogEntry {
+        topics: log.topics().iter().map(|topic| B256::from(topic.0)).collect(),
+        data: Bytes::copy_from_slice(&log.data.data),
+    }
+}
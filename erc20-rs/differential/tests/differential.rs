@@ -0,0 +1,209 @@
+//! Replays random call sequences on the Rust token and the OpenZeppelin fixture.
+//!
+//! Every call must produce the same return or revert data and the same logs on both, and
+//! afterwards the getters and the raw `_balances`, `_allowances` and `_totalSupply` slots must
+//! agree. The Rust token is built with the default layout, which matches a plain
+//! OpenZeppelin `ERC20` slot for slot.
+
+use alloy_primitives::{address, Address, U256};
+use alloy_sol_types::{sol, SolCall};
+use erc20_differential::{mapping_slot, OzToken, RustToken, Token};
+use proptest::prelude::*;
+
+sol! {
+    interface IToken {
+        function name() external view returns (string);
+        function symbol() external view returns (string);
+        function decimals() external view returns (uint8);
+        function totalSupply() external view returns (uint256);
+        function balanceOf(address account) external view returns (uint256);
+        function allowance(address owner, address spender) external view returns (uint256);
+        function transfer(address to, uint256 value) external returns (bool);
+        function approve(address spender, uint256 value) external returns (bool);
+        function transferFrom(address from, address to, uint256 value) external returns (bool);
+        function mint(address to, uint256 value) external returns (bool);
+        function burn(uint256 value) external returns (bool);
+        function owner() external view returns (address);
+        function pendingOwner() external view returns (address);
+        function transferOwnership(address newOwner) external;
+        function acceptOwnership() external;
+        function renounceOwnership() external;
+    }
+}
+
+const OWNER: Address = address!("1111111111111111111111111111111111111111");
+
+// The zero address is only ever a target, to exercise the invalid sender/receiver paths
+const CALLERS: [Address; 3] = [
+    OWNER,
+    address!("2222222222222222222222222222222222222222"),
+    address!("3333333333333333333333333333333333333333"),
+];
+const TARGETS: [Address; 4] = [CALLERS[0], CALLERS[1], CALLERS[2], Address::ZERO];
+
+const INITIAL_SUPPLY: u64 = 10_000;
+const MAX_VALUE: u64 = 6_000;
+
+// Default layout: `_balances`, `_allowances`, `_totalSupply` at slots 0, 1, 2
+const BALANCES_SLOT: U256 = U256::ZERO;
+const ALLOWANCES_SLOT: U256 = U256::from_limbs([1, 0, 0, 0]);
+const TOTAL_SUPPLY_SLOT: U256 = U256::from_limbs([2, 0, 0, 0]);
+
+#[derive(Clone, Debug)]
+struct Step {
+    caller: Address,
+    call: Call,
+}
+
+#[derive(Clone, Debug)]
+enum Call {
+    Transfer(Address, U256),
+    Approve(Address, U256),
+    TransferFrom(Address, Address, U256),
+    Mint(Address, U256),
+    Burn(U256),
+    TransferOwnership(Address),
+    AcceptOwnership,
+    RenounceOwnership,
+}
+
+impl Call {
+    fn encode(&self) -> Vec<u8> {
+        match *self {
+            Call::Transfer(to, value) => IToken::transferCall { to, value }.abi_encode(),
+            Call::Approve(spender, value) => IToken::approveCall { spender, value }.abi_encode(),
+            Call::TransferFrom(from, to, value) => {
+                IToken::transferFromCall { from, to, value }.abi_encode()
+            }
+            Call::Mint(to, value) => IToken::mintCall { to, value }.abi_encode(),
+            Call::Burn(value) => IToken::burnCall { value }.abi_encode(),
+            Call::TransferOwnership(new_owner) => {
+                IToken::transferOwnershipCall { newOwner: new_owner }.abi_encode()
+            }
+            Call::AcceptOwnership => IToken::acceptOwnershipCall {}.abi_encode(),
+            Call::RenounceOwnership => IToken::renounceOwnershipCall {}.abi_encode(),
+        }
+    }
+}
+
+fn value() -> impl Strategy<Value = U256> {
+    prop_oneof![
+        9 => (0..=MAX_VALUE).prop_map(U256::from),
+        // Infinite allowance, and an amount no balance can cover
+        1 => Just(U256::MAX),
+    ]
+}
+
+fn step_strategy() -> impl Strategy<Value = Step> {
+    let caller = || prop::sample::select(CALLERS.to_vec());
+    let target = || prop::sample::select(TARGETS.to_vec());
+    let call = prop_oneof![
+        6 => (target(), value()).prop_map(|(to, value)| Call::Transfer(to, value)),
+        4 => (target(), value()).prop_map(|(spender, value)| Call::Approve(spender, value)),
+        6 => (target(), target(), value())
+            .prop_map(|(from, to, value)| Call::TransferFrom(from, to, value)),
+        2 => (target(), value()).prop_map(|(to, value)| Call::Mint(to, value)),
+        2 => value().prop_map(Call::Burn),
+        1 => target().prop_map(Call::TransferOwnership),
+        1 => Just(Call::AcceptOwnership),
+        1 => Just(Call::RenounceOwnership),
+    ];
+    (caller(), call).prop_map(|(caller, call)| Step { caller, call })
+}
+
+/// Reads that must match after every step.
+fn observe(token: &mut impl Token) -> Vec<(String, Vec<u8>)> {
+    let mut reads = vec![
+        ("totalSupply()".to_string(), IToken::totalSupplyCall {}.abi_encode()),
+        ("owner()".to_string(), IToken::ownerCall {}.abi_encode()),
+        ("pendingOwner()".to_string(), IToken::pendingOwnerCall {}.abi_encode()),
+    ];
+    for account in TARGETS {
+        reads.push((
+            format!("balanceOf({account})"),
+            IToken::balanceOfCall { account }.abi_encode(),
+        ));
+        for spender in TARGETS {
+            reads.push((
+                format!("allowance({account}, {spender})"),
+                IToken::allowanceCall {
+                    owner: account,
+                    spender,
+                }
+                .abi_encode(),
+            ));
+        }
+    }
+
+    let mut observed: Vec<(String, Vec<u8>)> = reads
+        .into_iter()
+        .map(|(label, input)| {
+            let outcome = token.call(Address::ZERO, &input);
+            assert!(outcome.success, "{label} reverted");
+            (label, outcome.output.to_vec())
+        })
+        .collect();
+
+    let mut slots = vec![("_totalSupply".to_string(), TOTAL_SUPPLY_SLOT)];
+    for account in TARGETS {
+        slots.push((
+            format!("_balances[{account}]"),
+            mapping_slot(account, BALANCES_SLOT),
+        ));
+        for spender in TARGETS {
+            slots.push((
+                format!("_allowances[{account}][{spender}]"),
+                mapping_slot(spender, mapping_slot(account, ALLOWANCES_SLOT)),
+            ));
+        }
+    }
+    observed.extend(slots.into_iter().map(|(label, slot)| {
+        let value = token.storage(slot).to_be_bytes::<32>().to_vec();
+        (label, value)
+    }));
+    observed
+}
+
+fn deploy_both() -> (RustToken, OzToken) {
+    let supply = U256::from(INITIAL_SUPPLY);
+    let (rust, rust_logs) = RustToken::deploy(OWNER, "Differential", "DIF", supply);
+    let (oz, oz_logs) = OzToken::deploy(OWNER, "Differential", "DIF", supply);
+    assert_eq!(rust_logs, oz_logs, "constructor logs differ");
+    (rust, oz)
+}
+
+#[test]
+fn test_metadata_matches() {
+    let (mut rust, mut oz) = deploy_both();
+
+    for input in [
+        IToken::nameCall {}.abi_encode(),
+        IToken::symbolCall {}.abi_encode(),
+        IToken::decimalsCall {}.abi_encode(),
+    ] {
+        assert_eq!(rust.call(OWNER, &input), oz.call(OWNER, &input));
+    }
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(64))]
+
+    #[test]
+    fn test_rust_token_matches_openzeppelin(steps in prop::collection::vec(step_strategy(), 1..40)) {
+        let (mut rust, mut oz) = deploy_both();
+        prop_assert_eq!(observe(&mut rust), observe(&mut oz), "state after deployment");
+
+        for (index, step) in steps.iter().enumerate() {
+            let input = step.call.encode();
+            let rust_outcome = rust.call(step.caller, &input);
+            let oz_outcome = oz.call(step.caller, &input);
+            prop_assert_eq!(rust_outcome, oz_outcome, "step {}: {:?}", index, step);
+
+            let rust_state = observe(&mut rust);
+            let oz_state = observe(&mut oz);
+            for (rust_read, oz_read) in rust_state.iter().zip(&oz_state) {
+                prop_assert_eq!(rust_read, oz_read, "step {}: {:?}", index, step);
+            }
+        }
+    }
+}
@@ -0,0 +1,13 @@
+//! The ERC20 from `../erc20`, deployed with the compact-codec (`fluent`) router.
+//!
+//! Calls from other Rust contracts skip Solidity ABI encoding: arguments are packed
+//! little-endian on 4-byte boundaries instead of padded to 32-byte words. Solidity callers
+//! and wallets should use the `erc20` artifact instead.
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+extern crate fluentbase_sdk;
+
+use erc20::ERC20;
+use fluentbase_sdk::basic_entrypoint;
+
+basic_entrypoint!(ERC20);
@@ -0,0 +1,517 @@
+//! Typed clients for calling tokens from other Rust contracts.
+//!
+//! [`Erc20Client`] talks to any ERC-20 through the Solidity ABI, with the calls derived by
+//! `#[client]` from [`standard::ERC20Standard`]. The rest of this module is for calling this
+//! token in whichever codec it was built with.
+//!
+//! `#[client]` generates `TokenAPIClient<SDK>`, which encodes each call, performs it through
+//! `SharedAPI::call` and decodes the return value. Its codec follows the `fluent-abi` feature
+//! exactly like the router does, so the caller must enable the same codec feature as the
+//! deployed token. `library` keeps this crate's own entrypoint out of the caller's binary:
+//!
+//! ```ignore
+//! // erc20 = { path = "../erc20", default-features = false, features = ["library", "fluent-abi"] }
+//! let mut token = TokenAPIClient::new(sdk);
+//! // Target, attached native value and gas limit come first, then the method's arguments
+//! token.transfer(TOKEN, U256::ZERO, 100_000, recipient, amount);
+//! ```
+
+use alloc::string::String;
+use alloy_sol_types::SolError;
+use contract_utils::CallContract;
+use fluentbase_sdk::{derive::client, Address, Bytes, U256};
+
+/// Token entrypoints other contracts are expected to call.
+///
+/// Parameters are named `amount` where `ERC20Interface` says `value`, so they do not clash
+/// with the native `value` the generated methods take.
+#[cfg_attr(not(feature = "fluent-abi"), client(mode = "solidity"))]
+#[cfg_attr(feature = "fluent-abi", client(mode = "fluent"))]
+pub trait TokenAPI {
+    fn name(&self) -> String;
+    fn symbol(&self) -> String;
+    fn decimals(&self) -> U256;
+    fn total_supply(&self) -> U256;
+    fn balance_of(&self, account: Address) -> U256;
+    fn transfer(&mut self, to: Address, amount: U256) -> U256;
+    fn allowance(&self, owner: Address, spender: Address) -> U256;
+    fn approve(&mut self, spender: Address, amount: U256) -> U256;
+    fn transfer_from(&mut self, from: Address, to: Address, amount: U256) -> U256;
+    fn mint(&mut self, to: Address, amount: U256) -> U256;
+    fn burn(&mut self, amount: U256) -> U256;
+}
+
+/// The ERC-20 standard in the Solidity ABI every ERC-20 speaks, for [`Erc20Client`].
+///
+/// It sits in its own module because `#[client]` generates a `XCall`/`XReturn` codec pair per
+/// method, whose names would clash with `TokenAPI`'s.
+pub mod standard {
+    use alloc::string::String;
+    use fluentbase_sdk::{derive::client, Address, U256};
+
+    /// Same selectors as the token's router, with the standard return types: `decimals` is a
+    /// `uint8` and the state-changing calls return a `bool`.
+    #[client(mode = "solidity")]
+    pub trait ERC20Standard {
+        fn name(&self) -> String;
+        fn symbol(&self) -> String;
+        fn decimals(&self) -> u8;
+        fn total_supply(&self) -> U256;
+        fn balance_of(&self, account: Address) -> U256;
+        fn allowance(&self, owner: Address, spender: Address) -> U256;
+        fn transfer(&mut self, to: Address, amount: U256) -> bool;
+        fn approve(&mut self, spender: Address, amount: U256) -> bool;
+        fn transfer_from(&mut self, from: Address, to: Address, amount: U256) -> bool;
+    }
+}
+
+/// Why an [`Erc20Client`] call failed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Erc20CallError {
+    /// The token reverted with this data.
+    Reverted(Bytes),
+    /// The call succeeded, but its output does not decode as the expected return value.
+    InvalidReturn(Bytes),
+    /// `transfer` or `transferFrom` returned `false`.
+    ReturnedFalse,
+    /// The target has no code, so the call "succeeded" without doing anything.
+    NotAContract,
+}
+
+impl Erc20CallError {
+    /// Decodes the revert data as the custom error `E`, e.g. `ERC20InsufficientBalance`.
+    pub fn revert_reason<E: SolError>(&self) -> Option<E> {
+        match self {
+            Erc20CallError::Reverted(data) => E::abi_decode(data).ok(),
+            _ => None,
+        }
+    }
+}
+
+pub type Erc20CallResult<T> = Result<T, Erc20CallError>;
+
+/// Calls any ERC-20 through the Solidity ABI, this token or a Solidity one alike.
+///
+/// Every method takes the token's address first, like the generated `TokenAPIClient`. The
+/// `safe_*` methods follow OpenZeppelin's `SafeERC20`: they accept tokens that return nothing
+/// from `transfer`/`transferFrom`, and fail on a `false` return or a target without code.
+pub struct Erc20Client<SDK> {
+    pub sdk: SDK,
+    fuel_limit: Option<u64>,
+}
+
+impl<SDK: CallContract> Erc20Client<SDK> {
+    pub fn new(sdk: SDK) -> Self {
+        Self {
+            sdk,
+            fuel_limit: None,
+        }
+    }
+
+    /// Caps the fuel each call may use; by default a call may use all that is left.
+    pub fn with_fuel_limit(mut self, fuel_limit: u64) -> Self {
+        self.fuel_limit = Some(fuel_limit);
+        self
+    }
+
+    pub fn name(&mut self, token: Address) -> Erc20CallResult<String> {
+        let output = self.call(token, standard::NameCall::new(()).encode())?;
+        let decoded = standard::NameReturn::decode(&&output[..]).map(|value| value.0 .0);
+        returned(output, decoded)
+    }
+
+    pub fn symbol(&mut self, token: Address) -> Erc20CallResult<String> {
+        let output = self.call(token, standard::SymbolCall::new(()).encode())?;
+        let decoded = standard::SymbolReturn::decode(&&output[..]).map(|value| value.0 .0);
+        returned(output, decoded)
+    }
+
+    pub fn decimals(&mut self, token: Address) -> Erc20CallResult<u8> {
+        let output = self.call(token, standard::DecimalsCall::new(()).encode())?;
+        let decoded = standard::DecimalsReturn::decode(&&output[..]).map(|value| value.0 .0);
+        returned(output, decoded)
+    }
+
+    pub fn total_supply(&mut self, token: Address) -> Erc20CallResult<U256> {
+        let output = self.call(token, standard::TotalSupplyCall::new(()).encode())?;
+        let decoded = standard::TotalSupplyReturn::decode(&&output[..]).map(|value| value.0 .0);
+        returned(output, decoded)
+    }
+
+    pub fn balance_of(&mut self, token: Address, account: Address) -> Erc20CallResult<U256> {
+        let output = self.call(token, standard::BalanceOfCall::new((account,)).encode())?;
+        let decoded = standard::BalanceOfReturn::decode(&&output[..]).map(|value| value.0 .0);
+        returned(output, decoded)
+    }
+
+    pub fn allowance(
+        &mut self,
+        token: Address,
+        owner: Address,
+        spender: Address,
+    ) -> Erc20CallResult<U256> {
+        let output = self.call(
+            token,
+            standard::AllowanceCall::new((owner, spender)).encode(),
+        )?;
+        let decoded = standard::AllowanceReturn::decode(&&output[..]).map(|value| value.0 .0);
+        returned(output, decoded)
+    }
+
+    pub fn transfer(&mut self, token: Address, to: Address, amount: U256) -> Erc20CallResult<bool> {
+        let output = self.call(token, standard::TransferCall::new((to, amount)).encode())?;
+        let decoded = standard::TransferReturn::decode(&&output[..]).map(|value| value.0 .0);
+        returned(output, decoded)
+    }
+
+    pub fn approve(
+        &mut self,
+        token: Address,
+        spender: Address,
+        amount: U256,
+    ) -> Erc20CallResult<bool> {
+        let output = self.call(
+            token,
+            standard::ApproveCall::new((spender, amount)).encode(),
+        )?;
+        let decoded = standard::ApproveReturn::decode(&&output[..]).map(|value| value.0 .0);
+        returned(output, decoded)
+    }
+
+    pub fn transfer_from(
+        &mut self,
+        token: Address,
+        from: Address,
+        to: Address,
+        amount: U256,
+    ) -> Erc20CallResult<bool> {
+        let output = self.call(
+            token,
+            standard::TransferFromCall::new((from, to, amount)).encode(),
+        )?;
+        let decoded = standard::TransferFromReturn::decode(&&output[..]).map(|value| value.0 .0);
+        returned(output, decoded)
+    }
+
+    pub fn safe_transfer(
+        &mut self,
+        token: Address,
+        to: Address,
+        amount: U256,
+    ) -> Erc20CallResult<()> {
+        safe_transfer(&mut self.sdk, token, to, amount)
+    }
+
+    pub fn safe_transfer_from(
+        &mut self,
+        token: Address,
+        from: Address,
+        to: Address,
+        amount: U256,
+    ) -> Erc20CallResult<()> {
+        let input = standard::TransferFromCall::new((from, to, amount)).encode();
+        let output = call_token(&mut self.sdk, self.fuel_limit, token, &input)?;
+        let decoded = standard::TransferFromReturn::decode(&&output[..]).map(|value| value.0 .0);
+        optional_return(&self.sdk, token, output, decoded)
+    }
+
+    fn call(&mut self, token: Address, input: impl AsRef<[u8]>) -> Erc20CallResult<Bytes> {
+        call_token(&mut self.sdk, self.fuel_limit, token, input.as_ref())
+    }
+}
+
+/// [`Erc20Client::safe_transfer`] through a borrowed context, for a contract calling out
+/// through its own `sdk` field.
+pub fn safe_transfer<SDK: CallContract>(
+    sdk: &mut SDK,
+    token: Address,
+    to: Address,
+    amount: U256,
+) -> Erc20CallResult<()> {
+    let input = standard::TransferCall::new((to, amount)).encode();
+    let output = call_token(sdk, None, token, &input)?;
+    let decoded = standard::TransferReturn::decode(&&output[..]).map(|value| value.0 .0);
+    optional_return(sdk, token, output, decoded)
+}
+
+fn call_token<SDK: CallContract>(
+    sdk: &mut SDK,
+    fuel_limit: Option<u64>,
+    token: Address,
+    input: &[u8],
+) -> Erc20CallResult<Bytes> {
+    let (success, output) = sdk.call_contract(token, input, fuel_limit);
+    if success {
+        Ok(output)
+    } else {
+        Err(Erc20CallError::Reverted(output))
+    }
+}
+
+fn returned<T, E>(output: Bytes, decoded: Result<T, E>) -> Erc20CallResult<T> {
+    decoded.map_err(|_| Erc20CallError::InvalidReturn(output))
+}
+
+fn optional_return<SDK: CallContract, E>(
+    sdk: &SDK,
+    token: Address,
+    output: Bytes,
+    decoded: Result<bool, E>,
+) -> Erc20CallResult<()> {
+    // Tokens like USDT return nothing: trust that only when there is a contract to trust
+    if output.is_empty() {
+        return if sdk.has_code(token) {
+            Ok(())
+        } else {
+            Err(Erc20CallError::NotAContract)
+        };
+    }
+    match returned(output, decoded)? {
+        true => Ok(()),
+        false => Err(Erc20CallError::ReturnedFalse),
+    }
+}
+
+// Speaks the Solidity ABI to the token, so only against the default router
+#[cfg(all(test, not(feature = "fluent-abi")))]
+mod tests {
+    use super::*;
+    use crate::{testing::TokenHarness, ERC20InsufficientAllowance, ERC20InsufficientBalance};
+    use alloy_sol_types::SolValue;
+    use contract_utils::testing::{on_chain, ChainContext};
+    use fluentbase_sdk::{address, ContractContextV1};
+    use fluentbase_testing::HostTestingContext;
+    use std::collections::HashMap;
+
+    // The contract using the client, e.g. a game paying out rewards
+    const PAYER: Address = address!("5555555555555555555555555555555555555555");
+    const ALICE: Address = address!("2222222222222222222222222222222222222222");
+    const BOB: Address = address!("3333333333333333333333333333333333333333");
+
+    const RUST_TOKEN: Address = address!("aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa");
+    const SILENT_TOKEN: Address = address!("bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb");
+    const FALSE_TOKEN: Address = address!("cccccccccccccccccccccccccccccccccccccccc");
+    const NO_CODE: Address = address!("dddddddddddddddddddddddddddddddddddddddd");
+
+    /// Foreign tokens, reduced to what their `transfer`/`transferFrom` return.
+    enum MockToken {
+        /// USDT-style: succeeds without return data.
+        ReturnsNothing,
+        /// Reports failure by returning `false` instead of reverting.
+        ReturnsFalse,
+    }
+
+    /// A tiny chain: the Rust ERC20 and mocked tokens at fixed addresses, called as `PAYER`.
+    struct Chain {
+        tokens: HashMap<Address, TokenHarness>,
+        mocks: HashMap<Address, MockToken>,
+    }
+
+    impl Chain {
+        fn new() -> Self {
+            let mut tokens = HashMap::new();
+            tokens.insert(
+                RUST_TOKEN,
+                TokenHarness::deploy(PAYER, "Reward", "RWD", U256::from(1_000)),
+            );
+            let mut mocks = HashMap::new();
+            mocks.insert(SILENT_TOKEN, MockToken::ReturnsNothing);
+            mocks.insert(FALSE_TOKEN, MockToken::ReturnsFalse);
+            Self { tokens, mocks }
+        }
+
+        fn token(&mut self, address: Address) -> &mut TokenHarness {
+            self.tokens.get_mut(&address).unwrap()
+        }
+    }
+
+    impl CallContract for Chain {
+        fn call_contract(
+            &mut self,
+            target: Address,
+            input: &[u8],
+            _fuel_limit: Option<u64>,
+        ) -> (bool, Bytes) {
+            if let Some(token) = self.tokens.get_mut(&target) {
+                return match token.call(PAYER, input) {
+                    Ok(output) => (true, output),
+                    Err(revert) => (false, revert.data),
+                };
+            }
+            match self.mocks.get(&target) {
+                Some(MockToken::ReturnsNothing) => (true, Bytes::new()),
+                Some(MockToken::ReturnsFalse) => (true, Bytes::from(false.abi_encode())),
+                // Calling an account without code always succeeds, with no output
+                None => (true, Bytes::new()),
+            }
+        }
+
+        fn has_code(&self, account: Address) -> bool {
+            self.tokens.contains_key(&account) || self.mocks.contains_key(&account)
+        }
+
+        fn send_native(
+            &mut self,
+            _to: Address,
+            _amount: U256,
+            _fuel_limit: Option<u64>,
+        ) -> (bool, Bytes) {
+            unreachable!("the client never sends native value")
+        }
+    }
+
+    fn client() -> Erc20Client<Chain> {
+        Erc20Client::new(Chain::new())
+    }
+
+    #[test]
+    fn test_reads_rust_token() {
+        let mut client = client();
+
+        assert_eq!(client.name(RUST_TOKEN).unwrap(), "Reward");
+        assert_eq!(client.symbol(RUST_TOKEN).unwrap(), "RWD");
+        assert_eq!(client.decimals(RUST_TOKEN).unwrap(), 18);
+        assert_eq!(client.total_supply(RUST_TOKEN).unwrap(), U256::from(1_000));
+        assert_eq!(
+            client.balance_of(RUST_TOKEN, PAYER).unwrap(),
+            U256::from(1_000)
+        );
+    }
+
+    #[test]
+    fn test_safe_transfer_moves_rust_token_balance() {
+        let mut client = client();
+
+        client
+            .safe_transfer(RUST_TOKEN, ALICE, U256::from(300))
+            .unwrap();
+
+        assert_eq!(
+            client.balance_of(RUST_TOKEN, ALICE).unwrap(),
+            U256::from(300)
+        );
+        let transfers = client.sdk.token(RUST_TOKEN).events::<crate::Transfer>();
+        let last = transfers.last().unwrap();
+        assert_eq!((last.from, last.to), (PAYER, ALICE));
+    }
+
+    #[test]
+    fn test_safe_transfer_from_spends_allowance() {
+        let mut client = client();
+        client
+            .safe_transfer(RUST_TOKEN, ALICE, U256::from(300))
+            .unwrap();
+        client
+            .sdk
+            .token(RUST_TOKEN)
+            .approve(ALICE, PAYER, U256::from(100))
+            .unwrap();
+
+        client
+            .safe_transfer_from(RUST_TOKEN, ALICE, BOB, U256::from(60))
+            .unwrap();
+
+        assert_eq!(client.balance_of(RUST_TOKEN, BOB).unwrap(), U256::from(60));
+        assert_eq!(
+            client.allowance(RUST_TOKEN, ALICE, PAYER).unwrap(),
+            U256::from(40)
+        );
+
+        // Verify: The token's custom error comes back decodable
+        let error = client
+            .safe_transfer_from(RUST_TOKEN, ALICE, BOB, U256::from(60))
+            .unwrap_err();
+        let reason = error.revert_reason::<ERC20InsufficientAllowance>().unwrap();
+        assert_eq!(reason.allowance, U256::from(40));
+    }
+
+    #[test]
+    fn test_revert_is_decoded() {
+        let mut client = client();
+
+        let error = client
+            .transfer(RUST_TOKEN, ALICE, U256::from(5_000))
+            .unwrap_err();
+
+        let reason = error.revert_reason::<ERC20InsufficientBalance>().unwrap();
+        assert_eq!(reason.sender, PAYER);
+        assert_eq!(reason.balance, U256::from(1_000));
+        assert_eq!(reason.needed, U256::from(5_000));
+    }
+
+    #[test]
+    fn test_token_returning_nothing() {
+        let mut client = client();
+
+        // Verify: Only the safe variant tolerates the missing return value
+        client
+            .safe_transfer(SILENT_TOKEN, ALICE, U256::from(1))
+            .unwrap();
+        client
+            .safe_transfer_from(SILENT_TOKEN, ALICE, BOB, U256::from(1))
+            .unwrap();
+        assert_eq!(
+            client.transfer(SILENT_TOKEN, ALICE, U256::from(1)),
+            Err(Erc20CallError::InvalidReturn(Bytes::new()))
+        );
+    }
+
+    #[test]
+    fn test_token_returning_false() {
+        let mut client = client();
+
+        assert_eq!(
+            client.safe_transfer(FALSE_TOKEN, ALICE, U256::from(1)),
+            Err(Erc20CallError::ReturnedFalse)
+        );
+        assert_eq!(
+            client.transfer(FALSE_TOKEN, ALICE, U256::from(1)),
+            Ok(false)
+        );
+    }
+
+    #[test]
+    fn test_safe_transfer_rejects_account_without_code() {
+        let mut client = client();
+
+        assert_eq!(
+            client.safe_transfer(NO_CODE, ALICE, U256::from(1)),
+            Err(Erc20CallError::NotAContract)
+        );
+    }
+
+    #[test]
+    fn test_calls_token_contract_from_a_contract_context() {
+        // Setup: The token on the in-memory chain, holding PAYER's supply
+        let token = on_chain(TokenHarness::deploy_at(
+            RUST_TOKEN,
+            PAYER,
+            "Reward",
+            "RWD",
+            U256::from(1_000),
+        ));
+        let payer = HostTestingContext::default().with_contract_context(ContractContextV1 {
+            address: PAYER,
+            ..Default::default()
+        });
+        let mut client = Erc20Client::new(ChainContext::new(payer));
+
+        // Execute: The call goes through the `SharedAPI` implementation of `CallContract`, which
+        // the wrapper sends to the chain
+        client
+            .safe_transfer(RUST_TOKEN, ALICE, U256::from(300))
+            .unwrap();
+
+        // Verify: The token saw the paying contract as the caller
+        assert_eq!(token.borrow_mut().balance_of(ALICE), U256::from(300));
+        assert_eq!(
+            client.balance_of(RUST_TOKEN, PAYER).unwrap(),
+            U256::from(700)
+        );
+        let error = client
+            .transfer(RUST_TOKEN, ALICE, U256::from(5_000))
+            .unwrap_err();
+        let reason = error.revert_reason::<ERC20InsufficientBalance>().unwrap();
+        assert_eq!(reason.sender, PAYER);
+    }
+}
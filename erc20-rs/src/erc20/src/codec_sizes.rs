@@ -0,0 +1,68 @@
+//! Encoded sizes of the Solidity ABI and fluentbase's compact codec, side by side.
+//!
+//! Covers the arguments and return values of the entrypoints other contracts call most; the
+//! 4-byte selector is the same in both modes and left out. Run
+//! `cargo test codec_sizes -- --nocapture` to print the table.
+
+use fluentbase_sdk::{
+    address,
+    codec::{bytes::BytesMut, CompactABI, SolidityABI},
+    Address, U256,
+};
+use std::string::{String, ToString};
+
+const ALICE: Address = address!("2222222222222222222222222222222222222222");
+const BOB: Address = address!("3333333333333333333333333333333333333333");
+
+macro_rules! encoded_len {
+    ($codec:ident, $value:expr) => {{
+        let mut buf = BytesMut::new();
+        $codec::encode(&$value, &mut buf, 0).unwrap();
+        buf.len()
+    }};
+}
+
+macro_rules! row {
+    ($label:expr, $value:expr) => {
+        (
+            $label,
+            encoded_len!(SolidityABI, $value),
+            encoded_len!(CompactABI, $value),
+        )
+    };
+}
+
+fn rows() -> Vec<(&'static str, usize, usize)> {
+    let amount = U256::from(1_000_000_000_000_000_000u128);
+    let name: String = "Example Token".to_string();
+    let symbol: String = "EXT".to_string();
+
+    vec![
+        row!("balanceOf(address)", (ALICE,)),
+        row!("transfer(address,uint256)", (ALICE, amount)),
+        row!("allowance(address,address)", (ALICE, BOB)),
+        row!("approve(address,uint256)", (BOB, amount)),
+        row!("transferFrom(address,address,uint256)", (ALICE, BOB, amount)),
+        row!("mint(address,uint256)", (ALICE, amount)),
+        row!("burn(uint256)", (amount,)),
+        row!(
+            "initialize(string,string,uint256,address)",
+            (name.clone(), symbol, amount, ALICE)
+        ),
+        row!("-> uint256", (amount,)),
+        row!("-> string", (name,)),
+    ]
+}
+
+#[test]
+fn test_compact_codec_is_never_larger() {
+    println!("| call | solidity | fluent |");
+    println!("|------|---------:|-------:|");
+    for (label, solidity, fluent) in rows() {
+        println!("| `{label}` | {solidity} | {fluent} |");
+        assert!(
+            fluent <= solidity,
+            "{label}: compact encoding is {fluent} bytes, Solidity ABI {solidity}"
+        );
+    }
+}
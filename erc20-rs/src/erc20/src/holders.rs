@@ -0,0 +1,191 @@
+//! Enumerable set of the accounts holding a non-zero balance.
+//!
+//! Holders sit in a dense array, and each holder also maps to its position in it, so adding
+//! appends and removing moves the last holder into the gap: both O(1) whatever the number of
+//! holders. Removal therefore changes the order of the remaining holders.
+//!
+//! State lives in the ERC-7201 namespace `erc20.storage.Holders`:
+//!
+//! | offset | field                                     |
+//! |--------|-------------------------------------------|
+//! | 0      | number of holders                         |
+//! | 1      | index => holder                           |
+//! | 2      | holder => index + 1, zero if not a holder |
+
+use alloc::vec::Vec;
+use fluentbase_sdk::{
+    storage::{StorageAddress, StorageDescriptor, StorageMap, StorageU256},
+    Address, SharedAPI, U256,
+};
+use hex_literal::hex;
+
+/// `keccak256(abi.encode(uint256(keccak256("erc20.storage.Holders")) - 1)) & ~bytes32(uint256(0xff))`
+pub const HOLDERS_NAMESPACE_SLOT: U256 = U256::from_be_bytes(hex!(
+    "ced0d7c2ed8cb9acaa4fc390889d28ea5fed6f3ca02a14ee030be0bd3778d100"
+));
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct HolderRegistry;
+
+impl HolderRegistry {
+    fn count_accessor(&self) -> StorageU256 {
+        StorageU256::new(HOLDERS_NAMESPACE_SLOT, 0)
+    }
+
+    fn holders_accessor(&self) -> StorageMap<U256, StorageAddress> {
+        StorageMap::new(HOLDERS_NAMESPACE_SLOT + U256::from(1), 0)
+    }
+
+    fn positions_accessor(&self) -> StorageMap<Address, StorageU256> {
+        StorageMap::new(HOLDERS_NAMESPACE_SLOT + U256::from(2), 0)
+    }
+
+    pub fn count<SDK: SharedAPI>(&self, sdk: &SDK) -> U256 {
+        self.count_accessor().get(sdk)
+    }
+
+    pub fn contains<SDK: SharedAPI>(&self, sdk: &SDK, account: Address) -> bool {
+        !self.positions_accessor().entry(account).get(sdk).is_zero()
+    }
+
+    /// Up to `limit` holders starting at index `offset`; empty past the end.
+    pub fn page<SDK: SharedAPI>(&self, sdk: &SDK, offset: U256, limit: U256) -> Vec<Address> {
+        let count = self.count(sdk);
+        if offset >= count {
+            return Vec::new();
+        }
+        let end = offset.saturating_add(limit).min(count);
+
+        let mut page = Vec::new();
+        let mut index = offset;
+        while index < end {
+            page.push(self.holders_accessor().entry(index).get(sdk));
+            index += U256::from(1);
+        }
+        page
+    }
+
+    /// Keeps the set in sync with `account`'s balance going from `old` to `new`.
+    pub fn on_balance_change<SDK: SharedAPI>(
+        &self,
+        sdk: &mut SDK,
+        account: Address,
+        old: U256,
+        new: U256,
+    ) {
+        if old.is_zero() && !new.is_zero() {
+            self.add(sdk, account);
+        } else if !old.is_zero() && new.is_zero() {
+            self.remove(sdk, account);
+        }
+    }
+
+    fn add<SDK: SharedAPI>(&self, sdk: &mut SDK, account: Address) {
+        if account == Address::ZERO || self.contains(sdk, account) {
+            return;
+        }
+
+        let count = self.count(sdk);
+        self.holders_accessor().entry(count).set(sdk, account);
+        self.positions_accessor()
+            .entry(account)
+            .set(sdk, count + U256::from(1));
+        self.count_accessor().set(sdk, count + U256::from(1));
+    }
+
+    fn remove<SDK: SharedAPI>(&self, sdk: &mut SDK, account: Address) {
+        let position = self.positions_accessor().entry(account).get(sdk);
+        if position.is_zero() {
+            return;
+        }
+
+        // Move the last holder into the freed index, then drop the last index
+        let index = position - U256::from(1);
+        let last_index = self.count(sdk) - U256::from(1);
+        if index != last_index {
+            let last = self.holders_accessor().entry(last_index).get(sdk);
+            self.holders_accessor().entry(index).set(sdk, last);
+            self.positions_accessor().entry(last).set(sdk, position);
+        }
+        self.holders_accessor()
+            .entry(last_index)
+            .set(sdk, Address::ZERO);
+        self.positions_accessor()
+            .entry(account)
+            .set(sdk, U256::ZERO);
+        self.count_accessor().set(sdk, last_index);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fluentbase_sdk::address;
+    use fluentbase_testing::HostTestingContext;
+
+    const ALICE: Address = address!("1111111111111111111111111111111111111111");
+    const BOB: Address = address!("2222222222222222222222222222222222222222");
+    const CAROL: Address = address!("3333333333333333333333333333333333333333");
+
+    fn all(sdk: &HostTestingContext) -> Vec<Address> {
+        HolderRegistry.page(sdk, U256::ZERO, U256::MAX)
+    }
+
+    fn with_holders(holders: &[Address]) -> HostTestingContext {
+        let mut sdk = HostTestingContext::default();
+        for &holder in holders {
+            HolderRegistry.on_balance_change(&mut sdk, holder, U256::ZERO, U256::from(1));
+        }
+        sdk
+    }
+
+    #[test]
+    fn test_tracks_only_zero_crossings() {
+        let mut sdk = with_holders(&[ALICE, BOB]);
+
+        // Verify: Balance changes that stay non-zero or stay zero do not touch the set
+        HolderRegistry.on_balance_change(&mut sdk, ALICE, U256::from(1), U256::from(5));
+        HolderRegistry.on_balance_change(&mut sdk, CAROL, U256::ZERO, U256::ZERO);
+        assert_eq!(all(&sdk), vec![ALICE, BOB]);
+
+        HolderRegistry.on_balance_change(&mut sdk, ALICE, U256::from(5), U256::ZERO);
+        assert_eq!(all(&sdk), vec![BOB]);
+        assert!(!HolderRegistry.contains(&sdk, ALICE));
+    }
+
+    #[test]
+    fn test_removal_moves_last_holder_into_gap() {
+        let mut sdk = with_holders(&[ALICE, BOB, CAROL]);
+
+        HolderRegistry.on_balance_change(&mut sdk, ALICE, U256::from(1), U256::ZERO);
+
+        assert_eq!(all(&sdk), vec![CAROL, BOB]);
+        assert_eq!(HolderRegistry.count(&sdk), U256::from(2));
+
+        // Verify: Positions stay consistent after the swap
+        HolderRegistry.on_balance_change(&mut sdk, CAROL, U256::from(1), U256::ZERO);
+        HolderRegistry.on_balance_change(&mut sdk, BOB, U256::from(1), U256::ZERO);
+        assert_eq!(HolderRegistry.count(&sdk), U256::ZERO);
+        assert!(all(&sdk).is_empty());
+    }
+
+    #[test]
+    fn test_pages() {
+        let sdk = with_holders(&[ALICE, BOB, CAROL]);
+
+        assert_eq!(
+            HolderRegistry.page(&sdk, U256::ZERO, U256::from(2)),
+            vec![ALICE, BOB]
+        );
+        assert_eq!(
+            HolderRegistry.page(&sdk, U256::from(2), U256::from(2)),
+            vec![CAROL]
+        );
+        assert!(HolderRegistry
+            .page(&sdk, U256::from(3), U256::from(2))
+            .is_empty());
+        assert!(HolderRegistry
+            .page(&sdk, U256::ZERO, U256::ZERO)
+            .is_empty());
+    }
+}
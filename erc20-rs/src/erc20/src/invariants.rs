@@ -0,0 +1,224 @@
+//! Stateful property test: random call sequences against the token and a reference model.
+//!
+//! After every step the contract must agree with [`Model`] on whether the call reverted,
+//! every balance and allowance within the address pool, and the total supply, and the pool's
+//! balances must add up to the total supply. On failure proptest shrinks the sequence to a
+//! minimal reproduction.
+
+use crate::testing::TokenHarness;
+use fluentbase_sdk::{address, Address, U256};
+use proptest::prelude::*;
+use std::collections::HashMap;
+
+const OWNER: Address = address!("1111111111111111111111111111111111111111");
+
+const POOL: [Address; 4] = [
+    OWNER,
+    address!("2222222222222222222222222222222222222222"),
+    address!("3333333333333333333333333333333333333333"),
+    address!("4444444444444444444444444444444444444444"),
+];
+
+const INITIAL_SUPPLY: u64 = 10_000;
+
+// Large enough to regularly exceed balances and allowances
+const MAX_VALUE: u64 = 6_000;
+
+#[derive(Clone, Debug)]
+enum Op {
+    Transfer {
+        from: usize,
+        to: usize,
+        value: u64,
+    },
+    Approve {
+        owner: usize,
+        spender: usize,
+        value: u64,
+    },
+    TransferFrom {
+        spender: usize,
+        from: usize,
+        to: usize,
+        value: u64,
+    },
+    Mint {
+        caller: usize,
+        to: usize,
+        value: u64,
+    },
+    Burn {
+        from: usize,
+        value: u64,
+    },
+}
+
+fn op_strategy() -> impl Strategy<Value = Op> {
+    let account = || 0..POOL.len();
+    let value = || 0..=MAX_VALUE;
+    prop_oneof![
+        3 => (account(), account(), value())
+            .prop_map(|(from, to, value)| Op::Transfer { from, to, value }),
+        2 => (account(), account(), value())
+            .prop_map(|(owner, spender, value)| Op::Approve { owner, spender, value }),
+        3 => (account(), account(), account(), value()).prop_map(|(spender, from, to, value)| {
+            Op::TransferFrom {
+                spender,
+                from,
+                to,
+                value,
+            }
+        }),
+        1 => (account(), account(), value())
+            .prop_map(|(caller, to, value)| Op::Mint { caller, to, value }),
+        1 => (account(), value()).prop_map(|(from, value)| Op::Burn { from, value }),
+    ]
+}
+
+/// Plain-Rust ERC20 the contract is checked against.
+#[derive(Default)]
+struct Model {
+    balances: HashMap<Address, U256>,
+    allowances: HashMap<(Address, Address), U256>,
+    total_supply: U256,
+}
+
+impl Model {
+    fn deployed() -> Self {
+        let mut model = Model::default();
+        model.balances.insert(OWNER, U256::from(INITIAL_SUPPLY));
+        model.total_supply = U256::from(INITIAL_SUPPLY);
+        model
+    }
+
+    fn balance(&self, account: Address) -> U256 {
+        self.balances.get(&account).copied().unwrap_or_default()
+    }
+
+    fn allowance(&self, owner: Address, spender: Address) -> U256 {
+        self.allowances
+            .get(&(owner, spender))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    fn move_balance(&mut self, from: Address, to: Address, value: U256) {
+        let from_balance = self.balance(from);
+        self.balances.insert(from, from_balance - value);
+        let to_balance = self.balance(to);
+        self.balances.insert(to, to_balance + value);
+    }
+
+    /// Applies `op`, returning whether the contract is expected to accept it.
+    fn apply(&mut self, op: &Op) -> bool {
+        match *op {
+            Op::Transfer { from, to, value } => {
+                let (from, to, value) = (POOL[from], POOL[to], U256::from(value));
+                if self.balance(from) < value {
+                    return false;
+                }
+                self.move_balance(from, to, value);
+            }
+            Op::Approve {
+                owner,
+                spender,
+                value,
+            } => {
+                self.allowances
+                    .insert((POOL[owner], POOL[spender]), U256::from(value));
+            }
+            Op::TransferFrom {
+                spender,
+                from,
+                to,
+                value,
+            } => {
+                let (spender, from, to) = (POOL[spender], POOL[from], POOL[to]);
+                let value = U256::from(value);
+                let allowance = self.allowance(from, spender);
+                if allowance < value || self.balance(from) < value {
+                    return false;
+                }
+                self.allowances.insert((from, spender), allowance - value);
+                self.move_balance(from, to, value);
+            }
+            Op::Mint { caller, to, value } => {
+                if POOL[caller] != OWNER {
+                    return false;
+                }
+                let to_balance = self.balance(POOL[to]);
+                self.balances.insert(POOL[to], to_balance + U256::from(value));
+                self.total_supply += U256::from(value);
+            }
+            Op::Burn { from, value } => {
+                let (from, value) = (POOL[from], U256::from(value));
+                let from_balance = self.balance(from);
+                if from_balance < value {
+                    return false;
+                }
+                self.balances.insert(from, from_balance - value);
+                self.total_supply -= value;
+            }
+        }
+        true
+    }
+}
+
+fn execute(token: &mut TokenHarness, op: &Op) -> bool {
+    let result = match *op {
+        Op::Transfer { from, to, value } => {
+            token.transfer(POOL[from], POOL[to], U256::from(value))
+        }
+        Op::Approve {
+            owner,
+            spender,
+            value,
+        } => token.approve(POOL[owner], POOL[spender], U256::from(value)),
+        Op::TransferFrom {
+            spender,
+            from,
+            to,
+            value,
+        } => token.transfer_from(POOL[spender], POOL[from], POOL[to], U256::from(value)),
+        Op::Mint { caller, to, value } => token.mint(POOL[caller], POOL[to], U256::from(value)),
+        Op::Burn { from, value } => token.burn(POOL[from], U256::from(value)),
+    };
+    result.is_ok()
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(64))]
+
+    #[test]
+    fn test_token_matches_reference_model(ops in prop::collection::vec(op_strategy(), 1..40)) {
+        let mut token = TokenHarness::deploy(OWNER, "Model", "MDL", U256::from(INITIAL_SUPPLY));
+        let mut model = Model::deployed();
+
+        for (step, op) in ops.iter().enumerate() {
+            let expected = model.apply(op);
+            let accepted = execute(&mut token, op);
+            prop_assert_eq!(accepted, expected, "step {}: {:?} revert mismatch", step, op);
+
+            prop_assert_eq!(token.total_supply(), model.total_supply, "step {}: total supply", step);
+            let balances_sum = POOL
+                .iter()
+                .fold(U256::ZERO, |sum, &account| sum + token.balance_of(account));
+            prop_assert_eq!(balances_sum, model.total_supply, "step {}: supply not conserved", step);
+
+            for &account in POOL.iter() {
+                prop_assert_eq!(
+                    token.balance_of(account),
+                    model.balance(account),
+                    "step {}: balance of {}", step, account
+                );
+                for &spender in POOL.iter() {
+                    prop_assert_eq!(
+                        token.allowance(account, spender),
+                        model.allowance(account, spender),
+                        "step {}: allowance {} -> {}", step, account, spender
+                    );
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,186 @@
+//! Storage layout of the token state.
+//!
+//! All ERC20 state lives in five consecutive slots starting at [`ERC20_STORAGE_SLOT`],
+//! in the same order OpenZeppelin declares them:
+//!
+//! | offset | field          | OpenZeppelin name |
+//! |--------|----------------|-------------------|
+//! | 0      | `balances`     | `_balances`       |
+//! | 1      | `allowances`   | `_allowances`     |
+//! | 2      | `total_supply` | `_totalSupply`    |
+//! | 3      | `token_name`   | `_name`           |
+//! | 4      | `token_symbol` | `_symbol`         |
+//!
+//! Only the base slot depends on the layout mode, which is picked at compile time:
+//!
+//! * default - slot `0`, matching `ERC20` from OpenZeppelin v4 and v5.
+//! * `layout-oz-upgradeable-v4` - slot `51`, matching `ERC20Upgradeable` from
+//!   OpenZeppelin v4 (`Initializable` takes slot 0, `ContextUpgradeable.__gap` slots 1-50).
+//! * `layout-oz-erc7201` - the ERC-7201 namespace `openzeppelin.storage.ERC20` used by
+//!   `ERC20Upgradeable` from OpenZeppelin v5.
+//!
+//! Pick the mode that matches the implementation you are replacing, otherwise existing
+//! balances become invisible after the upgrade.
+//!
+//! Proxy bookkeeping follows OpenZeppelin as well. The initialized version is
+//! `Initializable._initialized` in slot 0 (`uint8`) under `layout-oz-upgradeable-v4`, and the
+//! ERC-7201 namespace `openzeppelin.storage.Initializable` (`uint64`) otherwise. The
+//! implementation always lives in the ERC-1967 slot. The constructor writes the token's own
+//! address to [`SELF_SLOT`], which stands in for the `__self` immutable of OpenZeppelin's
+//! `UUPSUpgradeable`.
+//!
+//! The owner and pending owner follow the layout mode too, assuming the token inherits
+//! `ERC20` before `Ownable2Step` as OpenZeppelin's wizard writes it:
+//!
+//! * default - slots `5` and `6`, right after `_symbol`.
+//! * `layout-oz-upgradeable-v4` - slots `101` and `151`, after `ERC20Upgradeable.__gap`
+//!   (slots 56-100) and `OwnableUpgradeable.__gap` (slots 102-150).
+//! * `layout-oz-erc7201` - the ERC-7201 namespaces `openzeppelin.storage.Ownable` and
+//!   `openzeppelin.storage.Ownable2Step`.
+
+use fluentbase_sdk::U256;
+use hex_literal::hex;
+
+pub use contract_utils::ownable::{OZ_OWNABLE2STEP_NAMESPACE_SLOT, OZ_OWNABLE_NAMESPACE_SLOT};
+
+#[cfg(all(feature = "layout-oz-upgradeable-v4", feature = "layout-oz-erc7201"))]
+compile_error!("only one storage layout feature can be enabled at a time");
+
+/// `keccak256(abi.encode(uint256(keccak256("openzeppelin.storage.ERC20")) - 1)) & ~bytes32(uint256(0xff))`
+pub const OZ_ERC20_NAMESPACE_SLOT: U256 = U256::from_be_bytes(hex!(
+    "52c63247e1f47db19d5ce0460030c497f067ca4cebf71ba98eeadabe20bace00"
+));
+
+/// `keccak256(abi.encode(uint256(keccak256("openzeppelin.storage.Initializable")) - 1)) & ~bytes32(uint256(0xff))`
+pub const OZ_INITIALIZABLE_NAMESPACE_SLOT: U256 = U256::from_be_bytes(hex!(
+    "f0c57e16840df040f15088dc2f81fe391c3923bec73e23a9662efc9c229c6a00"
+));
+
+/// `bytes32(uint256(keccak256("eip1967.proxy.implementation")) - 1)`
+pub const IMPLEMENTATION_SLOT: U256 = U256::from_be_bytes(hex!(
+    "360894a13ba1a3210667c828492db98dca3e2076cc3735a920a3ca505d382bbc"
+));
+
+/// `bytes32(uint256(keccak256("fluent.erc20.uups.self")) - 1)`. Only ever written by the
+/// constructor, so it holds the implementation's address in the implementation's storage and
+/// is empty in a proxy's.
+pub const SELF_SLOT: U256 = U256::from_be_bytes(hex!(
+    "6bb1cede624e6f8db6b1058b8c0a42e39e466bf2122166d25bda293ff63ad37a"
+));
+
+/// First slot of `ERC20Upgradeable` state in OpenZeppelin v4.
+pub const OZ_UPGRADEABLE_V4_SLOT: U256 = U256::from_limbs([51, 0, 0, 0]);
+
+#[cfg(not(any(feature = "layout-oz-upgradeable-v4", feature = "layout-oz-erc7201")))]
+pub const ERC20_STORAGE_SLOT: U256 = U256::ZERO;
+
+#[cfg(feature = "layout-oz-upgradeable-v4")]
+pub const ERC20_STORAGE_SLOT: U256 = OZ_UPGRADEABLE_V4_SLOT;
+
+#[cfg(feature = "layout-oz-erc7201")]
+pub const ERC20_STORAGE_SLOT: U256 = OZ_ERC20_NAMESPACE_SLOT;
+
+#[cfg(not(feature = "layout-oz-upgradeable-v4"))]
+pub const INITIALIZABLE_SLOT: U256 = OZ_INITIALIZABLE_NAMESPACE_SLOT;
+
+#[cfg(feature = "layout-oz-upgradeable-v4")]
+pub const INITIALIZABLE_SLOT: U256 = U256::ZERO;
+
+/// Bits of [`INITIALIZABLE_SLOT`] holding the initialized version (`uint64` in v5, `uint8` in v4).
+#[cfg(not(feature = "layout-oz-upgradeable-v4"))]
+pub const INITIALIZED_VERSION_MASK: U256 = U256::from_limbs([u64::MAX, 0, 0, 0]);
+
+#[cfg(feature = "layout-oz-upgradeable-v4")]
+pub const INITIALIZED_VERSION_MASK: U256 = U256::from_limbs([0xff, 0, 0, 0]);
+
+/// `Ownable._owner`, see the module docs.
+#[cfg(not(any(feature = "layout-oz-upgradeable-v4", feature = "layout-oz-erc7201")))]
+pub const OWNER_SLOT: U256 = U256::from_limbs([5, 0, 0, 0]);
+
+#[cfg(feature = "layout-oz-upgradeable-v4")]
+pub const OWNER_SLOT: U256 = U256::from_limbs([101, 0, 0, 0]);
+
+#[cfg(feature = "layout-oz-erc7201")]
+pub const OWNER_SLOT: U256 = OZ_OWNABLE_NAMESPACE_SLOT;
+
+/// `Ownable2Step._pendingOwner`, see the module docs.
+#[cfg(not(any(feature = "layout-oz-upgradeable-v4", feature = "layout-oz-erc7201")))]
+pub const PENDING_OWNER_SLOT: U256 = U256::from_limbs([6, 0, 0, 0]);
+
+#[cfg(feature = "layout-oz-upgradeable-v4")]
+pub const PENDING_OWNER_SLOT: U256 = U256::from_limbs([151, 0, 0, 0]);
+
+#[cfg(feature = "layout-oz-erc7201")]
+pub const PENDING_OWNER_SLOT: U256 = OZ_OWNABLE2STEP_NAMESPACE_SLOT;
+
+pub const BALANCES_OFFSET: u64 = 0;
+pub const ALLOWANCES_OFFSET: u64 = 1;
+pub const TOTAL_SUPPLY_OFFSET: u64 = 2;
+pub const NAME_OFFSET: u64 = 3;
+pub const SYMBOL_OFFSET: u64 = 4;
+
+/// Absolute slot of the field at `offset` for the active layout.
+pub fn slot(offset: u64) -> U256 {
+    ERC20_STORAGE_SLOT + U256::from(offset)
+}
+
+/// Computes an ERC-7201 namespaced slot: `keccak256(keccak256(id) - 1) & ~0xff`.
+#[cfg(test)]
+pub fn erc7201_slot(namespace: &str) -> U256 {
+    use fluentbase_sdk::keccak256;
+
+    let id = U256::from_be_bytes(keccak256(namespace.as_bytes()).0) - U256::from(1);
+    let slot = U256::from_be_bytes(keccak256(id.to_be_bytes::<32>()).0);
+    slot & !U256::from(0xff)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_namespace_slot_matches_erc7201_formula() {
+        assert_eq!(
+            erc7201_slot("openzeppelin.storage.ERC20"),
+            OZ_ERC20_NAMESPACE_SLOT
+        );
+        assert_eq!(
+            erc7201_slot("openzeppelin.storage.Initializable"),
+            OZ_INITIALIZABLE_NAMESPACE_SLOT
+        );
+        assert_eq!(
+            erc7201_slot("openzeppelin.storage.Ownable"),
+            OZ_OWNABLE_NAMESPACE_SLOT
+        );
+        assert_eq!(
+            erc7201_slot("openzeppelin.storage.Ownable2Step"),
+            OZ_OWNABLE2STEP_NAMESPACE_SLOT
+        );
+    }
+
+    #[test]
+    #[cfg(not(feature = "layout-oz-erc7201"))]
+    fn test_owner_slots_follow_erc20_state() {
+        // v4 upgradeable contracts pad `ERC20Upgradeable` and `OwnableUpgradeable` with gaps
+        let (erc20_slots, ownable_slots) = if cfg!(feature = "layout-oz-upgradeable-v4") {
+            (5 + 45, 1 + 49)
+        } else {
+            (5, 1)
+        };
+
+        assert_eq!(OWNER_SLOT, ERC20_STORAGE_SLOT + U256::from(erc20_slots));
+        assert_eq!(PENDING_OWNER_SLOT, OWNER_SLOT + U256::from(ownable_slots));
+    }
+
+    #[test]
+    fn test_implementation_slot_matches_erc1967() {
+        let hash = U256::from_be_bytes(fluentbase_sdk::keccak256("eip1967.proxy.implementation").0);
+        assert_eq!(hash - U256::from(1), IMPLEMENTATION_SLOT);
+    }
+
+    #[test]
+    fn test_self_slot_matches_its_preimage() {
+        let hash = U256::from_be_bytes(fluentbase_sdk::keccak256("fluent.erc20.uups.self").0);
+        assert_eq!(hash - U256::from(1), SELF_SLOT);
+    }
+}
@@ -0,0 +1,284 @@
+//! Recovery of foreign tokens and native funds sent to the token contract by mistake.
+//!
+//! Both recoveries pay out through an external call, so they are written against
+//! [`CallContract`] and return the event to emit instead of touching the token's storage.
+//! Access control and events are up to the caller. The token's own balance is never recoverable:
+//! tokens held by the contract itself back stream escrows.
+
+use crate::{
+    client::{self, Erc20CallError},
+    revert,
+};
+use alloy_sol_types::sol;
+use contract_utils::CallContract;
+use fluentbase_sdk::{Address, Bytes, ExitCode, SharedAPI, U256};
+
+sol! {
+    event ERC20Recovered(address indexed token, address indexed to, uint256 amount);
+    event NativeRecovered(address indexed to, uint256 amount);
+    error CannotRecoverOwnToken();
+    error InvalidRecoveryReceiver(address receiver);
+    // Same errors as OpenZeppelin's `SafeERC20` and `Address`
+    error SafeERC20FailedOperation(address token);
+    error FailedCall();
+}
+
+/// Why a recovery failed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RecoveryError {
+    /// `token` is the contract's own token.
+    OwnToken,
+    /// Funds would be sent to the zero address.
+    ZeroReceiver,
+    /// The foreign token's `transfer` failed.
+    Token(Address, Erc20CallError),
+    /// The native transfer reverted with this data.
+    Native(Bytes),
+}
+
+impl RecoveryError {
+    /// Reverts with the matching custom error, or with the callee's own revert data.
+    pub fn revert<SDK: SharedAPI>(self, sdk: &mut SDK) -> ! {
+        match self {
+            RecoveryError::OwnToken => revert(sdk, CannotRecoverOwnToken {}),
+            RecoveryError::ZeroReceiver => revert(
+                sdk,
+                InvalidRecoveryReceiver {
+                    receiver: Address::ZERO,
+                },
+            ),
+            // Bubble up the callee's reason, like Solidity does
+            RecoveryError::Token(_, Erc20CallError::Reverted(data))
+            | RecoveryError::Native(data)
+                if !data.is_empty() =>
+            {
+                sdk.write(&data);
+                sdk.exit(ExitCode::Panic)
+            }
+            RecoveryError::Token(token, _) => revert(sdk, SafeERC20FailedOperation { token }),
+            RecoveryError::Native(_) => revert(sdk, FailedCall {}),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Recovery;
+
+impl Recovery {
+    /// Sends `amount` of the foreign `token` held by `this`, the token contract, to `to`.
+    pub fn recover_erc20<SDK: CallContract>(
+        &self,
+        sdk: &mut SDK,
+        this: Address,
+        token: Address,
+        to: Address,
+        amount: U256,
+    ) -> Result<ERC20Recovered, RecoveryError> {
+        if token == this {
+            return Err(RecoveryError::OwnToken);
+        }
+        if to == Address::ZERO {
+            return Err(RecoveryError::ZeroReceiver);
+        }
+
+        client::safe_transfer(sdk, token, to, amount)
+            .map_err(|error| RecoveryError::Token(token, error))?;
+        Ok(ERC20Recovered { token, to, amount })
+    }
+
+    /// Sends `amount` of the native currency held by the token contract to `to`.
+    pub fn recover_native<SDK: CallContract>(
+        &self,
+        sdk: &mut SDK,
+        to: Address,
+        amount: U256,
+    ) -> Result<NativeRecovered, RecoveryError> {
+        if to == Address::ZERO {
+            return Err(RecoveryError::ZeroReceiver);
+        }
+
+        let (success, output) = sdk.send_native(to, amount, None);
+        if !success {
+            return Err(RecoveryError::Native(output));
+        }
+        Ok(NativeRecovered { to, amount })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ERC20InsufficientBalance;
+    use alloy_sol_types::{SolCall, SolError, SolValue};
+    use fluentbase_sdk::address;
+    use std::collections::HashMap;
+
+    // What `World` decodes of the calls the recovery makes
+    sol! {
+        function transfer(address to, uint256 value) external returns (bool);
+    }
+
+    const TOKEN_CONTRACT: Address = address!("aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa");
+    const FOREIGN_TOKEN: Address = address!("bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb");
+    const TREASURY: Address = address!("7777777777777777777777777777777777777777");
+    const REJECTS_NATIVE: Address = address!("8888888888888888888888888888888888888888");
+
+    /// The token contract's view of the outside world: a foreign ERC-20 with balances, and
+    /// native balances that receivers can refuse.
+    #[derive(Default)]
+    struct World {
+        foreign_balances: HashMap<Address, U256>,
+        native_balances: HashMap<Address, U256>,
+    }
+
+    impl World {
+        fn new() -> Self {
+            let mut world = Self::default();
+            world
+                .foreign_balances
+                .insert(TOKEN_CONTRACT, U256::from(500));
+            world
+                .native_balances
+                .insert(TOKEN_CONTRACT, U256::from(900));
+            world
+        }
+
+        fn move_balance(
+            balances: &mut HashMap<Address, U256>,
+            to: Address,
+            amount: U256,
+        ) -> Result<(), U256> {
+            let held = balances.get(&TOKEN_CONTRACT).copied().unwrap_or_default();
+            if held < amount {
+                return Err(held);
+            }
+            balances.insert(TOKEN_CONTRACT, held - amount);
+            *balances.entry(to).or_default() += amount;
+            Ok(())
+        }
+    }
+
+    impl CallContract for World {
+        fn call_contract(
+            &mut self,
+            target: Address,
+            input: &[u8],
+            _fuel_limit: Option<u64>,
+        ) -> (bool, Bytes) {
+            assert_eq!(target, FOREIGN_TOKEN, "unexpected call target");
+            let call = transferCall::abi_decode(input).unwrap();
+            match Self::move_balance(&mut self.foreign_balances, call.to, call.value) {
+                Ok(()) => (true, Bytes::from(true.abi_encode())),
+                Err(balance) => {
+                    let error = ERC20InsufficientBalance {
+                        sender: TOKEN_CONTRACT,
+                        balance,
+                        needed: call.value,
+                    };
+                    (false, Bytes::from(error.abi_encode()))
+                }
+            }
+        }
+
+        fn has_code(&self, account: Address) -> bool {
+            account == FOREIGN_TOKEN
+        }
+
+        fn send_native(
+            &mut self,
+            to: Address,
+            amount: U256,
+            _fuel_limit: Option<u64>,
+        ) -> (bool, Bytes) {
+            if to == REJECTS_NATIVE {
+                return (false, Bytes::new());
+            }
+            let success = Self::move_balance(&mut self.native_balances, to, amount).is_ok();
+            (success, Bytes::new())
+        }
+    }
+
+    #[test]
+    fn test_recovers_foreign_token() {
+        let mut world = World::new();
+
+        let event = Recovery
+            .recover_erc20(
+                &mut world,
+                TOKEN_CONTRACT,
+                FOREIGN_TOKEN,
+                TREASURY,
+                U256::from(200),
+            )
+            .unwrap();
+
+        assert_eq!(event.token, FOREIGN_TOKEN);
+        assert_eq!(event.to, TREASURY);
+        assert_eq!(event.amount, U256::from(200));
+        assert_eq!(world.foreign_balances[&TREASURY], U256::from(200));
+        assert_eq!(world.foreign_balances[&TOKEN_CONTRACT], U256::from(300));
+    }
+
+    #[test]
+    fn test_refuses_own_token() {
+        let mut world = World::new();
+
+        // Verify: Refused before any call is made, `World` would panic on this target
+        let error = Recovery
+            .recover_erc20(
+                &mut world,
+                TOKEN_CONTRACT,
+                TOKEN_CONTRACT,
+                TREASURY,
+                U256::from(1),
+            )
+            .unwrap_err();
+        assert_eq!(error, RecoveryError::OwnToken);
+    }
+
+    #[test]
+    fn test_foreign_revert_is_kept() {
+        let mut world = World::new();
+
+        let error = Recovery
+            .recover_erc20(
+                &mut world,
+                TOKEN_CONTRACT,
+                FOREIGN_TOKEN,
+                TREASURY,
+                U256::from(501),
+            )
+            .unwrap_err();
+
+        let RecoveryError::Token(token, call_error) = error else {
+            panic!("expected a token error, got {error:?}");
+        };
+        assert_eq!(token, FOREIGN_TOKEN);
+        let reason = call_error
+            .revert_reason::<ERC20InsufficientBalance>()
+            .unwrap();
+        assert_eq!(reason.balance, U256::from(500));
+    }
+
+    #[test]
+    fn test_recovers_native() {
+        let mut world = World::new();
+
+        let event = Recovery
+            .recover_native(&mut world, TREASURY, U256::from(400))
+            .unwrap();
+        assert_eq!((event.to, event.amount), (TREASURY, U256::from(400)));
+        assert_eq!(world.native_balances[&TREASURY], U256::from(400));
+
+        let error = Recovery
+            .recover_native(&mut world, REJECTS_NATIVE, U256::from(1))
+            .unwrap_err();
+        assert_eq!(error, RecoveryError::Native(Bytes::new()));
+        assert_eq!(
+            Recovery
+                .recover_native(&mut world, Address::ZERO, U256::from(1))
+                .unwrap_err(),
+            RecoveryError::ZeroReceiver
+        );
+    }
+}
@@ -0,0 +1,587 @@
+//! Token streams: a deposit that vests to the recipient every second between
+//! `start_time` and `stop_time`, in the style of Sablier v1.
+//!
+//! The deposit is escrowed on the token contract's own balance. At any moment the recipient
+//! can withdraw what has streamed so far, and either party can cancel, which pays out the
+//! streamed part to the recipient and refunds the rest to the sender. The total still owed
+//! over all streams is tracked, so the token can check its own balance covers it.
+//!
+//! Operations return a [`StreamError`] instead of reverting, and the token reverts with the
+//! matching custom error.
+//!
+//! State lives in the ERC-7201 namespace `erc20.storage.Streams`:
+//!
+//! | offset | field                        |
+//! |--------|------------------------------|
+//! | 0      | next stream id               |
+//! | 1      | stream id => [`Stream`]      |
+//! | 2      | total escrowed               |
+
+use crate::{emit_event, revert};
+use alloy_sol_types::sol;
+use fluentbase_sdk::{
+    derive::Storage,
+    storage::{StorageAddress, StorageDescriptor, StorageMap, StorageU256},
+    Address, ContextReader, SharedAPI, U256,
+};
+use hex_literal::hex;
+
+/// `keccak256(abi.encode(uint256(keccak256("erc20.storage.Streams")) - 1)) & ~bytes32(uint256(0xff))`
+pub const STREAMS_NAMESPACE_SLOT: U256 = U256::from_be_bytes(hex!(
+    "179185d6086e581252f7ebff29d6499c9c50e9a909367597cb0b92d741231700"
+));
+
+sol! {
+    event CreateStream(
+        uint256 indexed streamId,
+        address indexed sender,
+        address indexed recipient,
+        uint256 deposit,
+        uint256 startTime,
+        uint256 stopTime
+    );
+    event WithdrawFromStream(uint256 indexed streamId, address indexed recipient, uint256 amount);
+    event CancelStream(
+        uint256 indexed streamId,
+        address indexed sender,
+        address indexed recipient,
+        uint256 senderBalance,
+        uint256 recipientBalance
+    );
+    error StreamInvalidRecipient(address recipient);
+    error StreamInvalidDeposit(uint256 deposit);
+    error StreamInvalidTimes(uint256 startTime, uint256 stopTime);
+    error StreamNotFound(uint256 streamId);
+    error StreamUnauthorizedCaller(uint256 streamId, address caller);
+    error StreamZeroAmount();
+    error StreamInsufficientBalance(uint256 balance, uint256 needed);
+    error StreamEscrowNotCovered(uint256 balance, uint256 escrowed);
+}
+
+/// Why a stream operation failed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum StreamError {
+    /// The recipient is the zero address, the sender or the token contract.
+    InvalidRecipient(Address),
+    /// The deposit is zero, or too large to stream over the given times.
+    InvalidDeposit(U256),
+    /// The start time is in the past, or the stop time is not after it.
+    InvalidTimes(U256, U256),
+    NotFound(U256),
+    /// The caller of an operation on the stream is neither its sender nor its recipient.
+    UnauthorizedCaller(U256, Address),
+    ZeroAmount,
+    /// The recipient's streamed balance, and the amount they asked for.
+    InsufficientBalance(U256, U256),
+    /// The token contract's own balance, and the smaller total still owed over all streams.
+    EscrowNotCovered(U256, U256),
+}
+
+impl StreamError {
+    /// Reverts with the matching custom error.
+    pub fn revert<SDK: SharedAPI>(self, sdk: &mut SDK) -> ! {
+        match self {
+            StreamError::InvalidRecipient(recipient) => {
+                revert(sdk, StreamInvalidRecipient { recipient })
+            }
+            StreamError::InvalidDeposit(deposit) => revert(sdk, StreamInvalidDeposit { deposit }),
+            StreamError::InvalidTimes(start_time, stop_time) => revert(
+                sdk,
+                StreamInvalidTimes {
+                    startTime: start_time,
+                    stopTime: stop_time,
+                },
+            ),
+            StreamError::NotFound(stream_id) => revert(
+                sdk,
+                StreamNotFound {
+                    streamId: stream_id,
+                },
+            ),
+            StreamError::UnauthorizedCaller(stream_id, caller) => revert(
+                sdk,
+                StreamUnauthorizedCaller {
+                    streamId: stream_id,
+                    caller,
+                },
+            ),
+            StreamError::ZeroAmount => revert(sdk, StreamZeroAmount {}),
+            StreamError::InsufficientBalance(balance, needed) => {
+                revert(sdk, StreamInsufficientBalance { balance, needed })
+            }
+            StreamError::EscrowNotCovered(balance, escrowed) => {
+                revert(sdk, StreamEscrowNotCovered { balance, escrowed })
+            }
+        }
+    }
+}
+
+#[derive(Storage)]
+pub struct Stream {
+    sender: StorageAddress,
+    recipient: StorageAddress,
+    deposit: StorageU256,
+    start_time: StorageU256,
+    stop_time: StorageU256,
+    // Deposit minus everything withdrawn so far
+    remaining_balance: StorageU256,
+}
+
+/// Final split of a cancelled stream.
+pub struct CancelledStream {
+    pub sender: Address,
+    pub sender_balance: U256,
+    pub recipient: Address,
+    pub recipient_balance: U256,
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Streams;
+
+impl Streams {
+    fn next_stream_id_accessor(&self) -> StorageU256 {
+        StorageU256::new(STREAMS_NAMESPACE_SLOT, 0)
+    }
+
+    fn streams_accessor(&self) -> StorageMap<U256, Stream> {
+        StorageMap::new(STREAMS_NAMESPACE_SLOT + U256::from(1), 0)
+    }
+
+    fn total_escrowed_accessor(&self) -> StorageU256 {
+        StorageU256::new(STREAMS_NAMESPACE_SLOT + U256::from(2), 0)
+    }
+
+    /// Remaining balance of every open stream, which the token contract must hold.
+    pub fn total_escrowed<SDK: SharedAPI>(&self, sdk: &SDK) -> U256 {
+        self.total_escrowed_accessor().get(sdk)
+    }
+
+    /// Registers a stream and returns its id. Moving the deposit into escrow is up to the caller.
+    pub fn create<SDK: SharedAPI>(
+        &self,
+        sdk: &mut SDK,
+        sender: Address,
+        recipient: Address,
+        deposit: U256,
+        start_time: U256,
+        stop_time: U256,
+    ) -> Result<U256, StreamError> {
+        if recipient == Address::ZERO
+            || recipient == sender
+            || recipient == sdk.context().contract_address()
+        {
+            return Err(StreamError::InvalidRecipient(recipient));
+        }
+        if deposit.is_zero() {
+            return Err(StreamError::InvalidDeposit(deposit));
+        }
+        if start_time < U256::from(sdk.context().block_timestamp()) || stop_time <= start_time {
+            return Err(StreamError::InvalidTimes(start_time, stop_time));
+        }
+        // Keeps `deposit * elapsed` in `balance_of` from overflowing
+        if deposit > U256::MAX / (stop_time - start_time) {
+            return Err(StreamError::InvalidDeposit(deposit));
+        }
+
+        let stream_id = self.next_stream_id_accessor().get(sdk) + U256::from(1);
+        self.next_stream_id_accessor().set(sdk, stream_id);
+
+        let stream = self.streams_accessor().entry(stream_id);
+        stream.sender_accessor().set(sdk, sender);
+        stream.recipient_accessor().set(sdk, recipient);
+        stream.deposit_accessor().set(sdk, deposit);
+        stream.start_time_accessor().set(sdk, start_time);
+        stream.stop_time_accessor().set(sdk, stop_time);
+        stream.remaining_balance_accessor().set(sdk, deposit);
+        let total_escrowed = self.total_escrowed(sdk) + deposit;
+        self.total_escrowed_accessor().set(sdk, total_escrowed);
+
+        emit_event(
+            sdk,
+            CreateStream {
+                streamId: stream_id,
+                sender,
+                recipient,
+                deposit,
+                startTime: start_time,
+                stopTime: stop_time,
+            },
+        );
+        Ok(stream_id)
+    }
+
+    /// What `who` would receive if the stream ended now; zero for anyone but its two parties.
+    pub fn balance_of<SDK: SharedAPI>(
+        &self,
+        sdk: &SDK,
+        stream_id: U256,
+        who: Address,
+    ) -> Result<U256, StreamError> {
+        let stream = self.existing(sdk, stream_id)?;
+        let recipient_balance = self.recipient_balance(sdk, &stream);
+
+        Ok(if who == stream.recipient_accessor().get(sdk) {
+            recipient_balance
+        } else if who == stream.sender_accessor().get(sdk) {
+            stream.remaining_balance_accessor().get(sdk) - recipient_balance
+        } else {
+            U256::ZERO
+        })
+    }
+
+    /// Records a withdrawal of `amount` by either party and returns the recipient to pay.
+    pub fn withdraw<SDK: SharedAPI>(
+        &self,
+        sdk: &mut SDK,
+        stream_id: U256,
+        amount: U256,
+    ) -> Result<Address, StreamError> {
+        let stream = self.existing(sdk, stream_id)?;
+        self.only_parties(sdk, stream_id, &stream)?;
+
+        if amount.is_zero() {
+            return Err(StreamError::ZeroAmount);
+        }
+        let recipient_balance = self.recipient_balance(sdk, &stream);
+        if amount > recipient_balance {
+            return Err(StreamError::InsufficientBalance(recipient_balance, amount));
+        }
+
+        let remaining_balance = stream.remaining_balance_accessor().get(sdk) - amount;
+        let recipient = stream.recipient_accessor().get(sdk);
+        self.release(sdk, amount);
+        if remaining_balance.is_zero() {
+            self.clear(sdk, &stream);
+        } else {
+            stream
+                .remaining_balance_accessor()
+                .set(sdk, remaining_balance);
+        }
+
+        emit_event(
+            sdk,
+            WithdrawFromStream {
+                streamId: stream_id,
+                recipient,
+                amount,
+            },
+        );
+        Ok(recipient)
+    }
+
+    /// Ends the stream and returns how the remaining balance splits between the parties.
+    pub fn cancel<SDK: SharedAPI>(
+        &self,
+        sdk: &mut SDK,
+        stream_id: U256,
+    ) -> Result<CancelledStream, StreamError> {
+        let stream = self.existing(sdk, stream_id)?;
+        self.only_parties(sdk, stream_id, &stream)?;
+
+        let recipient_balance = self.recipient_balance(sdk, &stream);
+        let cancelled = CancelledStream {
+            sender: stream.sender_accessor().get(sdk),
+            sender_balance: stream.remaining_balance_accessor().get(sdk) - recipient_balance,
+            recipient: stream.recipient_accessor().get(sdk),
+            recipient_balance,
+        };
+        self.release(sdk, cancelled.sender_balance + cancelled.recipient_balance);
+        self.clear(sdk, &stream);
+
+        emit_event(
+            sdk,
+            CancelStream {
+                streamId: stream_id,
+                sender: cancelled.sender,
+                recipient: cancelled.recipient,
+                senderBalance: cancelled.sender_balance,
+                recipientBalance: cancelled.recipient_balance,
+            },
+        );
+        Ok(cancelled)
+    }
+
+    fn existing<SDK: SharedAPI>(&self, sdk: &SDK, stream_id: U256) -> Result<Stream, StreamError> {
+        let stream = self.streams_accessor().entry(stream_id);
+        if stream.deposit_accessor().get(sdk).is_zero() {
+            return Err(StreamError::NotFound(stream_id));
+        }
+        Ok(stream)
+    }
+
+    fn only_parties<SDK: SharedAPI>(
+        &self,
+        sdk: &SDK,
+        stream_id: U256,
+        stream: &Stream,
+    ) -> Result<(), StreamError> {
+        let caller = sdk.context().contract_caller();
+        if caller != stream.sender_accessor().get(sdk)
+            && caller != stream.recipient_accessor().get(sdk)
+        {
+            return Err(StreamError::UnauthorizedCaller(stream_id, caller));
+        }
+        Ok(())
+    }
+
+    // Streamed so far, minus what the recipient already withdrew
+    fn recipient_balance<SDK: SharedAPI>(&self, sdk: &SDK, stream: &Stream) -> U256 {
+        let now = U256::from(sdk.context().block_timestamp());
+        let start_time = stream.start_time_accessor().get(sdk);
+        let stop_time = stream.stop_time_accessor().get(sdk);
+        let deposit = stream.deposit_accessor().get(sdk);
+
+        let streamed = if now <= start_time {
+            U256::ZERO
+        } else if now >= stop_time {
+            deposit
+        } else {
+            deposit * (now - start_time) / (stop_time - start_time)
+        };
+        let withdrawn = deposit - stream.remaining_balance_accessor().get(sdk);
+        streamed - withdrawn
+    }
+
+    fn release<SDK: SharedAPI>(&self, sdk: &mut SDK, amount: U256) {
+        let total_escrowed = self.total_escrowed(sdk) - amount;
+        self.total_escrowed_accessor().set(sdk, total_escrowed);
+    }
+
+    fn clear<SDK: SharedAPI>(&self, sdk: &mut SDK, stream: &Stream) {
+        stream.sender_accessor().set(sdk, Address::ZERO);
+        stream.recipient_accessor().set(sdk, Address::ZERO);
+        stream.deposit_accessor().set(sdk, U256::ZERO);
+        stream.start_time_accessor().set(sdk, U256::ZERO);
+        stream.stop_time_accessor().set(sdk, U256::ZERO);
+        stream.remaining_balance_accessor().set(sdk, U256::ZERO);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fluentbase_sdk::{address, BlockContextV1, ContractContextV1};
+    use fluentbase_testing::HostTestingContext;
+
+    const EMPLOYER: Address = address!("1111111111111111111111111111111111111111");
+    const EMPLOYEE: Address = address!("2222222222222222222222222222222222222222");
+
+    fn at(sdk: HostTestingContext, timestamp: u64, caller: Address) -> HostTestingContext {
+        sdk.with_block_context(BlockContextV1 {
+            timestamp,
+            ..Default::default()
+        })
+        .with_contract_context(ContractContextV1 {
+            address: address!("aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa"),
+            caller,
+            ..Default::default()
+        })
+    }
+
+    // 3_000 tokens streamed over [1_000, 4_000): 1 token per second
+    fn payroll() -> (HostTestingContext, U256) {
+        let mut sdk = at(HostTestingContext::default(), 500, EMPLOYER);
+        let stream_id = Streams
+            .create(
+                &mut sdk,
+                EMPLOYER,
+                EMPLOYEE,
+                U256::from(3_000),
+                U256::from(1_000),
+                U256::from(4_000),
+            )
+            .unwrap();
+        (sdk, stream_id)
+    }
+
+    #[test]
+    fn test_balance_accrues_per_second() {
+        let (sdk, stream_id) = payroll();
+        assert_eq!(
+            Streams.balance_of(&sdk, stream_id, EMPLOYEE).unwrap(),
+            U256::ZERO
+        );
+        assert_eq!(
+            Streams.balance_of(&sdk, stream_id, EMPLOYER).unwrap(),
+            U256::from(3_000)
+        );
+
+        let sdk = at(sdk, 1_250, EMPLOYEE);
+        assert_eq!(
+            Streams.balance_of(&sdk, stream_id, EMPLOYEE).unwrap(),
+            U256::from(250)
+        );
+        assert_eq!(
+            Streams.balance_of(&sdk, stream_id, EMPLOYER).unwrap(),
+            U256::from(2_750)
+        );
+
+        let sdk = at(sdk, 9_999, EMPLOYEE);
+        assert_eq!(
+            Streams.balance_of(&sdk, stream_id, EMPLOYEE).unwrap(),
+            U256::from(3_000)
+        );
+    }
+
+    #[test]
+    fn test_partial_withdrawals() {
+        let (sdk, stream_id) = payroll();
+
+        let mut sdk = at(sdk, 1_600, EMPLOYEE);
+        assert_eq!(
+            Streams
+                .withdraw(&mut sdk, stream_id, U256::from(400))
+                .unwrap(),
+            EMPLOYEE
+        );
+        assert_eq!(
+            Streams.balance_of(&sdk, stream_id, EMPLOYEE).unwrap(),
+            U256::from(200)
+        );
+
+        let mut sdk = at(sdk, 2_000, EMPLOYEE);
+        Streams
+            .withdraw(&mut sdk, stream_id, U256::from(600))
+            .unwrap();
+        assert_eq!(
+            Streams.balance_of(&sdk, stream_id, EMPLOYEE).unwrap(),
+            U256::ZERO
+        );
+        assert_eq!(
+            Streams.balance_of(&sdk, stream_id, EMPLOYER).unwrap(),
+            U256::from(2_000)
+        );
+    }
+
+    #[test]
+    fn test_withdraw_more_than_streamed() {
+        let (sdk, stream_id) = payroll();
+
+        let mut sdk = at(sdk, 1_100, EMPLOYEE);
+        assert_eq!(
+            Streams.withdraw(&mut sdk, stream_id, U256::from(101)),
+            Err(StreamError::InsufficientBalance(
+                U256::from(100),
+                U256::from(101)
+            ))
+        );
+        assert_eq!(
+            Streams.withdraw(&mut sdk, stream_id, U256::ZERO),
+            Err(StreamError::ZeroAmount)
+        );
+    }
+
+    #[test]
+    fn test_cancel_splits_remaining_balance() {
+        let (sdk, stream_id) = payroll();
+
+        let mut sdk = at(sdk, 1_500, EMPLOYEE);
+        Streams
+            .withdraw(&mut sdk, stream_id, U256::from(300))
+            .unwrap();
+
+        let mut sdk = at(sdk, 2_000, EMPLOYER);
+        let cancelled = Streams.cancel(&mut sdk, stream_id).unwrap();
+
+        // Verify: 1_000 streamed, 300 already paid out
+        assert_eq!(cancelled.recipient, EMPLOYEE);
+        assert_eq!(cancelled.recipient_balance, U256::from(700));
+        assert_eq!(cancelled.sender, EMPLOYER);
+        assert_eq!(cancelled.sender_balance, U256::from(2_000));
+    }
+
+    #[test]
+    fn test_total_escrowed_follows_open_streams() {
+        let (sdk, stream_id) = payroll();
+        let mut sdk = at(sdk, 500, EMPLOYER);
+        let second_id = Streams
+            .create(
+                &mut sdk,
+                EMPLOYER,
+                EMPLOYEE,
+                U256::from(1_000),
+                U256::from(1_000),
+                U256::from(2_000),
+            )
+            .unwrap();
+        assert_eq!(Streams.total_escrowed(&sdk), U256::from(4_000));
+
+        let mut sdk = at(sdk, 1_500, EMPLOYEE);
+        Streams
+            .withdraw(&mut sdk, stream_id, U256::from(300))
+            .unwrap();
+        assert_eq!(Streams.total_escrowed(&sdk), U256::from(3_700));
+
+        let mut sdk = at(sdk, 1_500, EMPLOYER);
+        Streams.cancel(&mut sdk, stream_id).unwrap();
+        assert_eq!(Streams.total_escrowed(&sdk), U256::from(1_000));
+
+        let mut sdk = at(sdk, 2_000, EMPLOYEE);
+        Streams
+            .withdraw(&mut sdk, second_id, U256::from(1_000))
+            .unwrap();
+        assert_eq!(Streams.total_escrowed(&sdk), U256::ZERO);
+    }
+
+    #[test]
+    fn test_cancelled_stream_is_gone() {
+        let (sdk, stream_id) = payroll();
+
+        let mut sdk = at(sdk, 2_000, EMPLOYER);
+        Streams.cancel(&mut sdk, stream_id).unwrap();
+        assert_eq!(
+            Streams.balance_of(&sdk, stream_id, EMPLOYEE),
+            Err(StreamError::NotFound(stream_id))
+        );
+    }
+
+    #[test]
+    fn test_cancel_by_stranger() {
+        let (sdk, stream_id) = payroll();
+        let stranger = address!("6666666666666666666666666666666666666666");
+
+        let mut sdk = at(sdk, 2_000, stranger);
+        assert_eq!(
+            Streams.cancel(&mut sdk, stream_id).err(),
+            Some(StreamError::UnauthorizedCaller(stream_id, stranger))
+        );
+    }
+
+    #[test]
+    fn test_create_rejects_invalid_streams() {
+        let mut sdk = at(HostTestingContext::default(), 500, EMPLOYER);
+        let mut create = |recipient, deposit: u64, start_time: u64, stop_time: u64| {
+            Streams.create(
+                &mut sdk,
+                EMPLOYER,
+                recipient,
+                U256::from(deposit),
+                U256::from(start_time),
+                U256::from(stop_time),
+            )
+        };
+
+        assert_eq!(
+            create(EMPLOYER, 3_000, 1_000, 4_000),
+            Err(StreamError::InvalidRecipient(EMPLOYER))
+        );
+        assert_eq!(
+            create(EMPLOYEE, 0, 1_000, 4_000),
+            Err(StreamError::InvalidDeposit(U256::ZERO))
+        );
+        assert_eq!(
+            create(EMPLOYEE, 3_000, 400, 4_000),
+            Err(StreamError::InvalidTimes(
+                U256::from(400),
+                U256::from(4_000)
+            ))
+        );
+        assert_eq!(
+            create(EMPLOYEE, 3_000, 1_000, 1_000),
+            Err(StreamError::InvalidTimes(
+                U256::from(1_000),
+                U256::from(1_000)
+            ))
+        );
+    }
+}
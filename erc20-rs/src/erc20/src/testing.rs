@@ -0,0 +1,329 @@
+//! Host test harness for the token.
+//!
+//! [`TokenHarness`] wraps the shared `contract_utils::testing::Harness`, which owns an
+//! `ERC20<ChainContext<HostTestingContext>>`, calls it through the router like a transaction would,
+//! and keeps every emitted log. This module only adds one typed method per router entrypoint:
+//!
+//! ```ignore
+//! let mut token = TokenHarness::deploy(ALICE, "Token", "TKN", U256::from(1_000));
+//! token.transfer(ALICE, BOB, U256::from(10)).unwrap();
+//! assert_eq!(token.events::<Transfer>().last().unwrap().to, BOB);
+//! ```
+
+use crate::*;
+use contract_utils::testing::{ChainContext, Harness, HostContract};
+use core::ops::{Deref, DerefMut};
+use fluentbase_sdk::{address, codec::Encoder};
+use fluentbase_testing::HostTestingContext;
+use std::string::ToString;
+
+pub use contract_utils::testing::{CallResult, Revert};
+
+pub const TOKEN_ADDRESS: Address = address!("aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa");
+
+impl HostContract for ERC20<ChainContext<HostTestingContext>> {
+    fn with_host(sdk: HostTestingContext) -> Self {
+        Self::new(ChainContext::new(sdk))
+    }
+
+    fn host(&self) -> &HostTestingContext {
+        &self.sdk
+    }
+
+    fn host_mut(&mut self) -> &mut HostTestingContext {
+        &mut self.sdk
+    }
+
+    fn run_deploy(&mut self) {
+        self.deploy();
+    }
+
+    fn run_main(&mut self) {
+        self.main();
+    }
+}
+
+pub struct TokenHarness(Harness<ERC20<ChainContext<HostTestingContext>>>);
+
+impl Deref for TokenHarness {
+    type Target = Harness<ERC20<ChainContext<HostTestingContext>>>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for TokenHarness {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl Default for TokenHarness {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TokenHarness {
+    /// Harness over empty storage at [`TOKEN_ADDRESS`], with nothing deployed yet.
+    pub fn new() -> Self {
+        Self::at(TOKEN_ADDRESS)
+    }
+
+    /// Harness over empty storage at `address`, for tests with more than one token.
+    pub fn at(address: Address) -> Self {
+        Self(Harness::new(address))
+    }
+
+    /// Deploys through the constructor, as `deployer`.
+    pub fn deploy(deployer: Address, name: &str, symbol: &str, initial_supply: U256) -> Self {
+        Self::deploy_at(TOKEN_ADDRESS, deployer, name, symbol, initial_supply)
+    }
+
+    /// Like [`Self::deploy`], at `address`.
+    pub fn deploy_at(
+        address: Address,
+        deployer: Address,
+        name: &str,
+        symbol: &str,
+        initial_supply: U256,
+    ) -> Self {
+        let input = ConstructorCall::new((name.to_string(), symbol.to_string(), initial_supply))
+            .encode();
+        Self(Harness::deploy(address, deployer, input))
+    }
+
+    /// Calls `initialize` on fresh storage, as a proxy deployment would.
+    pub fn deploy_initialized(
+        caller: Address,
+        name: &str,
+        symbol: &str,
+        initial_supply: U256,
+        owner: Address,
+    ) -> Self {
+        let mut harness = Self::new();
+        harness
+            .initialize(caller, name, symbol, initial_supply, owner)
+            .expect("initialize reverted");
+        harness
+    }
+
+    // Typed calls, one per router entrypoint
+
+    pub fn name(&mut self) -> String {
+        let output = self.call(Address::ZERO, NameCall::new(()).encode()).unwrap();
+        NameReturn::decode(&&output[..]).unwrap().0 .0
+    }
+
+    pub fn symbol(&mut self) -> String {
+        let output = self.call(Address::ZERO, SymbolCall::new(()).encode()).unwrap();
+        SymbolReturn::decode(&&output[..]).unwrap().0 .0
+    }
+
+    pub fn decimals(&mut self) -> U256 {
+        let output = self.call(Address::ZERO, DecimalsCall::new(()).encode()).unwrap();
+        DecimalsReturn::decode(&&output[..]).unwrap().0 .0
+    }
+
+    pub fn total_supply(&mut self) -> U256 {
+        let output = self
+            .call(Address::ZERO, TotalSupplyCall::new(()).encode())
+            .unwrap();
+        TotalSupplyReturn::decode(&&output[..]).unwrap().0 .0
+    }
+
+    pub fn balance_of(&mut self, account: Address) -> U256 {
+        let output = self
+            .call(Address::ZERO, BalanceOfCall::new((account,)).encode())
+            .unwrap();
+        BalanceOfReturn::decode(&&output[..]).unwrap().0 .0
+    }
+
+    pub fn allowance(&mut self, owner: Address, spender: Address) -> U256 {
+        let output = self
+            .call(Address::ZERO, AllowanceCall::new((owner, spender)).encode())
+            .unwrap();
+        AllowanceReturn::decode(&&output[..]).unwrap().0 .0
+    }
+
+    pub fn transfer(&mut self, from: Address, to: Address, value: U256) -> CallResult<U256> {
+        let output = self.call(from, TransferCall::new((to, value)).encode())?;
+        Ok(TransferReturn::decode(&&output[..]).unwrap().0 .0)
+    }
+
+    pub fn approve(&mut self, owner: Address, spender: Address, value: U256) -> CallResult<U256> {
+        let output = self.call(owner, ApproveCall::new((spender, value)).encode())?;
+        Ok(ApproveReturn::decode(&&output[..]).unwrap().0 .0)
+    }
+
+    pub fn transfer_from(
+        &mut self,
+        spender: Address,
+        from: Address,
+        to: Address,
+        value: U256,
+    ) -> CallResult<U256> {
+        let output = self.call(spender, TransferFromCall::new((from, to, value)).encode())?;
+        Ok(TransferFromReturn::decode(&&output[..]).unwrap().0 .0)
+    }
+
+    pub fn mint(&mut self, caller: Address, to: Address, value: U256) -> CallResult<U256> {
+        let output = self.call(caller, MintCall::new((to, value)).encode())?;
+        Ok(MintReturn::decode(&&output[..]).unwrap().0 .0)
+    }
+
+    pub fn burn(&mut self, caller: Address, value: U256) -> CallResult<U256> {
+        let output = self.call(caller, BurnCall::new((value,)).encode())?;
+        Ok(BurnReturn::decode(&&output[..]).unwrap().0 .0)
+    }
+
+    pub fn initialize(
+        &mut self,
+        caller: Address,
+        name: &str,
+        symbol: &str,
+        initial_supply: U256,
+        owner: Address,
+    ) -> CallResult<()> {
+        let input = InitializeCall::new((
+            name.to_string(),
+            symbol.to_string(),
+            initial_supply,
+            owner,
+        ))
+        .encode();
+        self.call(caller, input).map(|_| ())
+    }
+
+    pub fn upgrade_to(&mut self, caller: Address, new_implementation: Address) -> CallResult<()> {
+        self.call(caller, UpgradeToCall::new((new_implementation,)).encode())
+            .map(|_| ())
+    }
+
+    pub fn proxiable_uuid(&mut self) -> CallResult<B256> {
+        self.call(Address::ZERO, ProxiableUuidCall::new(()).encode())
+            .map(|output| ProxiableUuidReturn::decode(&&output[..]).unwrap().0 .0)
+    }
+
+    pub fn owner(&mut self) -> Address {
+        let output = self.call(Address::ZERO, OwnerCall::new(()).encode()).unwrap();
+        OwnerReturn::decode(&&output[..]).unwrap().0 .0
+    }
+
+    pub fn pending_owner(&mut self) -> Address {
+        let output = self
+            .call(Address::ZERO, PendingOwnerCall::new(()).encode())
+            .unwrap();
+        PendingOwnerReturn::decode(&&output[..]).unwrap().0 .0
+    }
+
+    pub fn transfer_ownership(&mut self, caller: Address, new_owner: Address) -> CallResult<()> {
+        self.call(caller, TransferOwnershipCall::new((new_owner,)).encode())
+            .map(|_| ())
+    }
+
+    pub fn accept_ownership(&mut self, caller: Address) -> CallResult<()> {
+        self.call(caller, AcceptOwnershipCall::new(()).encode())
+            .map(|_| ())
+    }
+
+    pub fn renounce_ownership(&mut self, caller: Address) -> CallResult<()> {
+        self.call(caller, RenounceOwnershipCall::new(()).encode())
+            .map(|_| ())
+    }
+
+    pub fn set_transfer_limit(
+        &mut self,
+        caller: Address,
+        account: Address,
+        max_per_window: U256,
+        window_seconds: u64,
+    ) -> CallResult<()> {
+        let input = SetTransferLimitCall::new((account, max_per_window, window_seconds)).encode();
+        self.call(caller, input).map(|_| ())
+    }
+
+    pub fn available_to_transfer(&mut self, account: Address) -> U256 {
+        let output = self
+            .call(Address::ZERO, AvailableToTransferCall::new((account,)).encode())
+            .unwrap();
+        AvailableToTransferReturn::decode(&&output[..]).unwrap().0 .0
+    }
+
+    pub fn create_stream(
+        &mut self,
+        sender: Address,
+        recipient: Address,
+        deposit: U256,
+        start_time: u64,
+        stop_time: u64,
+    ) -> CallResult<U256> {
+        let input = CreateStreamCall::new((
+            recipient,
+            deposit,
+            U256::from(start_time),
+            U256::from(stop_time),
+        ))
+        .encode();
+        let output = self.call(sender, input)?;
+        Ok(CreateStreamReturn::decode(&&output[..]).unwrap().0 .0)
+    }
+
+    pub fn balance_of_stream(&mut self, stream_id: U256, who: Address) -> CallResult<U256> {
+        let output = self.call(
+            Address::ZERO,
+            BalanceOfStreamCall::new((stream_id, who)).encode(),
+        )?;
+        Ok(BalanceOfStreamReturn::decode(&&output[..]).unwrap().0 .0)
+    }
+
+    pub fn withdraw_from_stream(
+        &mut self,
+        caller: Address,
+        stream_id: U256,
+        amount: U256,
+    ) -> CallResult<()> {
+        self.call(
+            caller,
+            WithdrawFromStreamCall::new((stream_id, amount)).encode(),
+        )
+        .map(|_| ())
+    }
+
+    pub fn cancel_stream(&mut self, caller: Address, stream_id: U256) -> CallResult<()> {
+        self.call(caller, CancelStreamCall::new((stream_id,)).encode())
+            .map(|_| ())
+    }
+
+    pub fn recover_erc20(
+        &mut self,
+        caller: Address,
+        token: Address,
+        to: Address,
+        amount: U256,
+    ) -> CallResult<()> {
+        self.call(caller, RecoverErc20Call::new((token, to, amount)).encode())
+            .map(|_| ())
+    }
+
+    pub fn recover_native(&mut self, caller: Address, to: Address, amount: U256) -> CallResult<()> {
+        self.call(caller, RecoverNativeCall::new((to, amount)).encode())
+            .map(|_| ())
+    }
+
+    #[cfg(feature = "holder-registry")]
+    pub fn holder_count(&mut self) -> CallResult<U256> {
+        let output = self.call(Address::ZERO, HolderCountCall::new(()).encode())?;
+        Ok(HolderCountReturn::decode(&&output[..]).unwrap().0 .0)
+    }
+
+    #[cfg(feature = "holder-registry")]
+    pub fn holders_page(&mut self, offset: U256, limit: U256) -> CallResult<Vec<Address>> {
+        let output = self.call(
+            Address::ZERO,
+            HoldersPageCall::new((offset, limit)).encode(),
+        )?;
+        Ok(HoldersPageReturn::decode(&&output[..]).unwrap().0 .0)
+    }
+}
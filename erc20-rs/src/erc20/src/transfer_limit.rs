@@ -0,0 +1,339 @@
+//! Per-account limits on outgoing transfers.
+//!
+//! An account with a limit may send at most `max_per_window` tokens in any span of
+//! `window_seconds`, based on the block timestamp. The window rolls: there is no point in time
+//! at which the whole allowance comes back at once, so a limit of 100 per hour never lets 200
+//! out within an hour.
+//!
+//! Spending is recorded in up to [`BUCKETS`] buckets of `ceil(window_seconds / BUCKETS)`
+//! seconds each, kept in a ring of `BUCKETS + 1` entries. A transfer counts against the limit
+//! until its bucket is more than `window_seconds` in the past, so it is remembered for at
+//! most one bucket longer than the window, never shorter. A `window_seconds` of zero means the
+//! account has no limit.
+//!
+//! State lives in the ERC-7201 namespace `erc20.storage.TransferLimits`, one mapping per field:
+//!
+//! | offset | mapping                                        |
+//! |--------|------------------------------------------------|
+//! | 0      | account => max per window                      |
+//! | 1      | account => window length in seconds            |
+//! | 2      | account => ring index => bucket number         |
+//! | 3      | account => ring index => amount sent in bucket |
+
+use crate::{emit_event, revert};
+use alloy_sol_types::sol;
+use fluentbase_sdk::{
+    storage::{StorageDescriptor, StorageMap, StorageU256},
+    Address, ContextReader, SharedAPI, U256,
+};
+use hex_literal::hex;
+
+/// `keccak256(abi.encode(uint256(keccak256("erc20.storage.TransferLimits")) - 1)) & ~bytes32(uint256(0xff))`
+pub const TRANSFER_LIMITS_NAMESPACE_SLOT: U256 = U256::from_be_bytes(hex!(
+    "e37238672c76a58b7967362ea87d92e36c186058f844d6fc9ea8cc5704333300"
+));
+
+/// Most buckets a window is split into. More buckets forget old transfers closer to
+/// `window_seconds`, at the cost of more storage reads per limited transfer.
+pub const BUCKETS: u64 = 8;
+
+sol! {
+    event TransferLimitSet(address indexed account, uint256 maxPerWindow, uint64 windowSeconds);
+    error TransferLimitExceeded(address account, uint256 requested, uint256 available);
+}
+
+type Ring = StorageMap<Address, StorageMap<U256, StorageU256>>;
+
+/// Where `now` falls for a window of `window_seconds`, which must not be zero.
+struct Position {
+    /// Buckets since the epoch, at `now`.
+    bucket: u64,
+    /// Buckets a window of `window_seconds` touches, at most [`BUCKETS`].
+    span: u64,
+}
+
+impl Position {
+    fn at(now: u64, window_seconds: u64) -> Self {
+        let bucket_seconds = window_seconds.div_ceil(BUCKETS);
+        Self {
+            bucket: now / bucket_seconds,
+            span: window_seconds.div_ceil(bucket_seconds),
+        }
+    }
+
+    /// Buckets still counted at `now`: the current one and the `span` before it, since any
+    /// `window_seconds` long span ending now starts in one of them.
+    fn live(&self) -> impl Iterator<Item = u64> {
+        self.bucket.saturating_sub(self.span)..=self.bucket
+    }
+}
+
+fn ring_index(bucket: u64) -> U256 {
+    U256::from(bucket % (BUCKETS + 1))
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct TransferLimits;
+
+impl TransferLimits {
+    fn field(&self, offset: u64) -> StorageMap<Address, StorageU256> {
+        StorageMap::new(TRANSFER_LIMITS_NAMESPACE_SLOT + U256::from(offset), 0)
+    }
+
+    fn ring(&self, offset: u64) -> Ring {
+        StorageMap::new(TRANSFER_LIMITS_NAMESPACE_SLOT + U256::from(offset), 0)
+    }
+
+    fn max_per_window_accessor(&self) -> StorageMap<Address, StorageU256> {
+        self.field(0)
+    }
+
+    fn window_seconds_accessor(&self) -> StorageMap<Address, StorageU256> {
+        self.field(1)
+    }
+
+    fn bucket_number_accessor(&self) -> Ring {
+        self.ring(2)
+    }
+
+    fn bucket_amount_accessor(&self) -> Ring {
+        self.ring(3)
+    }
+
+    /// Replaces the limit for `account` and forgets what it sent so far.
+    pub fn set_limit<SDK: SharedAPI>(
+        &self,
+        sdk: &mut SDK,
+        account: Address,
+        max_per_window: U256,
+        window_seconds: u64,
+    ) {
+        self.max_per_window_accessor()
+            .entry(account)
+            .set(sdk, max_per_window);
+        self.window_seconds_accessor()
+            .entry(account)
+            .set(sdk, U256::from(window_seconds));
+        for index in 0..=BUCKETS {
+            self.bucket_amount_accessor()
+                .entry(account)
+                .entry(U256::from(index))
+                .set(sdk, U256::ZERO);
+        }
+
+        emit_event(
+            sdk,
+            TransferLimitSet {
+                account,
+                maxPerWindow: max_per_window,
+                windowSeconds: window_seconds,
+            },
+        );
+    }
+
+    /// Amount `account` can still send right now, `U256::MAX` without a limit.
+    pub fn available<SDK: SharedAPI>(&self, sdk: &SDK, account: Address) -> U256 {
+        let window_seconds = self.window_seconds(sdk, account);
+        if window_seconds == 0 {
+            return U256::MAX;
+        }
+
+        let max_per_window = self.max_per_window_accessor().entry(account).get(sdk);
+        let position = Position::at(sdk.context().block_timestamp(), window_seconds);
+        max_per_window.saturating_sub(self.sent(sdk, account, &position))
+    }
+
+    /// Records `amount` leaving `account`, reverting with `TransferLimitExceeded` past the limit.
+    pub fn consume<SDK: SharedAPI>(&self, sdk: &mut SDK, account: Address, amount: U256) {
+        let window_seconds = self.window_seconds(sdk, account);
+        if window_seconds == 0 {
+            return;
+        }
+
+        let available = self.available(sdk, account);
+        if amount > available {
+            revert(
+                sdk,
+                TransferLimitExceeded {
+                    account,
+                    requested: amount,
+                    available,
+                },
+            );
+        }
+
+        // Reuse the ring entry of a bucket that no longer counts
+        let bucket = Position::at(sdk.context().block_timestamp(), window_seconds).bucket;
+        let index = ring_index(bucket);
+        let sent = if self.bucket_number(sdk, account, index) == bucket {
+            self.bucket_amount_accessor()
+                .entry(account)
+                .entry(index)
+                .get(sdk)
+        } else {
+            self.bucket_number_accessor()
+                .entry(account)
+                .entry(index)
+                .set(sdk, U256::from(bucket));
+            U256::ZERO
+        };
+        self.bucket_amount_accessor()
+            .entry(account)
+            .entry(index)
+            .set(sdk, sent + amount);
+    }
+
+    fn window_seconds<SDK: SharedAPI>(&self, sdk: &SDK, account: Address) -> u64 {
+        let window_seconds = self.window_seconds_accessor().entry(account).get(sdk);
+        window_seconds.to::<u64>()
+    }
+
+    fn bucket_number<SDK: SharedAPI>(&self, sdk: &SDK, account: Address, index: U256) -> u64 {
+        let bucket = self
+            .bucket_number_accessor()
+            .entry(account)
+            .entry(index)
+            .get(sdk);
+        bucket.to::<u64>()
+    }
+
+    // Sum of the live buckets. Ring entries still holding an older bucket are skipped
+    fn sent<SDK: SharedAPI>(&self, sdk: &SDK, account: Address, position: &Position) -> U256 {
+        position
+            .live()
+            .map(|bucket| (bucket, ring_index(bucket)))
+            .filter(|&(bucket, index)| self.bucket_number(sdk, account, index) == bucket)
+            .map(|(_, index)| {
+                self.bucket_amount_accessor()
+                    .entry(account)
+                    .entry(index)
+                    .get(sdk)
+            })
+            .fold(U256::ZERO, U256::saturating_add)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_sol_types::SolError;
+    use fluentbase_sdk::{address, BlockContextV1};
+    use fluentbase_testing::HostTestingContext;
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+
+    const HOT_WALLET: Address = address!("1111111111111111111111111111111111111111");
+
+    fn at_time(sdk: HostTestingContext, timestamp: u64) -> HostTestingContext {
+        sdk.with_block_context(BlockContextV1 {
+            timestamp,
+            ..Default::default()
+        })
+    }
+
+    fn limited(max_per_window: u64, window_seconds: u64) -> HostTestingContext {
+        let mut sdk = at_time(HostTestingContext::default(), 1_000);
+        TransferLimits.set_limit(
+            &mut sdk,
+            HOT_WALLET,
+            U256::from(max_per_window),
+            window_seconds,
+        );
+        sdk
+    }
+
+    #[test]
+    fn test_no_limit_by_default() {
+        let mut sdk = HostTestingContext::default();
+        TransferLimits.consume(&mut sdk, HOT_WALLET, U256::MAX);
+        assert_eq!(TransferLimits.available(&sdk, HOT_WALLET), U256::MAX);
+    }
+
+    #[test]
+    fn test_spending_within_window() {
+        let mut sdk = limited(100, 3_600);
+
+        TransferLimits.consume(&mut sdk, HOT_WALLET, U256::from(60));
+        TransferLimits.consume(&mut sdk, HOT_WALLET, U256::from(40));
+
+        assert_eq!(TransferLimits.available(&sdk, HOT_WALLET), U256::ZERO);
+    }
+
+    #[test]
+    fn test_exceeding_limit_reverts_with_custom_error() {
+        let mut sdk = limited(100, 3_600);
+        TransferLimits.consume(&mut sdk, HOT_WALLET, U256::from(60));
+
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            TransferLimits.consume(&mut sdk, HOT_WALLET, U256::from(41));
+        }));
+        assert!(result.is_err(), "transfer over the limit should revert");
+
+        let error = TransferLimitExceeded::abi_decode(&sdk.take_output()).unwrap();
+        assert_eq!(error.account, HOT_WALLET);
+        assert_eq!(error.requested, U256::from(41));
+        assert_eq!(error.available, U256::from(40));
+    }
+
+    #[test]
+    fn test_window_rolls_instead_of_resetting() {
+        // 100 per hour, in buckets of 450 seconds
+        let mut sdk = limited(100, 3_600);
+        TransferLimits.consume(&mut sdk, HOT_WALLET, U256::from(60));
+
+        // Verify: Spending late in the hour does not free up a fresh allowance at the hour mark
+        let mut sdk = at_time(sdk, 4_000);
+        TransferLimits.consume(&mut sdk, HOT_WALLET, U256::from(40));
+        let sdk = at_time(sdk, 4_700);
+        assert_eq!(TransferLimits.available(&sdk, HOT_WALLET), U256::ZERO);
+
+        // The first 60 stop counting once their bucket is a whole window old, the 40 do not
+        let sdk = at_time(sdk, 4_949);
+        assert_eq!(TransferLimits.available(&sdk, HOT_WALLET), U256::ZERO);
+        let sdk = at_time(sdk, 4_950);
+        assert_eq!(TransferLimits.available(&sdk, HOT_WALLET), U256::from(60));
+        let sdk = at_time(sdk, 7_650);
+        assert_eq!(TransferLimits.available(&sdk, HOT_WALLET), U256::from(100));
+    }
+
+    #[test]
+    fn test_no_span_of_one_window_exceeds_the_limit() {
+        const WINDOW: u64 = 1_000;
+        let mut sdk = limited(100, WINDOW);
+        let mut sent = Vec::new();
+
+        // Execute: Send whatever is available every 37 seconds
+        for timestamp in (1_000..6_000).step_by(37) {
+            sdk = at_time(sdk, timestamp);
+            let available = TransferLimits
+                .available(&sdk, HOT_WALLET)
+                .min(U256::from(30));
+            TransferLimits.consume(&mut sdk, HOT_WALLET, available);
+            sent.push((timestamp, available));
+        }
+
+        // Verify: Every window long span holds at most the limit
+        for &(start, _) in &sent {
+            let in_span: U256 = sent
+                .iter()
+                .filter(|&&(timestamp, _)| timestamp >= start && timestamp < start + WINDOW)
+                .map(|&(_, amount)| amount)
+                .sum();
+            assert!(in_span <= U256::from(100), "{in_span} sent from t={start}");
+        }
+        let total: U256 = sent.iter().map(|&(_, amount)| amount).sum();
+        assert!(
+            total >= U256::from(400),
+            "limit never frees up, sent {total}"
+        );
+    }
+
+    #[test]
+    fn test_set_limit_forgets_past_transfers() {
+        let mut sdk = limited(100, 3_600);
+        TransferLimits.consume(&mut sdk, HOT_WALLET, U256::from(100));
+
+        TransferLimits.set_limit(&mut sdk, HOT_WALLET, U256::from(50), 60);
+
+        assert_eq!(TransferLimits.available(&sdk, HOT_WALLET), U256::from(50));
+    }
+}
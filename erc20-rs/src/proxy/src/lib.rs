@@ -0,0 +1,218 @@
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+extern crate alloc;
+extern crate fluentbase_sdk;
+
+use alloc::vec::Vec;
+use alloy_sol_types::{sol, SolEvent};
+use contract_utils::CallContract;
+use fluentbase_sdk::{
+    basic_entrypoint,
+    derive::{constructor, Contract},
+    storage::{StorageAddress, StorageDescriptor},
+    Address, Bytes, ExitCode, SharedAPI, B256, U256,
+};
+use hex_literal::hex;
+
+/// `bytes32(uint256(keccak256("eip1967.proxy.implementation")) - 1)`
+const IMPLEMENTATION_SLOT: U256 = U256::from_be_bytes(hex!(
+    "360894a13ba1a3210667c828492db98dca3e2076cc3735a920a3ca505d382bbc"
+));
+
+sol! {
+    event Upgraded(address indexed implementation);
+}
+
+fn emit_event<SDK: SharedAPI, T: SolEvent>(sdk: &mut SDK, event: T) {
+    let data = event.encode_data();
+    let topics: Vec<B256> = event
+        .encode_topics()
+        .iter()
+        .map(|v| B256::from(v.0))
+        .collect();
+    sdk.emit_log(&topics, &data);
+}
+
+// Minimal ERC-1967 proxy. Upgrades are UUPS-style: `upgradeTo` lives in the
+// implementation and rewrites the implementation slot through `delegate_call`.
+#[derive(Contract)]
+pub struct ERC1967Proxy<SDK> {
+    sdk: SDK,
+}
+
+#[constructor(mode = "solidity")]
+impl<SDK: SharedAPI> ERC1967Proxy<SDK> {
+    pub fn constructor(&mut self, implementation: Address, data: Bytes) {
+        if implementation == Address::ZERO {
+            panic!("implementation is the zero address");
+        }
+
+        self.implementation_accessor()
+            .set(&mut self.sdk, implementation);
+        emit_event(&mut self.sdk, Upgraded { implementation });
+
+        // Run the initializer, e.g. `initialize(...)`, against the proxy's storage
+        if !data.is_empty() {
+            let (success, _) = self.sdk.delegate_call_contract(implementation, &data, None);
+            if !success {
+                panic!("initialization failed");
+            }
+        }
+    }
+}
+
+impl<SDK: SharedAPI> ERC1967Proxy<SDK> {
+    fn implementation_accessor(&self) -> StorageAddress {
+        StorageAddress::new(IMPLEMENTATION_SLOT, 0)
+    }
+
+    // Forward every call to the implementation and bubble up its output and status
+    pub fn main(&mut self) {
+        let implementation = self.implementation_accessor().get(&self.sdk);
+        let input = self.sdk.input();
+
+        let (success, output) = self
+            .sdk
+            .delegate_call_contract(implementation, &input, None);
+        self.sdk.write(&output);
+        if !success {
+            self.sdk.exit(ExitCode::Panic);
+        }
+    }
+}
+
+basic_entrypoint!(ERC1967Proxy);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_sol_types::SolError;
+    use contract_utils::{
+        chain::{self, CallKind},
+        testing::{ChainContext, HostContract},
+    };
+    use erc20::{layout, ERC20InsufficientBalance, ERC20};
+    use fluentbase_sdk::{address, codec::Encoder, ContractContextV1};
+    use fluentbase_testing::HostTestingContext;
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+
+    const PROXY: Address = address!("bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb");
+    const IMPLEMENTATION: Address = address!("aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa");
+    const DEPLOYER: Address = address!("1111111111111111111111111111111111111111");
+    const ALICE: Address = address!("2222222222222222222222222222222222222222");
+
+    // The proxy's delegate calls reach the token on the chain
+    type TestProxy = ERC1967Proxy<ChainContext<HostTestingContext>>;
+
+    fn as_caller(sdk: HostTestingContext, caller: Address, input: Vec<u8>) -> HostTestingContext {
+        sdk.with_input(input)
+            .with_contract_context(ContractContextV1 {
+                address: PROXY,
+                caller,
+                ..Default::default()
+            })
+    }
+
+    // Puts the ERC20 token on the chain at `IMPLEMENTATION`. Delegate calls run it on the
+    // proxy's storage, which every clone of the proxy's `HostTestingContext` shares
+    fn register_token(proxy_sdk: HostTestingContext) {
+        chain::register(IMPLEMENTATION, move |call| {
+            assert_eq!(call.kind, CallKind::DelegateCall);
+            let sdk = as_caller(proxy_sdk.clone(), call.caller, call.input.to_vec());
+
+            let mut token = ERC20::with_host(sdk);
+            let result = catch_unwind(AssertUnwindSafe(|| token.run_main()));
+            (result.is_ok(), Bytes::from(token.host_mut().take_output()))
+        });
+    }
+
+    fn deploy_proxy(implementation: Address, data: Bytes) -> TestProxy {
+        let constructor_call = ConstructorCall::new((implementation, data));
+        let sdk = as_caller(
+            HostTestingContext::default(),
+            DEPLOYER,
+            constructor_call.encode().to_vec(),
+        );
+        register_token(sdk.clone());
+
+        let mut proxy = ERC1967Proxy::new(ChainContext::new(sdk));
+        proxy.deploy();
+        proxy
+    }
+
+    // The token behind the proxy, initialized by the constructor with the whole supply held by
+    // the deployer
+    fn proxied_token() -> TestProxy {
+        let initialize = erc20::InitializeCall::new((
+            "ProxyToken".to_string(),
+            "PXY".to_string(),
+            U256::from(1_000),
+            DEPLOYER,
+        ));
+        deploy_proxy(IMPLEMENTATION, Bytes::from(initialize.encode().to_vec()))
+    }
+
+    fn call_proxy(proxy: &mut TestProxy, caller: Address, input: Vec<u8>) -> (bool, Bytes) {
+        proxy.sdk = ChainContext::new(as_caller(proxy.sdk.clone().into_inner(), caller, input));
+        let result = catch_unwind(AssertUnwindSafe(|| proxy.main()));
+        (result.is_ok(), Bytes::from(proxy.sdk.take_output()))
+    }
+
+    #[test]
+    fn test_constructor_stores_implementation() {
+        let implementation = address!("aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa");
+        let proxy = deploy_proxy(implementation, Bytes::new());
+
+        assert_eq!(
+            proxy.implementation_accessor().get(&proxy.sdk),
+            implementation,
+            "implementation slot not set"
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "implementation is the zero address")]
+    fn test_constructor_rejects_zero_implementation() {
+        deploy_proxy(Address::ZERO, Bytes::new());
+    }
+
+    #[test]
+    fn test_forwards_calls_to_the_implementation() {
+        let mut proxy = proxied_token();
+
+        let transfer = erc20::TransferCall::new((ALICE, U256::from(250)));
+        let (success, _) = call_proxy(&mut proxy, DEPLOYER, transfer.encode().to_vec());
+        assert!(success, "transfer reverted");
+
+        // Verify: Return data passes through the proxy unchanged
+        let balance_of = erc20::BalanceOfCall::new((ALICE,));
+        let (success, output) = call_proxy(&mut proxy, ALICE, balance_of.encode().to_vec());
+        assert!(success);
+        let balance = erc20::BalanceOfReturn::decode(&&output[..]).unwrap().0 .0;
+        assert_eq!(balance, U256::from(250));
+
+        // Verify: The token state lives in the proxy's storage
+        let total_supply = proxy
+            .sdk
+            .storage(&layout::slot(layout::TOTAL_SUPPLY_OFFSET))
+            .data;
+        assert_eq!(total_supply, U256::from(1_000));
+        assert_eq!(
+            proxy.implementation_accessor().get(&proxy.sdk),
+            IMPLEMENTATION
+        );
+    }
+
+    #[test]
+    fn test_bubbles_up_revert_data() {
+        let mut proxy = proxied_token();
+
+        let transfer = erc20::TransferCall::new((DEPLOYER, U256::from(1)));
+        let (success, output) = call_proxy(&mut proxy, ALICE, transfer.encode().to_vec());
+
+        assert!(!success, "transfer without balance succeeded");
+        let error = ERC20InsufficientBalance::abi_decode(&output).unwrap();
+        assert_eq!(error.sender, ALICE);
+        assert_eq!((error.balance, error.needed), (U256::ZERO, U256::from(1)));
+    }
+}
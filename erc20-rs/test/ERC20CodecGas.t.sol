@@ -0,0 +1,179 @@
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.23;
+
+import {Test} from "forge-std/Test.sol";
+
+/// Calldata in fluentbase's compact codec, as the `erc20-fluent` router decodes it.
+///
+/// Static values are packed in order: addresses as their 20 raw bytes, integers little-endian.
+/// A string is an 8-byte header in place (u32 offset from the start of the arguments, u32
+/// length) with its bytes appended after all heads, padded to 4 bytes.
+library Compact {
+    function u256(uint256 value) internal pure returns (bytes memory out) {
+        out = new bytes(32);
+        for (uint256 i = 0; i < 32; i++) {
+            out[i] = bytes1(uint8(value >> (8 * i)));
+        }
+    }
+
+    function u32(uint256 value) internal pure returns (bytes memory out) {
+        out = new bytes(4);
+        for (uint256 i = 0; i < 4; i++) {
+            out[i] = bytes1(uint8(value >> (8 * i)));
+        }
+    }
+
+    function padded(bytes memory data) internal pure returns (bytes memory) {
+        return bytes.concat(data, new bytes((4 - data.length % 4) % 4));
+    }
+
+    /// Constructor arguments `(string name, string symbol, uint256 initialSupply)`.
+    function constructorArgs(string memory name, string memory symbol, uint256 supply)
+        internal
+        pure
+        returns (bytes memory)
+    {
+        bytes memory nameData = padded(bytes(name));
+        uint256 headsLength = 8 + 8 + 32;
+        return bytes.concat(
+            u32(headsLength),
+            u32(bytes(name).length),
+            u32(headsLength + nameData.length),
+            u32(bytes(symbol).length),
+            u256(supply),
+            nameData,
+            padded(bytes(symbol))
+        );
+    }
+}
+
+interface IToken {
+    function totalSupply() external view returns (uint256);
+    function balanceOf(address account) external view returns (uint256);
+    function transfer(address to, uint256 value) external returns (uint256);
+    function allowance(address owner, address spender) external view returns (uint256);
+    function approve(address spender, uint256 value) external returns (uint256);
+    function transferFrom(address from, address to, uint256 value) external returns (uint256);
+    function mint(address to, uint256 value) external returns (uint256);
+    function burn(uint256 value) external returns (uint256);
+}
+
+/// Gas of the same calls on the Solidity-ABI and compact-codec builds of the token, written to
+/// `snapshots/ERC20Codec.json` as `<entrypoint>_solidity` and `<entrypoint>_fluent`. Every call
+/// runs cold, right after `vm.cool`.
+contract ERC20CodecGasTest is Test {
+    string internal constant GROUP = "ERC20Codec";
+
+    uint256 internal constant INITIAL_SUPPLY = 1_000_000 ether;
+    uint256 internal constant AMOUNT = 100 ether;
+
+    address internal solidityToken;
+    address internal fluentToken;
+
+    address internal alice = makeAddr("alice");
+    address internal bob = makeAddr("bob");
+
+    function setUp() public {
+        solidityToken = vm.deployCode("out/erc20.wasm/foundry.json", abi.encode("Bench", "BNC", INITIAL_SUPPLY));
+        fluentToken = vm.deployCode(
+            "out/erc20-fluent.wasm/foundry.json", Compact.constructorArgs("Bench", "BNC", INITIAL_SUPPLY)
+        );
+
+        // Same starting state on both: alice holds tokens and may spend this contract's
+        _mustCall(solidityToken, _transfer(false, alice, AMOUNT * 10));
+        _mustCall(solidityToken, _approve(false, alice, INITIAL_SUPPLY));
+        _mustCall(fluentToken, _transfer(true, alice, AMOUNT * 10));
+        _mustCall(fluentToken, _approve(true, alice, INITIAL_SUPPLY));
+    }
+
+    function test_totalSupply() public {
+        _compare("totalSupply", alice, abi.encodeCall(IToken.totalSupply, ()), abi.encodeCall(IToken.totalSupply, ()));
+    }
+
+    function test_balanceOf() public {
+        _compare(
+            "balanceOf",
+            alice,
+            abi.encodeCall(IToken.balanceOf, (alice)),
+            bytes.concat(IToken.balanceOf.selector, abi.encodePacked(alice))
+        );
+    }
+
+    function test_allowance() public {
+        _compare(
+            "allowance",
+            alice,
+            abi.encodeCall(IToken.allowance, (address(this), alice)),
+            bytes.concat(IToken.allowance.selector, abi.encodePacked(address(this), alice))
+        );
+    }
+
+    function test_transfer() public {
+        _compare("transfer", alice, _transfer(false, bob, AMOUNT), _transfer(true, bob, AMOUNT));
+    }
+
+    function test_approve() public {
+        _compare("approve", alice, _approve(false, bob, AMOUNT), _approve(true, bob, AMOUNT));
+    }
+
+    function test_transferFrom() public {
+        _compare(
+            "transferFrom",
+            alice,
+            abi.encodeCall(IToken.transferFrom, (address(this), bob, AMOUNT)),
+            bytes.concat(IToken.transferFrom.selector, abi.encodePacked(address(this), bob), Compact.u256(AMOUNT))
+        );
+    }
+
+    function test_mint() public {
+        _compare(
+            "mint",
+            address(this),
+            abi.encodeCall(IToken.mint, (alice, AMOUNT)),
+            bytes.concat(IToken.mint.selector, abi.encodePacked(alice), Compact.u256(AMOUNT))
+        );
+    }
+
+    function test_burn() public {
+        _compare(
+            "burn",
+            alice,
+            abi.encodeCall(IToken.burn, (AMOUNT)),
+            bytes.concat(IToken.burn.selector, Compact.u256(AMOUNT))
+        );
+    }
+
+    // Helpers
+
+    function _transfer(bool fluent, address to, uint256 value) internal pure returns (bytes memory) {
+        if (!fluent) return abi.encodeCall(IToken.transfer, (to, value));
+        return bytes.concat(IToken.transfer.selector, abi.encodePacked(to), Compact.u256(value));
+    }
+
+    function _approve(bool fluent, address spender, uint256 value) internal pure returns (bytes memory) {
+        if (!fluent) return abi.encodeCall(IToken.approve, (spender, value));
+        return bytes.concat(IToken.approve.selector, abi.encodePacked(spender), Compact.u256(value));
+    }
+
+    function _compare(string memory entrypoint, address caller, bytes memory solidityCall, bytes memory fluentCall)
+        internal
+    {
+        _measure(string.concat(entrypoint, "_solidity"), solidityToken, caller, solidityCall);
+        _measure(string.concat(entrypoint, "_fluent"), fluentToken, caller, fluentCall);
+    }
+
+    function _measure(string memory label, address token, address caller, bytes memory data) internal {
+        vm.cool(token);
+        // Cheatcode calls do not consume the prank, and stay out of the measured window
+        vm.prank(caller);
+        vm.startSnapshotGas(GROUP, label);
+        (bool ok,) = token.call(data);
+        vm.stopSnapshotGas();
+        require(ok, string.concat(label, " reverted"));
+    }
+
+    function _mustCall(address token, bytes memory data) internal {
+        (bool ok,) = token.call(data);
+        require(ok, "setup call reverted");
+    }
+}
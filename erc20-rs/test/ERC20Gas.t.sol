@@ -0,0 +1,286 @@
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.23;
+
+import {Test} from "forge-std/Test.sol";
+
+interface IERC20Rust {
+    function name() external view returns (string memory);
+    function symbol() external view returns (string memory);
+    function decimals() external view returns (uint256);
+    function totalSupply() external view returns (uint256);
+    function balanceOf(address account) external view returns (uint256);
+    function transfer(address to, uint256 value) external returns (uint256);
+    function allowance(address owner, address spender) external view returns (uint256);
+    function approve(address spender, uint256 value) external returns (uint256);
+    function transferFrom(address from, address to, uint256 value) external returns (uint256);
+
+    function mint(address to, uint256 value) external returns (uint256);
+    function burn(uint256 value) external returns (uint256);
+
+    function initialize(string memory name, string memory symbol, uint256 initialSupply, address owner) external;
+    function upgradeTo(address newImplementation) external;
+    function proxiableUUID() external view returns (bytes32);
+
+    function owner() external view returns (address);
+    function pendingOwner() external view returns (address);
+    function transferOwnership(address newOwner) external;
+    function acceptOwnership() external;
+    function renounceOwnership() external;
+
+    function setTransferLimit(address account, uint256 maxPerWindow, uint64 windowSeconds) external;
+    function availableToTransfer(address account) external view returns (uint256);
+
+    function createStream(address recipient, uint256 deposit, uint256 startTime, uint256 stopTime)
+        external
+        returns (uint256);
+    function balanceOfStream(uint256 streamId, address who) external view returns (uint256);
+    function withdrawFromStream(uint256 streamId, uint256 amount) external returns (uint256);
+    function cancelStream(uint256 streamId) external returns (uint256);
+}
+
+/// Gas used by every router entrypoint of the compiled token, written to `snapshots/ERC20.json`.
+///
+/// The numbers are EVM gas as `vm.startSnapshotGas` reports it, not fuel: the runtime charges
+/// the wasm contract in fuel and converts it to gas at the call boundary. `<entrypoint>_cold` is
+/// measured right after `vm.cool`, so the token's account and every storage slot are cold, like
+/// the first touch in a transaction. `<entrypoint>_warm` repeats a call whose slots were just
+/// touched. The first run creates the snapshot; commit it, then run with
+/// `FORGE_SNAPSHOT_CHECK=true` to fail on any change. The token has no permit or batch
+/// entrypoints, so there are no variants for them.
+contract ERC20GasTest is Test {
+    string internal constant GROUP = "ERC20";
+
+    uint256 internal constant INITIAL_SUPPLY = 1_000_000 ether;
+    uint256 internal constant AMOUNT = 100 ether;
+    uint256 internal constant STREAM_DURATION = 3_600;
+
+    IERC20Rust internal token;
+
+    address internal alice = makeAddr("alice");
+    address internal bob = makeAddr("bob");
+
+    function setUp() public {
+        token = IERC20Rust(vm.deployCode("out/erc20.wasm/foundry.json", abi.encode("Bench", "BNC", INITIAL_SUPPLY)));
+
+        // Non-zero balances and allowances, so writes are updates rather than first sets
+        token.transfer(alice, AMOUNT * 100);
+        token.transfer(bob, AMOUNT * 100);
+        token.approve(alice, INITIAL_SUPPLY);
+    }
+
+    // ERC-20
+
+    function test_name() public {
+        _coldAndWarm("name", alice, abi.encodeCall(IERC20Rust.name, ()));
+    }
+
+    function test_symbol() public {
+        _coldAndWarm("symbol", alice, abi.encodeCall(IERC20Rust.symbol, ()));
+    }
+
+    function test_decimals() public {
+        _coldAndWarm("decimals", alice, abi.encodeCall(IERC20Rust.decimals, ()));
+    }
+
+    function test_totalSupply() public {
+        _coldAndWarm("totalSupply", alice, abi.encodeCall(IERC20Rust.totalSupply, ()));
+    }
+
+    function test_balanceOf() public {
+        _coldAndWarm("balanceOf", alice, abi.encodeCall(IERC20Rust.balanceOf, (alice)));
+    }
+
+    function test_allowance() public {
+        _coldAndWarm("allowance", alice, abi.encodeCall(IERC20Rust.allowance, (address(this), alice)));
+    }
+
+    function test_transfer() public {
+        _coldAndWarm("transfer", alice, abi.encodeCall(IERC20Rust.transfer, (bob, AMOUNT)));
+    }
+
+    function test_transfer_newHolder() public {
+        _cold("transfer_newHolder", alice, abi.encodeCall(IERC20Rust.transfer, (makeAddr("fresh"), AMOUNT)));
+    }
+
+    function test_approve() public {
+        _coldAndWarm("approve", alice, abi.encodeCall(IERC20Rust.approve, (bob, AMOUNT)));
+    }
+
+    function test_transferFrom() public {
+        _coldAndWarm("transferFrom", alice, abi.encodeCall(IERC20Rust.transferFrom, (address(this), bob, AMOUNT)));
+    }
+
+    function test_transferFrom_infiniteAllowance() public {
+        token.approve(alice, type(uint256).max);
+        _coldAndWarm(
+            "transferFrom_infiniteAllowance",
+            alice,
+            abi.encodeCall(IERC20Rust.transferFrom, (address(this), bob, AMOUNT))
+        );
+    }
+
+    // Supply management
+
+    function test_mint() public {
+        _coldAndWarm("mint", address(this), abi.encodeCall(IERC20Rust.mint, (alice, AMOUNT)));
+    }
+
+    function test_burn() public {
+        _coldAndWarm("burn", alice, abi.encodeCall(IERC20Rust.burn, (AMOUNT)));
+    }
+
+    // Upgradeability
+
+    function test_initialize() public {
+        // Only callable once, on storage no constructor has touched: go through a fresh proxy
+        address implementation = vm.deployCode("out/erc20.wasm/foundry.json", abi.encode("Impl", "IMP", uint256(0)));
+        address proxy =
+            vm.deployCode("out/erc1967-proxy.wasm/foundry.json", abi.encode(implementation, bytes("")));
+
+        vm.cool(proxy);
+        vm.startSnapshotGas(GROUP, "initialize_cold");
+        IERC20Rust(proxy).initialize("Proxied", "PRX", INITIAL_SUPPLY, address(this));
+        vm.stopSnapshotGas();
+    }
+
+    function test_upgradeTo() public {
+        // Only runs through a proxy, and only to a deployed implementation
+        address implementation = vm.deployCode("out/erc20.wasm/foundry.json", abi.encode("Impl", "IMP", uint256(0)));
+        address newImplementation =
+            vm.deployCode("out/erc20.wasm/foundry.json", abi.encode("Impl", "IMP", uint256(0)));
+        address proxy =
+            vm.deployCode("out/erc1967-proxy.wasm/foundry.json", abi.encode(implementation, bytes("")));
+        IERC20Rust(proxy).initialize("Proxied", "PRX", INITIAL_SUPPLY, address(this));
+
+        vm.cool(proxy);
+        vm.cool(newImplementation);
+        vm.startSnapshotGas(GROUP, "upgradeTo_cold");
+        IERC20Rust(proxy).upgradeTo(newImplementation);
+        vm.stopSnapshotGas();
+
+        vm.startSnapshotGas(GROUP, "upgradeTo_warm");
+        IERC20Rust(proxy).upgradeTo(implementation);
+        vm.stopSnapshotGas();
+    }
+
+    function test_proxiableUUID() public {
+        _coldAndWarm("proxiableUUID", alice, abi.encodeCall(IERC20Rust.proxiableUUID, ()));
+    }
+
+    // Ownership
+
+    function test_owner() public {
+        _coldAndWarm("owner", alice, abi.encodeCall(IERC20Rust.owner, ()));
+    }
+
+    function test_pendingOwner() public {
+        _coldAndWarm("pendingOwner", alice, abi.encodeCall(IERC20Rust.pendingOwner, ()));
+    }
+
+    function test_transferOwnership() public {
+        _coldAndWarm("transferOwnership", address(this), abi.encodeCall(IERC20Rust.transferOwnership, (alice)));
+    }
+
+    function test_acceptOwnership() public {
+        token.transferOwnership(alice);
+        _cold("acceptOwnership", alice, abi.encodeCall(IERC20Rust.acceptOwnership, ()));
+
+        vm.prank(alice);
+        token.transferOwnership(bob);
+        _warm("acceptOwnership", bob, abi.encodeCall(IERC20Rust.acceptOwnership, ()));
+    }
+
+    function test_renounceOwnership_cold() public {
+        _cold("renounceOwnership", address(this), abi.encodeCall(IERC20Rust.renounceOwnership, ()));
+    }
+
+    function test_renounceOwnership_warm() public {
+        token.transferOwnership(alice);
+        _warm("renounceOwnership", address(this), abi.encodeCall(IERC20Rust.renounceOwnership, ()));
+    }
+
+    // Transfer limits
+
+    function test_setTransferLimit() public {
+        _coldAndWarm(
+            "setTransferLimit", address(this), abi.encodeCall(IERC20Rust.setTransferLimit, (alice, AMOUNT * 5, 1 days))
+        );
+    }
+
+    function test_availableToTransfer() public {
+        token.setTransferLimit(alice, AMOUNT * 5, 1 days);
+        _coldAndWarm("availableToTransfer", alice, abi.encodeCall(IERC20Rust.availableToTransfer, (alice)));
+    }
+
+    function test_transfer_limited() public {
+        token.setTransferLimit(alice, AMOUNT * 5, 1 days);
+        _coldAndWarm("transfer_limited", alice, abi.encodeCall(IERC20Rust.transfer, (bob, AMOUNT)));
+    }
+
+    // Streams
+
+    function test_createStream() public {
+        _coldAndWarm("createStream", alice, _createStreamCall());
+    }
+
+    function test_balanceOfStream() public {
+        uint256 streamId = _createStream();
+        vm.warp(block.timestamp + STREAM_DURATION / 2);
+        _coldAndWarm("balanceOfStream", alice, abi.encodeCall(IERC20Rust.balanceOfStream, (streamId, bob)));
+    }
+
+    function test_withdrawFromStream() public {
+        uint256 streamId = _createStream();
+        vm.warp(block.timestamp + STREAM_DURATION / 2);
+        _coldAndWarm("withdrawFromStream", bob, abi.encodeCall(IERC20Rust.withdrawFromStream, (streamId, 1 ether)));
+    }
+
+    function test_cancelStream_cold() public {
+        uint256 streamId = _createStream();
+        vm.warp(block.timestamp + STREAM_DURATION / 2);
+        _cold("cancelStream", alice, abi.encodeCall(IERC20Rust.cancelStream, (streamId)));
+    }
+
+    function test_cancelStream_warm() public {
+        uint256 streamId = _createStream();
+        vm.warp(block.timestamp + STREAM_DURATION / 2);
+        token.balanceOfStream(streamId, bob);
+        _warm("cancelStream", alice, abi.encodeCall(IERC20Rust.cancelStream, (streamId)));
+    }
+
+    // Helpers
+
+    function _createStreamCall() internal view returns (bytes memory) {
+        uint256 start = block.timestamp + 1;
+        return abi.encodeCall(IERC20Rust.createStream, (bob, STREAM_DURATION * 1 ether, start, start + STREAM_DURATION));
+    }
+
+    function _createStream() internal returns (uint256) {
+        uint256 start = block.timestamp + 1;
+        vm.prank(alice);
+        return token.createStream(bob, STREAM_DURATION * 1 ether, start, start + STREAM_DURATION);
+    }
+
+    function _coldAndWarm(string memory entrypoint, address caller, bytes memory data) internal {
+        _cold(entrypoint, caller, data);
+        _warm(entrypoint, caller, data);
+    }
+
+    function _cold(string memory entrypoint, address caller, bytes memory data) internal {
+        vm.cool(address(token));
+        _measure(string.concat(entrypoint, "_cold"), caller, data);
+    }
+
+    function _warm(string memory entrypoint, address caller, bytes memory data) internal {
+        _measure(string.concat(entrypoint, "_warm"), caller, data);
+    }
+
+    function _measure(string memory label, address caller, bytes memory data) internal {
+        // Cheatcode calls do not consume the prank, and stay out of the measured window
+        vm.prank(caller);
+        vm.startSnapshotGas(GROUP, label);
+        (bool ok,) = address(token).call(data);
+        vm.stopSnapshotGas();
+        require(ok, string.concat(label, " reverted"));
+    }
+}
@@ -0,0 +1,291 @@
+//! ERC-721 enumerable extension: every token, and every owner's tokens, by index.
+//!
+//! Same bookkeeping as OpenZeppelin's `ERC721Enumerable`: each list is a dense array plus the
+//! index of each token in it, and removing a token moves the last one into its place. Both are
+//! O(1), and removals reorder the remaining tokens.
+//!
+//! State lives in the ERC-7201 namespace `erc721.storage.Enumerable`:
+//!
+//! | offset | field                        |
+//! |--------|------------------------------|
+//! | 0      | number of tokens             |
+//! | 1      | index => token id            |
+//! | 2      | token id => index            |
+//! | 3      | owner => index => token id   |
+//! | 4      | token id => index in owner's |
+
+use fluentbase_sdk::{
+    storage::{StorageDescriptor, StorageMap, StorageU256},
+    Address, SharedAPI, U256,
+};
+use hex_literal::hex;
+
+/// `keccak256(abi.encode(uint256(keccak256("erc721.storage.Enumerable")) - 1)) & ~bytes32(uint256(0xff))`
+pub const ENUMERABLE_NAMESPACE_SLOT: U256 = U256::from_be_bytes(hex!(
+    "6983a8cdb2783ec4f6d3d73d2cb1099e0fbb842ea2d4eb722122ebb4997c9f00"
+));
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Enumerable;
+
+impl Enumerable {
+    fn total_supply_accessor(&self) -> StorageU256 {
+        StorageU256::new(ENUMERABLE_NAMESPACE_SLOT, 0)
+    }
+
+    fn all_tokens_accessor(&self) -> StorageMap<U256, StorageU256> {
+        StorageMap::new(ENUMERABLE_NAMESPACE_SLOT + U256::from(1), 0)
+    }
+
+    fn all_tokens_index_accessor(&self) -> StorageMap<U256, StorageU256> {
+        StorageMap::new(ENUMERABLE_NAMESPACE_SLOT + U256::from(2), 0)
+    }
+
+    fn owned_tokens_accessor(&self) -> StorageMap<Address, StorageMap<U256, StorageU256>> {
+        StorageMap::new(ENUMERABLE_NAMESPACE_SLOT + U256::from(3), 0)
+    }
+
+    fn owned_tokens_index_accessor(&self) -> StorageMap<U256, StorageU256> {
+        StorageMap::new(ENUMERABLE_NAMESPACE_SLOT + U256::from(4), 0)
+    }
+
+    pub fn total_supply<SDK: SharedAPI>(&self, sdk: &SDK) -> U256 {
+        self.total_supply_accessor().get(sdk)
+    }
+
+    /// Token at `index` of all tokens, `None` past the end.
+    pub fn token_by_index<SDK: SharedAPI>(&self, sdk: &SDK, index: U256) -> Option<U256> {
+        if index >= self.total_supply(sdk) {
+            return None;
+        }
+        Some(self.all_tokens_accessor().entry(index).get(sdk))
+    }
+
+    /// Token at `index` of `owner`'s tokens, given `owner`'s balance; `None` past the end.
+    pub fn token_of_owner_by_index<SDK: SharedAPI>(
+        &self,
+        sdk: &SDK,
+        owner: Address,
+        balance: U256,
+        index: U256,
+    ) -> Option<U256> {
+        if index >= balance {
+            return None;
+        }
+        Some(
+            self.owned_tokens_accessor()
+                .entry(owner)
+                .entry(index)
+                .get(sdk),
+        )
+    }
+
+    /// Records `token_id` moving from `from` to `to`, zero for a mint or a burn.
+    ///
+    /// Takes both balances from before the move.
+    pub fn on_transfer<SDK: SharedAPI>(
+        &self,
+        sdk: &mut SDK,
+        from: Address,
+        from_balance: U256,
+        to: Address,
+        to_balance: U256,
+        token_id: U256,
+    ) {
+        if from == Address::ZERO {
+            self.add_to_all_tokens(sdk, token_id);
+        } else if from != to {
+            self.remove_from_owner(sdk, from, from_balance, token_id);
+        }
+
+        if to == Address::ZERO {
+            self.remove_from_all_tokens(sdk, token_id);
+        } else if to != from {
+            self.owned_tokens_accessor()
+                .entry(to)
+                .entry(to_balance)
+                .set(sdk, token_id);
+            self.owned_tokens_index_accessor()
+                .entry(token_id)
+                .set(sdk, to_balance);
+        }
+    }
+
+    fn add_to_all_tokens<SDK: SharedAPI>(&self, sdk: &mut SDK, token_id: U256) {
+        let index = self.total_supply(sdk);
+        self.all_tokens_accessor().entry(index).set(sdk, token_id);
+        self.all_tokens_index_accessor()
+            .entry(token_id)
+            .set(sdk, index);
+        self.total_supply_accessor().set(sdk, index + U256::from(1));
+    }
+
+    fn remove_from_all_tokens<SDK: SharedAPI>(&self, sdk: &mut SDK, token_id: U256) {
+        let last_index = self.total_supply(sdk) - U256::from(1);
+        let index = self.all_tokens_index_accessor().entry(token_id).get(sdk);
+
+        // Move the last token into the freed index, then drop the last index
+        if index != last_index {
+            let last = self.all_tokens_accessor().entry(last_index).get(sdk);
+            self.all_tokens_accessor().entry(index).set(sdk, last);
+            self.all_tokens_index_accessor().entry(last).set(sdk, index);
+        }
+        self.all_tokens_accessor()
+            .entry(last_index)
+            .set(sdk, U256::ZERO);
+        self.all_tokens_index_accessor()
+            .entry(token_id)
+            .set(sdk, U256::ZERO);
+        self.total_supply_accessor().set(sdk, last_index);
+    }
+
+    fn remove_from_owner<SDK: SharedAPI>(
+        &self,
+        sdk: &mut SDK,
+        owner: Address,
+        balance: U256,
+        token_id: U256,
+    ) {
+        let last_index = balance - U256::from(1);
+        let index = self.owned_tokens_index_accessor().entry(token_id).get(sdk);
+
+        if index != last_index {
+            let last = self
+                .owned_tokens_accessor()
+                .entry(owner)
+                .entry(last_index)
+                .get(sdk);
+            self.owned_tokens_accessor()
+                .entry(owner)
+                .entry(index)
+                .set(sdk, last);
+            self.owned_tokens_index_accessor()
+                .entry(last)
+                .set(sdk, index);
+        }
+        self.owned_tokens_accessor()
+            .entry(owner)
+            .entry(last_index)
+            .set(sdk, U256::ZERO);
+        self.owned_tokens_index_accessor()
+            .entry(token_id)
+            .set(sdk, U256::ZERO);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fluentbase_sdk::address;
+    use fluentbase_testing::HostTestingContext;
+
+    const ALICE: Address = address!("2222222222222222222222222222222222222222");
+    const BOB: Address = address!("3333333333333333333333333333333333333333");
+
+    /// Applies a transfer with the balances the contract would pass, and keeps them.
+    struct Tracker {
+        sdk: HostTestingContext,
+        alice: U256,
+        bob: U256,
+    }
+
+    impl Tracker {
+        fn new() -> Self {
+            Self {
+                sdk: HostTestingContext::default(),
+                alice: U256::ZERO,
+                bob: U256::ZERO,
+            }
+        }
+
+        fn balance(&mut self, account: Address) -> &mut U256 {
+            match account {
+                ALICE => &mut self.alice,
+                BOB => &mut self.bob,
+                _ => unreachable!(),
+            }
+        }
+
+        fn transfer(&mut self, from: Address, to: Address, token_id: u64) {
+            let from_balance = if from == Address::ZERO {
+                U256::ZERO
+            } else {
+                *self.balance(from)
+            };
+            let to_balance = if to == Address::ZERO {
+                U256::ZERO
+            } else {
+                *self.balance(to)
+            };
+            Enumerable.on_transfer(
+                &mut self.sdk,
+                from,
+                from_balance,
+                to,
+                to_balance,
+                U256::from(token_id),
+            );
+            if from != Address::ZERO {
+                *self.balance(from) -= U256::from(1);
+            }
+            if to != Address::ZERO {
+                *self.balance(to) += U256::from(1);
+            }
+        }
+
+        fn all(&self) -> Vec<u64> {
+            let supply = Enumerable.total_supply(&self.sdk).to::<u64>();
+            (0..supply)
+                .map(|i| {
+                    Enumerable
+                        .token_by_index(&self.sdk, U256::from(i))
+                        .unwrap()
+                        .to::<u64>()
+                })
+                .collect()
+        }
+
+        fn owned(&mut self, owner: Address) -> Vec<u64> {
+            let balance = *self.balance(owner);
+            (0..balance.to::<u64>())
+                .map(|i| {
+                    Enumerable
+                        .token_of_owner_by_index(&self.sdk, owner, balance, U256::from(i))
+                        .unwrap()
+                        .to::<u64>()
+                })
+                .collect()
+        }
+    }
+
+    #[test]
+    fn test_mints_append() {
+        let mut tracker = Tracker::new();
+
+        tracker.transfer(Address::ZERO, ALICE, 10);
+        tracker.transfer(Address::ZERO, ALICE, 11);
+        tracker.transfer(Address::ZERO, BOB, 12);
+
+        assert_eq!(tracker.all(), vec![10, 11, 12]);
+        assert_eq!(tracker.owned(ALICE), vec![10, 11]);
+        assert_eq!(tracker.owned(BOB), vec![12]);
+        assert_eq!(Enumerable.token_by_index(&tracker.sdk, U256::from(3)), None);
+    }
+
+    #[test]
+    fn test_transfer_and_burn_move_last_token_into_gap() {
+        let mut tracker = Tracker::new();
+        for token_id in [10, 11, 12] {
+            tracker.transfer(Address::ZERO, ALICE, token_id);
+        }
+
+        // Execute: Alice's first token leaves, then the first token overall is burned
+        tracker.transfer(ALICE, BOB, 10);
+        assert_eq!(tracker.owned(ALICE), vec![12, 11]);
+        assert_eq!(tracker.owned(BOB), vec![10]);
+
+        tracker.transfer(BOB, Address::ZERO, 10);
+        assert_eq!(tracker.all(), vec![12, 11]);
+        assert!(tracker.owned(BOB).is_empty());
+    }
+}
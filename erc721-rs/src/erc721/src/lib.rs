@@ -0,0 +1,747 @@
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+#![allow(dead_code)]
+
+extern crate alloc;
+extern crate fluentbase_sdk;
+
+pub mod enumerable;
+pub mod receiver;
+
+#[cfg(test)]
+mod testing;
+
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use alloy_sol_types::{sol, SolError, SolEvent};
+use contract_utils::ownable::Ownable2Step;
+use enumerable::Enumerable;
+use fluentbase_sdk::derive::Contract;
+use fluentbase_sdk::{
+    basic_entrypoint,
+    derive::{constructor, router, Storage},
+    storage::{StorageAddress, StorageMap, StorageString, StorageU256},
+    Address, Bytes, ContextReader, ExitCode, FixedBytes, SharedAPI, B256, U256,
+};
+use hex_literal::hex;
+use receiver::ReceiverError;
+
+// Define the ERC-721 events. Unlike ERC-20's, `tokenId` is indexed
+sol! {
+    event Transfer(address indexed from, address indexed to, uint256 indexed tokenId);
+    event Approval(address indexed owner, address indexed approved, uint256 indexed tokenId);
+    event ApprovalForAll(address indexed owner, address indexed operator, bool approved);
+}
+
+// Same errors as OpenZeppelin's `IERC721Errors`, for the calls that can revert with one.
+// Views only borrow the SDK, so they cannot write revert data and panic instead
+sol! {
+    error ERC721NonexistentToken(uint256 tokenId);
+    error ERC721IncorrectOwner(address sender, uint256 tokenId, address owner);
+    error ERC721InvalidSender(address sender);
+    error ERC721InvalidReceiver(address receiver);
+    error ERC721InsufficientApproval(address operator, uint256 tokenId);
+    error ERC721InvalidApprover(address approver);
+    error ERC721InvalidOperator(address operator);
+}
+
+/// ERC-165 interface ids reported by `supportsInterface`
+pub const INTERFACE_ID_ERC165: [u8; 4] = hex!("01ffc9a7");
+pub const INTERFACE_ID_ERC721: [u8; 4] = hex!("80ac58cd");
+pub const INTERFACE_ID_ERC721_METADATA: [u8; 4] = hex!("5b5e139f");
+pub const INTERFACE_ID_ERC721_ENUMERABLE: [u8; 4] = hex!("780e9d63");
+
+fn emit_event<SDK: SharedAPI, T: SolEvent>(sdk: &mut SDK, event: T) {
+    let data = event.encode_data();
+    let topics: Vec<B256> = event
+        .encode_topics()
+        .iter()
+        .map(|v| B256::from(v.0))
+        .collect();
+    sdk.emit_log(&topics, &data);
+}
+
+// Revert with ABI-encoded custom error data, so callers can decode the reason
+fn revert<SDK: SharedAPI, T: SolError>(sdk: &mut SDK, error: T) -> ! {
+    revert_with_data(sdk, &error.abi_encode())
+}
+
+fn revert_with_data<SDK: SharedAPI>(sdk: &mut SDK, data: &[u8]) -> ! {
+    sdk.write(data);
+    sdk.exit(ExitCode::Panic)
+}
+
+// Define ERC721 trait interface
+pub trait ERC721Interface {
+    // ERC-165
+    fn supports_interface(&self, interface_id: FixedBytes<4>) -> bool;
+
+    // ERC-721
+    fn balance_of(&self, owner: Address) -> U256;
+    fn owner_of(&self, token_id: U256) -> Address;
+    fn safe_transfer_from(&mut self, from: Address, to: Address, token_id: U256);
+    fn safe_transfer_from_with_data(
+        &mut self,
+        from: Address,
+        to: Address,
+        token_id: U256,
+        data: Bytes,
+    );
+    fn transfer_from(&mut self, from: Address, to: Address, token_id: U256);
+    fn approve(&mut self, to: Address, token_id: U256);
+    fn set_approval_for_all(&mut self, operator: Address, approved: bool);
+    fn get_approved(&self, token_id: U256) -> Address;
+    fn is_approved_for_all(&self, owner: Address, operator: Address) -> bool;
+
+    // Metadata
+    fn name(&self) -> String;
+    fn symbol(&self) -> String;
+    fn token_uri(&self, token_id: U256) -> String;
+
+    // Enumerable
+    fn total_supply(&self) -> U256;
+    fn token_by_index(&self, index: U256) -> U256;
+    fn token_of_owner_by_index(&self, owner: Address, index: U256) -> U256;
+
+    // Supply management
+    fn mint(&mut self, to: Address, token_id: U256);
+    fn burn(&mut self, token_id: U256);
+
+    // Ownership (Ownable2Step), the owner is the only minter
+    fn owner(&self) -> Address;
+    fn pending_owner(&self) -> Address;
+    fn transfer_ownership(&mut self, new_owner: Address);
+    fn accept_ownership(&mut self);
+    fn renounce_ownership(&mut self);
+}
+
+// Storage structure
+#[derive(Contract)]
+pub struct ERC721<SDK> {
+    sdk: SDK,
+    token_name: StorageString,
+    token_symbol: StorageString,
+    base_uri: StorageString,
+    owners: StorageMap<U256, StorageAddress>,
+    balances: StorageMap<Address, StorageU256>,
+    token_approvals: StorageMap<U256, StorageAddress>,
+    // Non-zero when the operator is approved
+    operator_approvals: StorageMap<Address, StorageMap<Address, StorageU256>>,
+}
+
+impl<SDK: SharedAPI> ERC721<SDK> {
+    fn enumerable_accessor(&self) -> Enumerable {
+        Enumerable
+    }
+
+    fn ownable_accessor(&self) -> Ownable2Step {
+        Ownable2Step::default()
+    }
+}
+
+// Shared token logic, following OpenZeppelin v5's `_update` and `_checkAuthorized`
+impl<SDK: SharedAPI> ERC721<SDK> {
+    fn require_owned(&mut self, token_id: U256) -> Address {
+        let owner = self.owners_accessor().entry(token_id).get(&self.sdk);
+        if owner == Address::ZERO {
+            revert(&mut self.sdk, ERC721NonexistentToken { tokenId: token_id });
+        }
+        owner
+    }
+
+    fn is_operator(&self, owner: Address, operator: Address) -> bool {
+        !self
+            .operator_approvals_accessor()
+            .entry(owner)
+            .entry(operator)
+            .get(&self.sdk)
+            .is_zero()
+    }
+
+    fn is_authorized(&self, owner: Address, spender: Address, token_id: U256) -> bool {
+        spender != Address::ZERO
+            && (owner == spender
+                || self.is_operator(owner, spender)
+                || self
+                    .token_approvals_accessor()
+                    .entry(token_id)
+                    .get(&self.sdk)
+                    == spender)
+    }
+
+    /// Moves `token_id` to `to`, minting from or burning to the zero address, and returns
+    /// its previous owner. A non-zero `auth` must be allowed to move the token.
+    fn update(&mut self, to: Address, token_id: U256, auth: Address) -> Address {
+        let from = self.owners_accessor().entry(token_id).get(&self.sdk);
+
+        if auth != Address::ZERO && !self.is_authorized(from, auth, token_id) {
+            if from == Address::ZERO {
+                revert(&mut self.sdk, ERC721NonexistentToken { tokenId: token_id });
+            }
+            revert(
+                &mut self.sdk,
+                ERC721InsufficientApproval {
+                    operator: auth,
+                    tokenId: token_id,
+                },
+            );
+        }
+
+        let from_balance = self.balances_accessor().entry(from).get(&self.sdk);
+        let to_balance = self.balances_accessor().entry(to).get(&self.sdk);
+        self.enumerable_accessor().on_transfer(
+            &mut self.sdk,
+            from,
+            from_balance,
+            to,
+            to_balance,
+            token_id,
+        );
+
+        if from != Address::ZERO {
+            // Clear the single-token approval, without an Approval event like OpenZeppelin v5
+            self.token_approvals_accessor()
+                .entry(token_id)
+                .set(&mut self.sdk, Address::ZERO);
+            self.balances_accessor()
+                .entry(from)
+                .set(&mut self.sdk, from_balance - U256::from(1));
+        }
+        if to != Address::ZERO {
+            // Re-read: `from` and `to` may be the same account
+            let to_balance = self.balances_accessor().entry(to).get(&self.sdk);
+            self.balances_accessor()
+                .entry(to)
+                .set(&mut self.sdk, to_balance + U256::from(1));
+        }
+        self.owners_accessor()
+            .entry(token_id)
+            .set(&mut self.sdk, to);
+
+        emit_event(
+            &mut self.sdk,
+            Transfer {
+                from,
+                to,
+                tokenId: token_id,
+            },
+        );
+        from
+    }
+
+    fn transfer(&mut self, from: Address, to: Address, token_id: U256) {
+        if to == Address::ZERO {
+            revert(&mut self.sdk, ERC721InvalidReceiver { receiver: to });
+        }
+
+        let caller = self.sdk.context().contract_caller();
+        let previous_owner = self.update(to, token_id, caller);
+        if previous_owner == Address::ZERO {
+            revert(&mut self.sdk, ERC721NonexistentToken { tokenId: token_id });
+        }
+        if previous_owner != from {
+            revert(
+                &mut self.sdk,
+                ERC721IncorrectOwner {
+                    sender: from,
+                    tokenId: token_id,
+                    owner: previous_owner,
+                },
+            );
+        }
+    }
+
+    fn safe_transfer(&mut self, from: Address, to: Address, token_id: U256, data: Bytes) {
+        self.transfer(from, to, token_id);
+
+        let operator = self.sdk.context().contract_caller();
+        match receiver::check_on_erc721_received(&mut self.sdk, operator, from, to, token_id, data)
+        {
+            Ok(()) => {}
+            Err(ReceiverError::Rejected) => {
+                revert(&mut self.sdk, ERC721InvalidReceiver { receiver: to })
+            }
+            // Bubble up the receiver's reason, like Solidity does
+            Err(ReceiverError::Reverted(reason)) => revert_with_data(&mut self.sdk, &reason),
+        }
+    }
+}
+
+// Separate constructor implementation. The deployer becomes the owner, the only minter
+#[constructor(mode = "solidity")]
+impl<SDK: SharedAPI> ERC721<SDK> {
+    pub fn constructor(&mut self, name: String, symbol: String, base_uri: String) {
+        self.token_name_accessor().set(&mut self.sdk, &name);
+        self.token_symbol_accessor().set(&mut self.sdk, &symbol);
+        self.base_uri_accessor().set(&mut self.sdk, &base_uri);
+
+        let deployer = self.sdk.context().contract_caller();
+        self.ownable_accessor().initialize(&mut self.sdk, deployer);
+    }
+}
+
+// Router implementation for trait methods
+#[router(mode = "solidity")]
+impl<SDK: SharedAPI> ERC721Interface for ERC721<SDK> {
+    #[function_id("supportsInterface(bytes4)")]
+    fn supports_interface(&self, interface_id: FixedBytes<4>) -> bool {
+        [
+            INTERFACE_ID_ERC165,
+            INTERFACE_ID_ERC721,
+            INTERFACE_ID_ERC721_METADATA,
+            INTERFACE_ID_ERC721_ENUMERABLE,
+        ]
+        .contains(&interface_id.0)
+    }
+
+    fn balance_of(&self, owner: Address) -> U256 {
+        if owner == Address::ZERO {
+            panic!("balance query for the zero address");
+        }
+        self.balances_accessor().entry(owner).get(&self.sdk)
+    }
+
+    fn owner_of(&self, token_id: U256) -> Address {
+        let owner = self.owners_accessor().entry(token_id).get(&self.sdk);
+        if owner == Address::ZERO {
+            panic!("nonexistent token");
+        }
+        owner
+    }
+
+    #[function_id("safeTransferFrom(address,address,uint256)")]
+    fn safe_transfer_from(&mut self, from: Address, to: Address, token_id: U256) {
+        self.safe_transfer(from, to, token_id, Bytes::new());
+    }
+
+    #[function_id("safeTransferFrom(address,address,uint256,bytes)")]
+    fn safe_transfer_from_with_data(
+        &mut self,
+        from: Address,
+        to: Address,
+        token_id: U256,
+        data: Bytes,
+    ) {
+        self.safe_transfer(from, to, token_id, data);
+    }
+
+    fn transfer_from(&mut self, from: Address, to: Address, token_id: U256) {
+        self.transfer(from, to, token_id);
+    }
+
+    fn approve(&mut self, to: Address, token_id: U256) {
+        let caller = self.sdk.context().contract_caller();
+        let owner = self.require_owned(token_id);
+        if caller != owner && !self.is_operator(owner, caller) {
+            revert(&mut self.sdk, ERC721InvalidApprover { approver: caller });
+        }
+
+        self.token_approvals_accessor()
+            .entry(token_id)
+            .set(&mut self.sdk, to);
+
+        emit_event(
+            &mut self.sdk,
+            Approval {
+                owner,
+                approved: to,
+                tokenId: token_id,
+            },
+        );
+    }
+
+    fn set_approval_for_all(&mut self, operator: Address, approved: bool) {
+        let owner = self.sdk.context().contract_caller();
+        if operator == Address::ZERO {
+            revert(&mut self.sdk, ERC721InvalidOperator { operator });
+        }
+
+        self.operator_approvals_accessor()
+            .entry(owner)
+            .entry(operator)
+            .set(&mut self.sdk, U256::from(approved as u8));
+
+        emit_event(
+            &mut self.sdk,
+            ApprovalForAll {
+                owner,
+                operator,
+                approved,
+            },
+        );
+    }
+
+    fn get_approved(&self, token_id: U256) -> Address {
+        self.owner_of(token_id);
+        self.token_approvals_accessor()
+            .entry(token_id)
+            .get(&self.sdk)
+    }
+
+    fn is_approved_for_all(&self, owner: Address, operator: Address) -> bool {
+        self.is_operator(owner, operator)
+    }
+
+    fn name(&self) -> String {
+        self.token_name_accessor().get(&self.sdk)
+    }
+
+    fn symbol(&self) -> String {
+        self.token_symbol_accessor().get(&self.sdk)
+    }
+
+    // `base_uri` followed by the decimal token id, or empty without a base URI
+    fn token_uri(&self, token_id: U256) -> String {
+        self.owner_of(token_id);
+
+        let base_uri = self.base_uri_accessor().get(&self.sdk);
+        if base_uri.is_empty() {
+            return String::new();
+        }
+        base_uri + &token_id.to_string()
+    }
+
+    fn total_supply(&self) -> U256 {
+        self.enumerable_accessor().total_supply(&self.sdk)
+    }
+
+    fn token_by_index(&self, index: U256) -> U256 {
+        match self.enumerable_accessor().token_by_index(&self.sdk, index) {
+            Some(token_id) => token_id,
+            None => panic!("index out of bounds"),
+        }
+    }
+
+    fn token_of_owner_by_index(&self, owner: Address, index: U256) -> U256 {
+        let balance = self.balance_of(owner);
+        match self
+            .enumerable_accessor()
+            .token_of_owner_by_index(&self.sdk, owner, balance, index)
+        {
+            Some(token_id) => token_id,
+            None => panic!("owner index out of bounds"),
+        }
+    }
+
+    fn mint(&mut self, to: Address, token_id: U256) {
+        self.ownable_accessor().only_owner(&mut self.sdk);
+        if to == Address::ZERO {
+            revert(&mut self.sdk, ERC721InvalidReceiver { receiver: to });
+        }
+
+        let previous_owner = self.update(to, token_id, Address::ZERO);
+        if previous_owner != Address::ZERO {
+            revert(
+                &mut self.sdk,
+                ERC721InvalidSender {
+                    sender: Address::ZERO,
+                },
+            );
+        }
+    }
+
+    // Callable by the owner, an approved account or an operator
+    fn burn(&mut self, token_id: U256) {
+        let caller = self.sdk.context().contract_caller();
+        let previous_owner = self.update(Address::ZERO, token_id, caller);
+        if previous_owner == Address::ZERO {
+            revert(&mut self.sdk, ERC721NonexistentToken { tokenId: token_id });
+        }
+    }
+
+    fn owner(&self) -> Address {
+        self.ownable_accessor().owner(&self.sdk)
+    }
+
+    fn pending_owner(&self) -> Address {
+        self.ownable_accessor().pending_owner(&self.sdk)
+    }
+
+    fn transfer_ownership(&mut self, new_owner: Address) {
+        self.ownable_accessor()
+            .transfer_ownership(&mut self.sdk, new_owner);
+    }
+
+    fn accept_ownership(&mut self) {
+        self.ownable_accessor().accept_ownership(&mut self.sdk);
+    }
+
+    fn renounce_ownership(&mut self) {
+        self.ownable_accessor().renounce_ownership(&mut self.sdk);
+    }
+}
+
+basic_entrypoint!(ERC721);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::NftHarness;
+    use contract_utils::ownable::{OwnableUnauthorizedAccount, OwnershipTransferred};
+    use fluentbase_sdk::address;
+
+    const MINTER: Address = address!("1111111111111111111111111111111111111111");
+    const ALICE: Address = address!("2222222222222222222222222222222222222222");
+    const BOB: Address = address!("3333333333333333333333333333333333333333");
+    const OPERATOR: Address = address!("4444444444444444444444444444444444444444");
+    const STRANGER: Address = address!("6666666666666666666666666666666666666666");
+
+    // Alice holds tokens 1, 2 and 3
+    fn minted() -> NftHarness {
+        let mut nft = NftHarness::deploy(MINTER, "Tiles", "TILE", "https://tiles.example/");
+        for token_id in 1..=3 {
+            nft.mint(MINTER, ALICE, token_id).unwrap();
+        }
+        nft
+    }
+
+    #[test]
+    fn test_metadata_and_interfaces() {
+        let mut nft = minted();
+
+        assert_eq!(nft.name(), "Tiles");
+        assert_eq!(nft.symbol(), "TILE");
+        assert_eq!(nft.token_uri(2).unwrap(), "https://tiles.example/2");
+        assert!(nft.token_uri(9).is_err());
+
+        for interface_id in [
+            INTERFACE_ID_ERC165,
+            INTERFACE_ID_ERC721,
+            INTERFACE_ID_ERC721_METADATA,
+            INTERFACE_ID_ERC721_ENUMERABLE,
+        ] {
+            assert!(nft.supports_interface(interface_id));
+        }
+        assert!(!nft.supports_interface([0xff; 4]));
+    }
+
+    #[test]
+    fn test_token_uri_empty_without_base_uri() {
+        let mut nft = NftHarness::deploy(MINTER, "Tiles", "TILE", "");
+        nft.mint(MINTER, ALICE, 1).unwrap();
+
+        assert_eq!(nft.token_uri(1).unwrap(), "");
+    }
+
+    #[test]
+    fn test_mint() {
+        let mut nft = minted();
+
+        assert_eq!(nft.balance_of(ALICE).unwrap(), U256::from(3));
+        assert_eq!(nft.owner_of(1).unwrap(), ALICE);
+        assert!(nft.owner_of(4).is_err());
+        assert!(nft.balance_of(Address::ZERO).is_err());
+
+        let transfers = nft.events::<Transfer>();
+        assert_eq!(transfers.len(), 3);
+        assert_eq!(transfers[0].from, Address::ZERO);
+        assert_eq!(transfers[0].tokenId, U256::from(1));
+
+        // Verify: Only the deployer mints, and each id only once
+        let revert = nft.mint(STRANGER, BOB, 4).unwrap_err();
+        assert_eq!(
+            revert.decode::<OwnableUnauthorizedAccount>().account,
+            STRANGER
+        );
+        let revert = nft.mint(MINTER, BOB, 1).unwrap_err();
+        assert_eq!(revert.decode::<ERC721InvalidSender>().sender, Address::ZERO);
+        let revert = nft.mint(MINTER, Address::ZERO, 4).unwrap_err();
+        revert.decode::<ERC721InvalidReceiver>();
+    }
+
+    #[test]
+    fn test_ownership_moves_minting_rights() {
+        let mut nft = minted();
+        assert_eq!(nft.owner(), MINTER);
+        assert_eq!(nft.events::<OwnershipTransferred>()[0].newOwner, MINTER);
+
+        // Execute: The deployer hands minting over in two steps
+        nft.transfer_ownership(MINTER, BOB).unwrap();
+        assert_eq!(nft.pending_owner(), BOB);
+        nft.mint(BOB, BOB, 4).unwrap_err();
+        nft.accept_ownership(BOB).unwrap();
+
+        // Verify: Only the new owner mints
+        nft.mint(BOB, BOB, 4).unwrap();
+        let revert = nft.mint(MINTER, BOB, 5).unwrap_err();
+        assert_eq!(
+            revert.decode::<OwnableUnauthorizedAccount>().account,
+            MINTER
+        );
+    }
+
+    #[test]
+    fn test_transfer_from_by_owner() {
+        let mut nft = minted();
+
+        nft.transfer_from(ALICE, ALICE, BOB, 2).unwrap();
+
+        assert_eq!(nft.owner_of(2).unwrap(), BOB);
+        assert_eq!(nft.balance_of(ALICE).unwrap(), U256::from(2));
+        assert_eq!(nft.balance_of(BOB).unwrap(), U256::from(1));
+        let last = nft.events::<Transfer>().pop().unwrap();
+        assert_eq!((last.from, last.to), (ALICE, BOB));
+
+        // Verify: `from` must be the current owner
+        let revert = nft.transfer_from(BOB, ALICE, BOB, 2).unwrap_err();
+        let error = revert.decode::<ERC721IncorrectOwner>();
+        assert_eq!((error.sender, error.owner), (ALICE, BOB));
+    }
+
+    #[test]
+    fn test_transfer_from_requires_approval() {
+        let mut nft = minted();
+
+        let revert = nft.transfer_from(STRANGER, ALICE, BOB, 1).unwrap_err();
+        let error = revert.decode::<ERC721InsufficientApproval>();
+        assert_eq!((error.operator, error.tokenId), (STRANGER, U256::from(1)));
+
+        let revert = nft.transfer_from(ALICE, ALICE, BOB, 9).unwrap_err();
+        revert.decode::<ERC721NonexistentToken>();
+        let revert = nft
+            .transfer_from(ALICE, ALICE, Address::ZERO, 1)
+            .unwrap_err();
+        revert.decode::<ERC721InvalidReceiver>();
+    }
+
+    #[test]
+    fn test_single_token_approval() {
+        let mut nft = minted();
+
+        nft.approve(ALICE, BOB, 1).unwrap();
+        assert_eq!(nft.get_approved(1).unwrap(), BOB);
+        let approval = nft.events::<Approval>().pop().unwrap();
+        assert_eq!(
+            (approval.owner, approval.approved, approval.tokenId),
+            (ALICE, BOB, U256::from(1))
+        );
+
+        // Execute: Bob moves the approved token to himself
+        nft.transfer_from(BOB, ALICE, BOB, 1).unwrap();
+
+        // Verify: The approval is cleared by the transfer, and did not cover other tokens
+        assert_eq!(nft.get_approved(1).unwrap(), Address::ZERO);
+        assert!(nft.transfer_from(BOB, ALICE, BOB, 2).is_err());
+
+        // Verify: Only the owner or an operator approves
+        let revert = nft.approve(STRANGER, STRANGER, 2).unwrap_err();
+        assert_eq!(revert.decode::<ERC721InvalidApprover>().approver, STRANGER);
+    }
+
+    #[test]
+    fn test_operator_approval() {
+        let mut nft = minted();
+
+        nft.set_approval_for_all(ALICE, OPERATOR, true).unwrap();
+        assert!(nft.is_approved_for_all(ALICE, OPERATOR));
+        let event = nft.events::<ApprovalForAll>().pop().unwrap();
+        assert!(event.approved);
+
+        // Execute: The operator approves for Alice and moves her tokens
+        nft.approve(OPERATOR, STRANGER, 3).unwrap();
+        nft.transfer_from(OPERATOR, ALICE, BOB, 1).unwrap();
+        assert_eq!(nft.owner_of(1).unwrap(), BOB);
+        assert_eq!(nft.get_approved(3).unwrap(), STRANGER);
+
+        // Verify: Revoking takes effect immediately
+        nft.set_approval_for_all(ALICE, OPERATOR, false).unwrap();
+        assert!(!nft.is_approved_for_all(ALICE, OPERATOR));
+        assert!(nft.transfer_from(OPERATOR, ALICE, BOB, 2).is_err());
+
+        let revert = nft
+            .set_approval_for_all(ALICE, Address::ZERO, true)
+            .unwrap_err();
+        revert.decode::<ERC721InvalidOperator>();
+    }
+
+    #[test]
+    fn test_safe_transfer_to_account_without_code() {
+        let mut nft = minted();
+
+        nft.safe_transfer_from(ALICE, ALICE, BOB, 1).unwrap();
+        nft.safe_transfer_from_with_data(ALICE, ALICE, BOB, 2, b"data")
+            .unwrap();
+
+        assert_eq!(nft.tokens_of(BOB), vec![1, 2]);
+    }
+
+    #[test]
+    fn test_safe_transfer_to_receiver_contract() {
+        use alloy_sol_types::{SolCall, SolValue};
+        use contract_utils::chain;
+        use receiver::IERC721Receiver::onERC721ReceivedCall;
+
+        const ACCEPTS: Address = address!("a1a1a1a1a1a1a1a1a1a1a1a1a1a1a1a1a1a1a1a1");
+        const REJECTS: Address = address!("b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2");
+        const REVERTS: Address = address!("c3c3c3c3c3c3c3c3c3c3c3c3c3c3c3c3c3c3c3c3");
+        let mut nft = minted();
+
+        chain::register(ACCEPTS, |call| {
+            let received = onERC721ReceivedCall::abi_decode(&call.input).unwrap();
+            assert_eq!((received.operator, received.from), (ALICE, ALICE));
+            assert_eq!(received.data, Bytes::from_static(b"data"));
+            let selector = FixedBytes::from(onERC721ReceivedCall::SELECTOR);
+            (true, Bytes::from(selector.abi_encode()))
+        });
+        chain::register(REJECTS, |_| {
+            (true, Bytes::from(FixedBytes::<4>::ZERO.abi_encode()))
+        });
+        chain::register(REVERTS, |_| {
+            let reason = OwnableUnauthorizedAccount { account: STRANGER };
+            (false, Bytes::from(reason.abi_encode()))
+        });
+
+        nft.safe_transfer_from_with_data(ALICE, ALICE, ACCEPTS, 1, b"data")
+            .unwrap();
+        assert_eq!(nft.owner_of(1).unwrap(), ACCEPTS);
+
+        // Verify: A wrong answer reverts with the receiver, a revert bubbles up its reason
+        let revert = nft
+            .safe_transfer_from(ALICE, ALICE, REJECTS, 2)
+            .unwrap_err();
+        assert_eq!(revert.decode::<ERC721InvalidReceiver>().receiver, REJECTS);
+        let revert = nft
+            .safe_transfer_from(ALICE, ALICE, REVERTS, 3)
+            .unwrap_err();
+        assert_eq!(
+            revert.decode::<OwnableUnauthorizedAccount>().account,
+            STRANGER
+        );
+    }
+
+    #[test]
+    fn test_enumeration_follows_transfers_and_burns() {
+        let mut nft = minted();
+        assert_eq!(nft.total_supply(), U256::from(3));
+
+        // Execute: Alice's first token leaves, her last one takes its index
+        nft.transfer_from(ALICE, ALICE, BOB, 1).unwrap();
+        assert_eq!(nft.tokens_of(ALICE), vec![3, 2]);
+        assert_eq!(nft.tokens_of(BOB), vec![1]);
+
+        nft.burn(ALICE, 3).unwrap();
+        assert_eq!(nft.total_supply(), U256::from(2));
+        assert_eq!(nft.token_by_index(0).unwrap(), U256::from(1));
+        assert_eq!(nft.token_by_index(1).unwrap(), U256::from(2));
+        assert!(nft.token_by_index(2).is_err());
+        assert!(nft.token_of_owner_by_index(ALICE, 1).is_err());
+        assert!(nft.owner_of(3).is_err());
+    }
+
+    #[test]
+    fn test_burn_requires_approval() {
+        let mut nft = minted();
+
+        let revert = nft.burn(STRANGER, 1).unwrap_err();
+        revert.decode::<ERC721InsufficientApproval>();
+
+        nft.approve(ALICE, STRANGER, 1).unwrap();
+        nft.burn(STRANGER, 1).unwrap();
+        let last = nft.events::<Transfer>().pop().unwrap();
+        assert_eq!((last.from, last.to), (ALICE, Address::ZERO));
+
+        let revert = nft.burn(ALICE, 1).unwrap_err();
+        revert.decode::<ERC721NonexistentToken>();
+    }
+}
@@ -0,0 +1,187 @@
+//! `onERC721Received` check for safe transfers.
+//!
+//! The check makes an external call, so it is written against [`CallContract`] rather than
+//! `SharedAPI`. Every `SharedAPI` context implements it, and host tests implement it to
+//! stand in for receiver contracts.
+
+use alloy_sol_types::{sol, SolCall};
+use contract_utils::CallContract;
+use fluentbase_sdk::{Address, Bytes, U256};
+
+sol! {
+    interface IERC721Receiver {
+        function onERC721Received(address operator, address from, uint256 tokenId, bytes data)
+            external
+            returns (bytes4);
+    }
+}
+
+/// Why a receiver did not accept a token.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ReceiverError {
+    /// The receiver returned something other than the `onERC721Received` selector, or
+    /// reverted without a reason.
+    Rejected,
+    /// The receiver reverted with this reason.
+    Reverted(Bytes),
+}
+
+/// Checks that `to` accepts `token_id`, the same way OpenZeppelin's `ERC721Utils` does.
+///
+/// Accounts without code always accept.
+pub fn check_on_erc721_received<SDK: CallContract>(
+    sdk: &mut SDK,
+    operator: Address,
+    from: Address,
+    to: Address,
+    token_id: U256,
+    data: Bytes,
+) -> Result<(), ReceiverError> {
+    if !sdk.has_code(to) {
+        return Ok(());
+    }
+
+    let call = IERC721Receiver::onERC721ReceivedCall {
+        operator,
+        from,
+        tokenId: token_id,
+        data,
+    };
+    let (success, output) = sdk.call_contract(to, &call.abi_encode(), None);
+    if !success {
+        return if output.is_empty() {
+            Err(ReceiverError::Rejected)
+        } else {
+            Err(ReceiverError::Reverted(output))
+        };
+    }
+
+    match IERC721Receiver::onERC721ReceivedCall::abi_decode_returns(&output) {
+        Ok(selector) if selector == IERC721Receiver::onERC721ReceivedCall::SELECTOR => Ok(()),
+        _ => Err(ReceiverError::Rejected),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_sol_types::{Revert, SolError, SolValue};
+    use fluentbase_sdk::{address, FixedBytes};
+
+    const OPERATOR: Address = address!("1111111111111111111111111111111111111111");
+    const FROM: Address = address!("2222222222222222222222222222222222222222");
+    const EOA: Address = address!("3333333333333333333333333333333333333333");
+    const RECEIVER: Address = address!("cccccccccccccccccccccccccccccccccccccccc");
+
+    /// A receiver contract, reduced to how it answers `onERC721Received`.
+    enum Receiver {
+        Accepts,
+        ReturnsWrongSelector,
+        RevertsWithReason,
+        RevertsSilently,
+        /// Has no `onERC721Received`, so the call succeeds with no output.
+        NoHook,
+    }
+
+    struct MockChain {
+        receiver: Receiver,
+        last_call: Option<IERC721Receiver::onERC721ReceivedCall>,
+    }
+
+    impl CallContract for MockChain {
+        fn call_contract(
+            &mut self,
+            target: Address,
+            input: &[u8],
+            _fuel_limit: Option<u64>,
+        ) -> (bool, Bytes) {
+            assert_eq!(target, RECEIVER);
+            self.last_call =
+                Some(IERC721Receiver::onERC721ReceivedCall::abi_decode(input).unwrap());
+            let selector = IERC721Receiver::onERC721ReceivedCall::SELECTOR;
+            match self.receiver {
+                Receiver::Accepts => (true, Bytes::from(FixedBytes(selector).abi_encode())),
+                Receiver::ReturnsWrongSelector => (
+                    true,
+                    Bytes::from(FixedBytes([0xde, 0xad, 0xbe, 0xef]).abi_encode()),
+                ),
+                Receiver::RevertsWithReason => {
+                    (false, Bytes::from(Revert::from("no thanks").abi_encode()))
+                }
+                Receiver::RevertsSilently => (false, Bytes::new()),
+                Receiver::NoHook => (true, Bytes::new()),
+            }
+        }
+
+        fn has_code(&self, account: Address) -> bool {
+            account == RECEIVER
+        }
+
+        fn send_native(
+            &mut self,
+            _to: Address,
+            _amount: U256,
+            _fuel_limit: Option<u64>,
+        ) -> (bool, Bytes) {
+            unreachable!("receiver checks never send native value")
+        }
+    }
+
+    fn check(receiver: Receiver, to: Address) -> (Result<(), ReceiverError>, MockChain) {
+        let mut chain = MockChain {
+            receiver,
+            last_call: None,
+        };
+        let result = check_on_erc721_received(
+            &mut chain,
+            OPERATOR,
+            FROM,
+            to,
+            U256::from(7),
+            Bytes::from_static(b"hello"),
+        );
+        (result, chain)
+    }
+
+    #[test]
+    fn test_accepting_receiver_gets_call_details() {
+        let (result, chain) = check(Receiver::Accepts, RECEIVER);
+
+        assert_eq!(result, Ok(()));
+        let call = chain.last_call.unwrap();
+        assert_eq!((call.operator, call.from), (OPERATOR, FROM));
+        assert_eq!(call.tokenId, U256::from(7));
+        assert_eq!(&call.data[..], b"hello");
+    }
+
+    #[test]
+    fn test_account_without_code_is_not_called() {
+        let (result, chain) = check(Receiver::RevertsSilently, EOA);
+
+        assert_eq!(result, Ok(()));
+        assert!(chain.last_call.is_none());
+    }
+
+    #[test]
+    fn test_rejections() {
+        assert_eq!(
+            check(Receiver::ReturnsWrongSelector, RECEIVER).0,
+            Err(ReceiverError::Rejected)
+        );
+        assert_eq!(
+            check(Receiver::RevertsSilently, RECEIVER).0,
+            Err(ReceiverError::Rejected)
+        );
+        assert_eq!(
+            check(Receiver::NoHook, RECEIVER).0,
+            Err(ReceiverError::Rejected)
+        );
+
+        // Verify: A reason is kept so the token can bubble it up
+        let (result, _) = check(Receiver::RevertsWithReason, RECEIVER);
+        let Err(ReceiverError::Reverted(reason)) = result else {
+            panic!("expected a revert reason, got {result:?}");
+        };
+        assert_eq!(Revert::abi_decode(&reason).unwrap().reason, "no thanks");
+    }
+}
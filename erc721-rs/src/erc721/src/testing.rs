@@ -0,0 +1,235 @@
+//! Host test harness for the NFT.
+//!
+//! [`NftHarness`] wraps the shared `contract_utils::testing::Harness`, which owns an
+//! `ERC721<ChainContext<HostTestingContext>>`, calls it through the router like a transaction
+//! would, and keeps every emitted log. This module only adds one typed method per router
+//! entrypoint.
+
+use crate::*;
+use contract_utils::testing::{ChainContext, Harness, HostContract};
+use core::ops::{Deref, DerefMut};
+use fluentbase_sdk::{address, codec::Encoder};
+use fluentbase_testing::HostTestingContext;
+use std::string::ToString;
+
+pub use contract_utils::testing::{CallResult, Revert};
+
+pub const NFT_ADDRESS: Address = address!("aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa");
+
+impl HostContract for ERC721<ChainContext<HostTestingContext>> {
+    fn with_host(sdk: HostTestingContext) -> Self {
+        Self::new(ChainContext::new(sdk))
+    }
+
+    fn host(&self) -> &HostTestingContext {
+        &self.sdk
+    }
+
+    fn host_mut(&mut self) -> &mut HostTestingContext {
+        &mut self.sdk
+    }
+
+    fn run_deploy(&mut self) {
+        self.deploy();
+    }
+
+    fn run_main(&mut self) {
+        self.main();
+    }
+}
+
+pub struct NftHarness(Harness<ERC721<ChainContext<HostTestingContext>>>);
+
+impl Deref for NftHarness {
+    type Target = Harness<ERC721<ChainContext<HostTestingContext>>>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for NftHarness {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl NftHarness {
+    /// Deploys through the constructor, as `deployer`.
+    pub fn deploy(deployer: Address, name: &str, symbol: &str, base_uri: &str) -> Self {
+        let input =
+            ConstructorCall::new((name.to_string(), symbol.to_string(), base_uri.to_string()))
+                .encode();
+        Self(Harness::deploy(NFT_ADDRESS, deployer, input))
+    }
+
+    // Typed calls, one per router entrypoint
+
+    pub fn supports_interface(&mut self, interface_id: [u8; 4]) -> bool {
+        let input = SupportsInterfaceCall::new((FixedBytes(interface_id),)).encode();
+        let output = self.call(Address::ZERO, input).unwrap();
+        SupportsInterfaceReturn::decode(&&output[..]).unwrap().0 .0
+    }
+
+    pub fn balance_of(&mut self, owner: Address) -> CallResult<U256> {
+        let output = self.call(Address::ZERO, BalanceOfCall::new((owner,)).encode())?;
+        Ok(BalanceOfReturn::decode(&&output[..]).unwrap().0 .0)
+    }
+
+    pub fn owner_of(&mut self, token_id: u64) -> CallResult<Address> {
+        let input = OwnerOfCall::new((U256::from(token_id),)).encode();
+        let output = self.call(Address::ZERO, input)?;
+        Ok(OwnerOfReturn::decode(&&output[..]).unwrap().0 .0)
+    }
+
+    pub fn safe_transfer_from(
+        &mut self,
+        caller: Address,
+        from: Address,
+        to: Address,
+        token_id: u64,
+    ) -> CallResult<()> {
+        let input = SafeTransferFromCall::new((from, to, U256::from(token_id))).encode();
+        self.call(caller, input).map(|_| ())
+    }
+
+    pub fn safe_transfer_from_with_data(
+        &mut self,
+        caller: Address,
+        from: Address,
+        to: Address,
+        token_id: u64,
+        data: &[u8],
+    ) -> CallResult<()> {
+        let input = SafeTransferFromWithDataCall::new((
+            from,
+            to,
+            U256::from(token_id),
+            Bytes::copy_from_slice(data),
+        ))
+        .encode();
+        self.call(caller, input).map(|_| ())
+    }
+
+    pub fn transfer_from(
+        &mut self,
+        caller: Address,
+        from: Address,
+        to: Address,
+        token_id: u64,
+    ) -> CallResult<()> {
+        let input = TransferFromCall::new((from, to, U256::from(token_id))).encode();
+        self.call(caller, input).map(|_| ())
+    }
+
+    pub fn approve(&mut self, caller: Address, to: Address, token_id: u64) -> CallResult<()> {
+        let input = ApproveCall::new((to, U256::from(token_id))).encode();
+        self.call(caller, input).map(|_| ())
+    }
+
+    pub fn set_approval_for_all(
+        &mut self,
+        owner: Address,
+        operator: Address,
+        approved: bool,
+    ) -> CallResult<()> {
+        let input = SetApprovalForAllCall::new((operator, approved)).encode();
+        self.call(owner, input).map(|_| ())
+    }
+
+    pub fn get_approved(&mut self, token_id: u64) -> CallResult<Address> {
+        let input = GetApprovedCall::new((U256::from(token_id),)).encode();
+        let output = self.call(Address::ZERO, input)?;
+        Ok(GetApprovedReturn::decode(&&output[..]).unwrap().0 .0)
+    }
+
+    pub fn is_approved_for_all(&mut self, owner: Address, operator: Address) -> bool {
+        let input = IsApprovedForAllCall::new((owner, operator)).encode();
+        let output = self.call(Address::ZERO, input).unwrap();
+        IsApprovedForAllReturn::decode(&&output[..]).unwrap().0 .0
+    }
+
+    pub fn name(&mut self) -> String {
+        let output = self
+            .call(Address::ZERO, NameCall::new(()).encode())
+            .unwrap();
+        NameReturn::decode(&&output[..]).unwrap().0 .0
+    }
+
+    pub fn symbol(&mut self) -> String {
+        let output = self
+            .call(Address::ZERO, SymbolCall::new(()).encode())
+            .unwrap();
+        SymbolReturn::decode(&&output[..]).unwrap().0 .0
+    }
+
+    pub fn token_uri(&mut self, token_id: u64) -> CallResult<String> {
+        let input = TokenUriCall::new((U256::from(token_id),)).encode();
+        let output = self.call(Address::ZERO, input)?;
+        Ok(TokenUriReturn::decode(&&output[..]).unwrap().0 .0)
+    }
+
+    pub fn total_supply(&mut self) -> U256 {
+        let output = self
+            .call(Address::ZERO, TotalSupplyCall::new(()).encode())
+            .unwrap();
+        TotalSupplyReturn::decode(&&output[..]).unwrap().0 .0
+    }
+
+    pub fn token_by_index(&mut self, index: u64) -> CallResult<U256> {
+        let input = TokenByIndexCall::new((U256::from(index),)).encode();
+        let output = self.call(Address::ZERO, input)?;
+        Ok(TokenByIndexReturn::decode(&&output[..]).unwrap().0 .0)
+    }
+
+    pub fn token_of_owner_by_index(&mut self, owner: Address, index: u64) -> CallResult<U256> {
+        let input = TokenOfOwnerByIndexCall::new((owner, U256::from(index))).encode();
+        let output = self.call(Address::ZERO, input)?;
+        Ok(TokenOfOwnerByIndexReturn::decode(&&output[..])
+            .unwrap()
+            .0
+             .0)
+    }
+
+    pub fn mint(&mut self, caller: Address, to: Address, token_id: u64) -> CallResult<()> {
+        let input = MintCall::new((to, U256::from(token_id))).encode();
+        self.call(caller, input).map(|_| ())
+    }
+
+    pub fn burn(&mut self, caller: Address, token_id: u64) -> CallResult<()> {
+        let input = BurnCall::new((U256::from(token_id),)).encode();
+        self.call(caller, input).map(|_| ())
+    }
+
+    pub fn owner(&mut self) -> Address {
+        let output = self
+            .call(Address::ZERO, OwnerCall::new(()).encode())
+            .unwrap();
+        OwnerReturn::decode(&&output[..]).unwrap().0 .0
+    }
+
+    pub fn pending_owner(&mut self) -> Address {
+        let output = self
+            .call(Address::ZERO, PendingOwnerCall::new(()).encode())
+            .unwrap();
+        PendingOwnerReturn::decode(&&output[..]).unwrap().0 .0
+    }
+
+    pub fn transfer_ownership(&mut self, caller: Address, new_owner: Address) -> CallResult<()> {
+        self.call(caller, TransferOwnershipCall::new((new_owner,)).encode())
+            .map(|_| ())
+    }
+
+    pub fn accept_ownership(&mut self, caller: Address) -> CallResult<()> {
+        self.call(caller, AcceptOwnershipCall::new(()).encode())
+            .map(|_| ())
+    }
+
+    /// All tokens `owner` holds, in enumeration order.
+    pub fn tokens_of(&mut self, owner: Address) -> Vec<u64> {
+        let balance = self.balance_of(owner).unwrap().to::<u64>();
+        (0..balance)
+            .map(|i| self.token_of_owner_by_index(owner, i).unwrap().to::<u64>())
+            .collect()
+    }
+}
@@ -0,0 +1,97 @@
+//! External calls from one contract to another.
+//!
+//! Code that calls out is written against [`CallContract`] rather than `SharedAPI`:
+//!
+//! ```ignore
+//! fn check_receiver<SDK: CallContract>(sdk: &mut SDK, to: Address) -> bool {
+//!     !sdk.has_code(to) || sdk.call_contract(to, &input, None).0
+//! }
+//! ```
+//!
+//! Every `SharedAPI` context implements it, so contracts pass `&mut self.sdk`, and unit tests
+//! implement it on a small mock to stand in for the contracts on the other end. Host tests that
+//! need real contracts on the other end wrap their context in the `testing` feature's
+//! `chain::ChainContext`, whose calls reach the in-memory chain.
+
+use fluentbase_sdk::{Address, Bytes, SharedAPI, U256};
+
+/// The host capability external calls need.
+pub trait CallContract {
+    /// Calls `target` with `input`, returning whether it succeeded and its output.
+    fn call_contract(
+        &mut self,
+        target: Address,
+        input: &[u8],
+        fuel_limit: Option<u64>,
+    ) -> (bool, Bytes);
+
+    /// Calls `target` with `input` without allowing it to change state, e.g. for views.
+    ///
+    /// Defaults to [`Self::call_contract`], which is enough for mocks.
+    fn static_call_contract(
+        &mut self,
+        target: Address,
+        input: &[u8],
+        fuel_limit: Option<u64>,
+    ) -> (bool, Bytes) {
+        self.call_contract(target, input, fuel_limit)
+    }
+
+    /// Runs the code of `target` with `input` on the caller's own storage, e.g. for proxies.
+    ///
+    /// Defaults to [`Self::call_contract`], which is enough for mocks.
+    fn delegate_call_contract(
+        &mut self,
+        target: Address,
+        input: &[u8],
+        fuel_limit: Option<u64>,
+    ) -> (bool, Bytes) {
+        self.call_contract(target, input, fuel_limit)
+    }
+
+    fn has_code(&self, account: Address) -> bool;
+
+    /// Sends `amount` of the native currency to `to`, without call data.
+    fn send_native(&mut self, to: Address, amount: U256, fuel_limit: Option<u64>) -> (bool, Bytes);
+}
+
+impl<SDK: SharedAPI> CallContract for SDK {
+    fn call_contract(
+        &mut self,
+        target: Address,
+        input: &[u8],
+        fuel_limit: Option<u64>,
+    ) -> (bool, Bytes) {
+        let result = self.call(target, U256::ZERO, input, fuel_limit);
+        (result.status.is_ok(), result.data)
+    }
+
+    fn static_call_contract(
+        &mut self,
+        target: Address,
+        input: &[u8],
+        fuel_limit: Option<u64>,
+    ) -> (bool, Bytes) {
+        let result = self.static_call(target, input, fuel_limit);
+        (result.status.is_ok(), result.data)
+    }
+
+    fn delegate_call_contract(
+        &mut self,
+        target: Address,
+        input: &[u8],
+        fuel_limit: Option<u64>,
+    ) -> (bool, Bytes) {
+        let result = self.delegate_call(target, input, fuel_limit);
+        (result.status.is_ok(), result.data)
+    }
+
+    fn has_code(&self, account: Address) -> bool {
+        self.code_size(&account).data > 0
+    }
+
+    fn send_native(&mut self, to: Address, amount: U256, fuel_limit: Option<u64>) -> (bool, Bytes) {
+        let result = self.call(to, amount, &[], fuel_limit);
+        (result.status.is_ok(), result.data)
+    }
+}
@@ -0,0 +1,381 @@
+//! In-memory chain for host tests.
+//!
+//! `HostTestingContext` cannot call other contracts, so harnesses wrap it in a [`ChainContext`],
+//! whose external calls land here instead. A contract on the chain is a handler that answers calls to its address, either a whole
+//! contract under a harness (see [`testing::on_chain`](crate::testing::on_chain)) or a closure
+//! standing in for one:
+//!
+//! ```ignore
+//! let token = on_chain(TokenHarness::deploy(OWNER, "Token", "TKN", U256::from(1_000)));
+//! chain::register(REJECTING_RECEIVER, |_call| (false, Bytes::new()));
+//! ```
+//!
+//! Contracts are registered per thread, and every test runs on its own thread, so tests do not
+//! see each other's contracts. A call to an address without a contract behaves like a call to
+//! an account without code: it succeeds with no output.
+
+use fluentbase_sdk::{
+    Address, Bytes, ContextReader, ExitCode, MetadataAPI, SharedAPI, StorageAPI, SyscallResult,
+    B256, U256,
+};
+use std::{
+    boxed::Box,
+    cell::RefCell,
+    collections::HashMap,
+    ops::{Deref, DerefMut},
+    rc::Rc,
+};
+
+/// How a contract was called.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CallKind {
+    Call,
+    /// A call that must not change state. The chain does not enforce it.
+    StaticCall,
+    /// A call whose code runs on the caller's storage. `caller` is the caller's own caller,
+    /// and the handler must reach the caller's storage itself.
+    DelegateCall,
+}
+
+/// A call, as the contract receiving it sees it.
+#[derive(Clone, Debug)]
+pub struct Call {
+    pub kind: CallKind,
+    pub caller: Address,
+    pub target: Address,
+    pub value: U256,
+    pub input: Bytes,
+}
+
+type Handler = Rc<RefCell<Box<dyn FnMut(Call) -> (bool, Bytes)>>>;
+
+thread_local! {
+    static CONTRACTS: RefCell<HashMap<Address, Handler>> = RefCell::new(HashMap::new());
+}
+
+/// Makes `handler` answer every call to `address`, in place of any contract already there.
+/// It returns whether the call succeeded and its output, the revert data on failure.
+pub fn register(address: Address, handler: impl FnMut(Call) -> (bool, Bytes) + 'static) {
+    let handler: Handler = Rc::new(RefCell::new(Box::new(handler)));
+    CONTRACTS.with(|contracts| contracts.borrow_mut().insert(address, handler));
+}
+
+/// Removes the contract at `address`, leaving an account without code.
+pub fn unregister(address: Address) {
+    CONTRACTS.with(|contracts| contracts.borrow_mut().remove(&address));
+}
+
+pub fn has_code(account: Address) -> bool {
+    CONTRACTS.with(|contracts| contracts.borrow().contains_key(&account))
+}
+
+/// Runs `call` on the contract at `call.target`.
+///
+/// A contract cannot be re-entered: calling back into a contract that is still handling a call
+/// panics.
+pub fn dispatch(call: Call) -> (bool, Bytes) {
+    let handler = CONTRACTS.with(|contracts| contracts.borrow().get(&call.target).cloned());
+    let Some(handler) = handler else {
+        return (true, Bytes::new());
+    };
+
+    let mut handler = handler
+        .try_borrow_mut()
+        .unwrap_or_else(|_| panic!("re-entrant call into {}", call.target));
+    (*handler)(call)
+}
+
+/// A context that sends its external calls to the chain, and does everything else with `SDK`,
+/// e.g. `ChainContext<HostTestingContext>` for the contracts under a harness.
+///
+/// It gets [`CallContract`](crate::CallContract) from its `SharedAPI` implementation like any
+/// other context, so the contracts under test run the production call code. It derefs to `SDK`
+/// for the test setup, e.g. `with_input`.
+#[derive(Clone, Default)]
+pub struct ChainContext<SDK>(SDK);
+
+impl<SDK> ChainContext<SDK> {
+    pub fn new(sdk: SDK) -> Self {
+        Self(sdk)
+    }
+
+    pub fn into_inner(self) -> SDK {
+        self.0
+    }
+}
+
+impl<SDK> Deref for ChainContext<SDK> {
+    type Target = SDK;
+
+    fn deref(&self) -> &SDK {
+        &self.0
+    }
+}
+
+impl<SDK> DerefMut for ChainContext<SDK> {
+    fn deref_mut(&mut self) -> &mut SDK {
+        &mut self.0
+    }
+}
+
+// `dispatch` as the result of a call syscall
+fn call_syscall(
+    kind: CallKind,
+    caller: Address,
+    target: Address,
+    value: U256,
+    input: &[u8],
+) -> SyscallResult<Bytes> {
+    let (success, output) = dispatch(Call {
+        kind,
+        caller,
+        target,
+        value,
+        input: Bytes::copy_from_slice(input),
+    });
+    let status = if success {
+        ExitCode::Ok
+    } else {
+        ExitCode::Panic
+    };
+    SyscallResult::new(output, 0, 0, status)
+}
+
+impl<SDK: StorageAPI> StorageAPI for ChainContext<SDK> {
+    fn write_storage(&mut self, slot: U256, value: U256) -> SyscallResult<()> {
+        self.0.write_storage(slot, value)
+    }
+
+    fn storage(&self, slot: &U256) -> SyscallResult<U256> {
+        self.0.storage(slot)
+    }
+}
+
+impl<SDK: MetadataAPI> MetadataAPI for ChainContext<SDK> {
+    fn metadata_write(
+        &mut self,
+        address: &Address,
+        offset: u32,
+        metadata: Bytes,
+    ) -> SyscallResult<()> {
+        self.0.metadata_write(address, offset, metadata)
+    }
+
+    fn metadata_size(&self, address: &Address) -> SyscallResult<(u32, bool, bool, bool)> {
+        self.0.metadata_size(address)
+    }
+
+    fn metadata_create(&mut self, salt: &U256, metadata: Bytes) -> SyscallResult<()> {
+        self.0.metadata_create(salt, metadata)
+    }
+
+    fn metadata_copy(&self, address: &Address, offset: u32, length: u32) -> SyscallResult<Bytes> {
+        self.0.metadata_copy(address, offset, length)
+    }
+}
+
+// Everything but calls and code lookups goes to `SDK` unchanged
+impl<SDK: SharedAPI> SharedAPI for ChainContext<SDK> {
+    fn context(&self) -> impl ContextReader {
+        self.0.context()
+    }
+
+    fn keccak256(&self, data: &[u8]) -> B256 {
+        self.0.keccak256(data)
+    }
+
+    fn sha256(&self, data: &[u8]) -> B256 {
+        self.0.sha256(data)
+    }
+
+    fn read(&self, target: &mut [u8], offset: u32) {
+        self.0.read(target, offset)
+    }
+
+    fn input_size(&self) -> u32 {
+        self.0.input_size()
+    }
+
+    fn read_context(&self, target: &mut [u8], offset: u32) {
+        self.0.read_context(target, offset)
+    }
+
+    fn charge_fuel_manually(&self, fuel_consumed: u64, fuel_refunded: i64) -> u64 {
+        self.0.charge_fuel_manually(fuel_consumed, fuel_refunded)
+    }
+
+    fn fuel(&self) -> u64 {
+        self.0.fuel()
+    }
+
+    fn write(&mut self, output: &[u8]) {
+        self.0.write(output)
+    }
+
+    fn exit(&self, exit_code: ExitCode) -> ! {
+        self.0.exit(exit_code)
+    }
+
+    fn return_data(&self) -> Bytes {
+        self.0.return_data()
+    }
+
+    fn write_transient_storage(&mut self, slot: U256, value: U256) -> SyscallResult<()> {
+        self.0.write_transient_storage(slot, value)
+    }
+
+    fn transient_storage(&self, slot: &U256) -> SyscallResult<U256> {
+        self.0.transient_storage(slot)
+    }
+
+    fn emit_log(&mut self, topics: &[B256], data: &[u8]) -> SyscallResult<()> {
+        self.0.emit_log(topics, data)
+    }
+
+    fn self_balance(&self) -> SyscallResult<U256> {
+        self.0.self_balance()
+    }
+
+    fn balance(&self, address: &Address) -> SyscallResult<U256> {
+        self.0.balance(address)
+    }
+
+    fn code_size(&self, address: &Address) -> SyscallResult<u32> {
+        let size = u32::from(has_code(*address));
+        SyscallResult::new(size, 0, 0, ExitCode::Ok)
+    }
+
+    fn code_hash(&self, address: &Address) -> SyscallResult<B256> {
+        self.0.code_hash(address)
+    }
+
+    fn code_copy(
+        &self,
+        address: &Address,
+        code_offset: u64,
+        code_length: u64,
+    ) -> SyscallResult<Bytes> {
+        self.0.code_copy(address, code_offset, code_length)
+    }
+
+    fn write_preimage(&mut self, preimage: Bytes) -> SyscallResult<B256> {
+        self.0.write_preimage(preimage)
+    }
+
+    fn create(
+        &mut self,
+        salt: Option<U256>,
+        value: &U256,
+        init_code: &[u8],
+    ) -> SyscallResult<Bytes> {
+        self.0.create(salt, value, init_code)
+    }
+
+    fn call(
+        &mut self,
+        address: Address,
+        value: U256,
+        input: &[u8],
+        _fuel_limit: Option<u64>,
+    ) -> SyscallResult<Bytes> {
+        let caller = self.context().contract_address();
+        call_syscall(CallKind::Call, caller, address, value, input)
+    }
+
+    fn call_code(
+        &mut self,
+        address: Address,
+        value: U256,
+        input: &[u8],
+        fuel_limit: Option<u64>,
+    ) -> SyscallResult<Bytes> {
+        self.0.call_code(address, value, input, fuel_limit)
+    }
+
+    fn delegate_call(
+        &mut self,
+        address: Address,
+        input: &[u8],
+        _fuel_limit: Option<u64>,
+    ) -> SyscallResult<Bytes> {
+        let caller = self.context().contract_caller();
+        call_syscall(CallKind::DelegateCall, caller, address, U256::ZERO, input)
+    }
+
+    fn static_call(
+        &mut self,
+        address: Address,
+        input: &[u8],
+        _fuel_limit: Option<u64>,
+    ) -> SyscallResult<Bytes> {
+        let caller = self.context().contract_address();
+        call_syscall(CallKind::StaticCall, caller, address, U256::ZERO, input)
+    }
+
+    fn destroy_account(&mut self, address: Address) -> SyscallResult<()> {
+        self.0.destroy_account(address)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::CallContract;
+    use fluentbase_sdk::{address, ContractContextV1};
+    use fluentbase_testing::HostTestingContext;
+
+    const CALLER: Address = address!("1111111111111111111111111111111111111111");
+    const ECHO: Address = address!("cccccccccccccccccccccccccccccccccccccccc");
+
+    fn call(target: Address, input: &'static [u8]) -> Call {
+        Call {
+            kind: CallKind::Call,
+            caller: CALLER,
+            target,
+            value: U256::ZERO,
+            input: Bytes::from_static(input),
+        }
+    }
+
+    #[test]
+    fn test_calls_reach_registered_contracts() {
+        register(ECHO, |call| (call.caller == CALLER, call.input));
+
+        assert!(has_code(ECHO));
+        assert_eq!(
+            dispatch(call(ECHO, b"ping")),
+            (true, Bytes::from_static(b"ping"))
+        );
+
+        // Verify: Without a contract, the call succeeds and returns nothing
+        unregister(ECHO);
+        assert!(!has_code(ECHO));
+        assert_eq!(dispatch(call(ECHO, b"ping")), (true, Bytes::new()));
+    }
+
+    #[test]
+    fn test_chain_context_calls_the_chain() {
+        register(ECHO, |call| (call.caller == CALLER, call.input));
+        let mut sdk = ChainContext::new(HostTestingContext::default().with_contract_context(
+            ContractContextV1 {
+                address: CALLER,
+                ..Default::default()
+            },
+        ));
+
+        // Verify: The production `CallContract` code reaches the chain through the wrapper
+        assert!(sdk.has_code(ECHO));
+        assert_eq!(
+            sdk.call_contract(ECHO, b"ping", None),
+            (true, Bytes::from_static(b"ping"))
+        );
+        assert!(!sdk.has_code(CALLER));
+    }
+
+    #[test]
+    #[should_panic(expected = "re-entrant call")]
+    fn test_reentrant_call_panics() {
+        register(ECHO, |_call| dispatch(call(ECHO, b"again")));
+        dispatch(call(ECHO, b"ping"));
+    }
+}
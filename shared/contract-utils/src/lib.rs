@@ -0,0 +1,21 @@
+//! Building blocks shared by the Rust contract examples.
+//!
+//! * [`calls`] - external calls behind the [`CallContract`] trait, so the code making them can be
+//!   unit tested against a mock instead of a full `SharedAPI` context.
+//! * [`ownable`] - two-step ownership transfer, compatible with OpenZeppelin `Ownable2Step`.
+//! * `testing` (feature `testing`) - a host test harness that drives any contract through its
+//!   router, and `chain`, the in-memory chain that lets contracts under test call each other
+//!   through a `ChainContext`.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+pub mod calls;
+#[cfg(feature = "testing")]
+pub mod chain;
+pub mod ownable;
+#[cfg(feature = "testing")]
+pub mod testing;
+
+pub use calls::CallContract;
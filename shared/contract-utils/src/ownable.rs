@@ -0,0 +1,264 @@
+//! Two-step ownership transfer, compatible with OpenZeppelin `Ownable2Step`.
+//!
+//! The component has no fields of its own. By default the owner lives in the ERC-7201
+//! namespace `openzeppelin.storage.Ownable` and the pending owner in
+//! `openzeppelin.storage.Ownable2Step`, so it can be dropped into any `#[derive(Contract)]`
+//! struct without shifting its slots:
+//!
+//! ```ignore
+//! fn ownable_accessor(&self) -> Ownable2Step {
+//!     Ownable2Step::default()
+//! }
+//!
+//! fn transfer_ownership(&mut self, new_owner: Address) {
+//!     self.ownable_accessor().transfer_ownership(&mut self.sdk, new_owner);
+//! }
+//! ```
+//!
+//! Contracts replacing a Solidity implementation with another layout pass its slots to
+//! [`Ownable2Step::new`] instead, as the ERC20 does for each of its layout modes.
+
+use alloc::vec::Vec;
+use alloy_sol_types::{sol, SolError, SolEvent};
+use fluentbase_sdk::{
+    storage::{StorageAddress, StorageDescriptor},
+    Address, ContextReader, ExitCode, SharedAPI, B256, U256,
+};
+use hex_literal::hex;
+
+/// `keccak256(abi.encode(uint256(keccak256("openzeppelin.storage.Ownable")) - 1)) & ~bytes32(uint256(0xff))`
+pub const OZ_OWNABLE_NAMESPACE_SLOT: U256 = U256::from_be_bytes(hex!(
+    "9016d09d72d40fdae2fd8ceac6b6234c7706214fd39c1cd1e609a0528c199300"
+));
+
+/// `keccak256(abi.encode(uint256(keccak256("openzeppelin.storage.Ownable2Step")) - 1)) & ~bytes32(uint256(0xff))`
+pub const OZ_OWNABLE2STEP_NAMESPACE_SLOT: U256 = U256::from_be_bytes(hex!(
+    "237e158222e3e6968b72b9db0d8043aacf074ad9f650f0d1606b4d82ee432c00"
+));
+
+sol! {
+    event OwnershipTransferStarted(address indexed previousOwner, address indexed newOwner);
+    event OwnershipTransferred(address indexed previousOwner, address indexed newOwner);
+    error OwnableUnauthorizedAccount(address account);
+    error OwnableInvalidOwner(address owner);
+}
+
+fn emit_event<SDK: SharedAPI, T: SolEvent>(sdk: &mut SDK, event: T) {
+    let data = event.encode_data();
+    let topics: Vec<B256> = event
+        .encode_topics()
+        .iter()
+        .map(|v| B256::from(v.0))
+        .collect();
+    sdk.emit_log(&topics, &data);
+}
+
+// Revert with ABI-encoded custom error data, so callers can decode the reason
+fn revert<SDK: SharedAPI, T: SolError>(sdk: &mut SDK, error: T) -> ! {
+    sdk.write(&error.abi_encode());
+    sdk.exit(ExitCode::Panic)
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Ownable2Step {
+    owner_slot: U256,
+    pending_owner_slot: U256,
+}
+
+impl Default for Ownable2Step {
+    /// Keeps the owner in OpenZeppelin's ERC-7201 namespaces.
+    fn default() -> Self {
+        Self::new(OZ_OWNABLE_NAMESPACE_SLOT, OZ_OWNABLE2STEP_NAMESPACE_SLOT)
+    }
+}
+
+impl Ownable2Step {
+    /// Keeps the owner and pending owner in the given slots.
+    pub const fn new(owner_slot: U256, pending_owner_slot: U256) -> Self {
+        Self {
+            owner_slot,
+            pending_owner_slot,
+        }
+    }
+
+    fn owner_accessor(&self) -> StorageAddress {
+        StorageAddress::new(self.owner_slot, 0)
+    }
+
+    fn pending_owner_accessor(&self) -> StorageAddress {
+        StorageAddress::new(self.pending_owner_slot, 0)
+    }
+
+    pub fn owner<SDK: SharedAPI>(&self, sdk: &SDK) -> Address {
+        self.owner_accessor().get(sdk)
+    }
+
+    pub fn pending_owner<SDK: SharedAPI>(&self, sdk: &SDK) -> Address {
+        self.pending_owner_accessor().get(sdk)
+    }
+
+    /// Reverts with `OwnableUnauthorizedAccount` unless the current caller is the owner.
+    pub fn only_owner<SDK: SharedAPI>(&self, sdk: &mut SDK) {
+        let caller = sdk.context().contract_caller();
+        if caller != self.owner(sdk) {
+            revert(sdk, OwnableUnauthorizedAccount { account: caller });
+        }
+    }
+
+    /// Sets the first owner, for constructors and initializers.
+    pub fn initialize<SDK: SharedAPI>(&self, sdk: &mut SDK, owner: Address) {
+        if owner == Address::ZERO {
+            revert(sdk, OwnableInvalidOwner { owner });
+        }
+        self.set_owner(sdk, owner);
+    }
+
+    /// Starts a transfer; `new_owner` must call `accept_ownership` to complete it.
+    /// Passing the zero address cancels a pending transfer.
+    pub fn transfer_ownership<SDK: SharedAPI>(&self, sdk: &mut SDK, new_owner: Address) {
+        self.only_owner(sdk);
+
+        self.pending_owner_accessor().set(sdk, new_owner);
+        let previous_owner = self.owner(sdk);
+        emit_event(
+            sdk,
+            OwnershipTransferStarted {
+                previousOwner: previous_owner,
+                newOwner: new_owner,
+            },
+        );
+    }
+
+    pub fn accept_ownership<SDK: SharedAPI>(&self, sdk: &mut SDK) {
+        let caller = sdk.context().contract_caller();
+        if caller != self.pending_owner(sdk) {
+            revert(sdk, OwnableUnauthorizedAccount { account: caller });
+        }
+        self.set_owner(sdk, caller);
+    }
+
+    /// Leaves the contract without an owner, disabling every owner-only entrypoint.
+    pub fn renounce_ownership<SDK: SharedAPI>(&self, sdk: &mut SDK) {
+        self.only_owner(sdk);
+        self.set_owner(sdk, Address::ZERO);
+    }
+
+    // Also drops any pending transfer, like OpenZeppelin's `_transferOwnership`
+    fn set_owner<SDK: SharedAPI>(&self, sdk: &mut SDK, new_owner: Address) {
+        self.pending_owner_accessor().set(sdk, Address::ZERO);
+
+        let previous_owner = self.owner(sdk);
+        self.owner_accessor().set(sdk, new_owner);
+        emit_event(
+            sdk,
+            OwnershipTransferred {
+                previousOwner: previous_owner,
+                newOwner: new_owner,
+            },
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fluentbase_sdk::{address, ContractContextV1};
+    use fluentbase_testing::HostTestingContext;
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+
+    const OWNABLE: Ownable2Step =
+        Ownable2Step::new(OZ_OWNABLE_NAMESPACE_SLOT, OZ_OWNABLE2STEP_NAMESPACE_SLOT);
+    const OWNER: Address = address!("1111111111111111111111111111111111111111");
+    const NEW_OWNER: Address = address!("2222222222222222222222222222222222222222");
+    const STRANGER: Address = address!("6666666666666666666666666666666666666666");
+
+    fn as_caller(sdk: HostTestingContext, caller: Address) -> HostTestingContext {
+        sdk.with_contract_context(ContractContextV1 {
+            address: address!("aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa"),
+            caller,
+            ..Default::default()
+        })
+    }
+
+    fn assert_unauthorized(
+        sdk: &mut HostTestingContext,
+        account: Address,
+        f: impl FnOnce(&mut HostTestingContext),
+    ) {
+        let result = catch_unwind(AssertUnwindSafe(|| f(sdk)));
+        assert!(result.is_err(), "call should revert");
+
+        let error = OwnableUnauthorizedAccount::abi_decode(&sdk.take_output()).unwrap();
+        assert_eq!(error.account, account);
+    }
+
+    fn owned_by(owner: Address) -> HostTestingContext {
+        let mut sdk = as_caller(HostTestingContext::default(), owner);
+        OWNABLE.initialize(&mut sdk, owner);
+        sdk
+    }
+
+    #[test]
+    fn test_transfer_requires_acceptance() {
+        let mut sdk = owned_by(OWNER);
+
+        OWNABLE.transfer_ownership(&mut sdk, NEW_OWNER);
+
+        // Verify: Ownership does not move until accepted
+        assert_eq!(OWNABLE.owner(&sdk), OWNER);
+        assert_eq!(OWNABLE.pending_owner(&sdk), NEW_OWNER);
+
+        let mut sdk = as_caller(sdk, NEW_OWNER);
+        OWNABLE.accept_ownership(&mut sdk);
+
+        assert_eq!(OWNABLE.owner(&sdk), NEW_OWNER);
+        assert_eq!(OWNABLE.pending_owner(&sdk), Address::ZERO);
+    }
+
+    #[test]
+    fn test_accept_rejects_other_callers() {
+        let mut sdk = owned_by(OWNER);
+        OWNABLE.transfer_ownership(&mut sdk, NEW_OWNER);
+
+        let mut sdk = as_caller(sdk, STRANGER);
+        assert_unauthorized(&mut sdk, STRANGER, |sdk| OWNABLE.accept_ownership(sdk));
+        assert_eq!(OWNABLE.owner(&sdk), OWNER);
+    }
+
+    #[test]
+    fn test_transfer_rejects_non_owner() {
+        let sdk = owned_by(OWNER);
+
+        let mut sdk = as_caller(sdk, STRANGER);
+        assert_unauthorized(&mut sdk, STRANGER, |sdk| {
+            OWNABLE.transfer_ownership(sdk, STRANGER)
+        });
+        assert_eq!(OWNABLE.pending_owner(&sdk), Address::ZERO);
+    }
+
+    #[test]
+    fn test_renounce_clears_owner_and_pending_owner() {
+        let mut sdk = owned_by(OWNER);
+        OWNABLE.transfer_ownership(&mut sdk, NEW_OWNER);
+
+        OWNABLE.renounce_ownership(&mut sdk);
+
+        assert_eq!(OWNABLE.owner(&sdk), Address::ZERO);
+        assert_eq!(OWNABLE.pending_owner(&sdk), Address::ZERO);
+    }
+
+    #[test]
+    fn test_custom_slots() {
+        let ownable = Ownable2Step::new(U256::from(5), U256::from(6));
+        let mut sdk = as_caller(HostTestingContext::default(), OWNER);
+
+        ownable.initialize(&mut sdk, OWNER);
+        ownable.transfer_ownership(&mut sdk, NEW_OWNER);
+
+        // Verify: Both addresses land in the given slots, not the namespaces
+        let word = |address: Address| U256::from_be_slice(address.as_slice());
+        assert_eq!(sdk.storage(&U256::from(5)).data, word(OWNER));
+        assert_eq!(sdk.storage(&U256::from(6)).data, word(NEW_OWNER));
+        assert_eq!(OWNABLE.owner(&sdk), Address::ZERO);
+    }
+}
@@ -0,0 +1,220 @@
+//! Host test harness for any contract with a router.
+//!
+//! [`Harness`] owns a contract over a [`ChainContext`]`<HostTestingContext>`, calls it through its
+//! router like a transaction would, and keeps every emitted log. Each example wraps it with one typed method
+//! per router entrypoint, e.g. the ERC20's `TokenHarness`:
+//!
+//! ```ignore
+//! let mut token = TokenHarness::deploy(ALICE, "Token", "TKN", U256::from(1_000));
+//! token.transfer(ALICE, BOB, U256::from(10)).unwrap();
+//! assert_eq!(token.events::<Transfer>().last().unwrap().to, BOB);
+//! ```
+//!
+//! [`on_chain`] puts a harness on the in-memory [`chain`], so that contracts under test can
+//! call it.
+
+pub use crate::chain::ChainContext;
+
+use crate::chain::{self, Call, CallKind};
+use alloy_primitives::{Log, LogData};
+use alloy_sol_types::{SolError, SolEvent};
+use fluentbase_sdk::{Address, BlockContextV1, Bytes, ContractContextV1, U256};
+use fluentbase_testing::HostTestingContext;
+use std::{
+    cell::RefCell,
+    ops::DerefMut,
+    panic::{catch_unwind, AssertUnwindSafe},
+    rc::Rc,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+/// What the harness needs from a contract: its context, constructor and router.
+///
+/// `#[derive(Contract)]`, `#[constructor]` and `#[router]` generate all of it, so an
+/// implementation only forwards to them. The contract's context is a [`ChainContext`] around the
+/// host, so that its external calls reach the chain:
+///
+/// ```ignore
+/// impl HostContract for ERC20<ChainContext<HostTestingContext>> {
+///     fn with_host(sdk: HostTestingContext) -> Self {
+///         Self::new(ChainContext::new(sdk))
+///     }
+///     fn host(&self) -> &HostTestingContext {
+///         &self.sdk
+///     }
+///     // ...
+/// }
+/// ```
+pub trait HostContract {
+    fn with_host(sdk: HostTestingContext) -> Self;
+    fn host(&self) -> &HostTestingContext;
+    fn host_mut(&mut self) -> &mut HostTestingContext;
+    /// Runs the constructor on the current input.
+    fn run_deploy(&mut self);
+    /// Runs the router on the current input.
+    fn run_main(&mut self);
+}
+
+/// A reverted call: the panic message for `panic!` reverts, the output for custom errors.
+#[derive(Debug)]
+pub struct Revert {
+    pub message: String,
+    pub data: Bytes,
+}
+
+impl Revert {
+    /// Decodes the revert data as the custom error `E`, panicking if it is something else.
+    pub fn decode<E: SolError>(&self) -> E {
+        E::abi_decode(&self.data)
+            .unwrap_or_else(|_| panic!("expected {}, got revert {:?}", E::SIGNATURE, self))
+    }
+}
+
+pub type CallResult<T> = Result<T, Revert>;
+
+pub struct Harness<C> {
+    pub contract: C,
+    /// The contract's own address, also where [`on_chain`] puts it.
+    pub address: Address,
+    /// Block timestamp seen by the following calls.
+    pub timestamp: u64,
+    logs: Vec<Log>,
+}
+
+impl<C: HostContract> Harness<C> {
+    /// Harness over empty storage, with nothing deployed yet.
+    pub fn new(address: Address) -> Self {
+        Self {
+            contract: C::with_host(HostTestingContext::default()),
+            address,
+            timestamp: 0,
+            logs: Vec::new(),
+        }
+    }
+
+    /// Deploys through the constructor with the encoded arguments in `input`, as `deployer`.
+    pub fn deploy(address: Address, deployer: Address, input: impl AsRef<[u8]>) -> Self {
+        let mut harness = Self::new(address);
+        harness.prepare(deployer, input.as_ref().to_vec());
+        harness.contract.run_deploy();
+        harness.capture_logs();
+        harness
+    }
+
+    /// Sets the block timestamp seen by the following calls.
+    pub fn warp(&mut self, timestamp: u64) {
+        self.timestamp = timestamp;
+    }
+
+    pub fn sdk(&self) -> &HostTestingContext {
+        self.contract.host()
+    }
+
+    pub fn sdk_mut(&mut self) -> &mut HostTestingContext {
+        self.contract.host_mut()
+    }
+
+    /// Runs `input` through the router as `caller`, returning the raw output or the revert.
+    pub fn call(&mut self, caller: Address, input: impl AsRef<[u8]>) -> CallResult<Bytes> {
+        self.prepare(caller, input.as_ref().to_vec());
+
+        let result = catch_unwind(AssertUnwindSafe(|| self.contract.run_main()));
+        let output = Bytes::from(self.contract.host_mut().take_output());
+        self.capture_logs();
+
+        match result {
+            Ok(()) => Ok(output),
+            Err(payload) => {
+                let message = payload
+                    .downcast_ref::<String>()
+                    .cloned()
+                    .or_else(|| payload.downcast_ref::<&str>().map(|s| s.to_string()))
+                    .unwrap_or_default();
+                Err(Revert {
+                    message,
+                    data: output,
+                })
+            }
+        }
+    }
+
+    /// Every captured log that decodes as `E`, oldest first.
+    pub fn events<E: SolEvent>(&self) -> Vec<E> {
+        self.logs
+            .iter()
+            .filter_map(|log| E::decode_log(log).ok())
+            .map(|log| log.data)
+            .collect()
+    }
+
+    pub fn clear_logs(&mut self) {
+        self.logs.clear();
+    }
+
+    /// Returns the captured logs, oldest first, and clears them.
+    pub fn take_logs(&mut self) -> Vec<Log> {
+        core::mem::take(&mut self.logs)
+    }
+
+    /// Reads a raw storage slot, bypassing the router.
+    pub fn storage(&self, slot: U256) -> U256 {
+        self.contract.host().storage(&slot).data
+    }
+
+    /// Answers a call from another contract on the chain.
+    pub fn serve(&mut self, call: Call) -> (bool, Bytes) {
+        // The harness only has its own storage, not the caller's
+        assert_ne!(
+            call.kind,
+            CallKind::DelegateCall,
+            "delegate call into {}",
+            self.address
+        );
+        match self.call(call.caller, &call.input) {
+            Ok(output) => (true, output),
+            Err(revert) => (false, revert.data),
+        }
+    }
+
+    fn prepare(&mut self, caller: Address, input: Vec<u8>) {
+        let sdk = self
+            .contract
+            .host()
+            .clone()
+            .with_input(input)
+            .with_contract_context(ContractContextV1 {
+                address: self.address,
+                caller,
+                ..Default::default()
+            })
+            .with_block_context(BlockContextV1 {
+                timestamp: self.timestamp,
+                ..Default::default()
+            });
+        *self.contract.host_mut() = sdk;
+    }
+
+    fn capture_logs(&mut self) {
+        for (data, topics) in self.contract.host_mut().take_logs() {
+            self.logs.push(Log {
+                address: self.address,
+                data: LogData::new_unchecked(topics, data),
+            });
+        }
+    }
+}
+
+/// Puts `harness`, or a typed wrapper around one, on the chain at its address, so contracts
+/// under test can call it. Keep using it through the returned handle.
+pub fn on_chain<H, C>(harness: H) -> Rc<RefCell<H>>
+where
+    H: DerefMut<Target = Harness<C>> + 'static,
+    C: HostContract,
+{
+    let address = harness.address;
+    let harness = Rc::new(RefCell::new(harness));
+    let handle = harness.clone();
+    chain::register(address, move |call| handle.borrow_mut().serve(call));
+    harness
+}
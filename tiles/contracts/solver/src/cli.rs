@@ -0,0 +1,155 @@
+//! The `solve` CLI: solves the game of a seed and prints it as a JSON [`Fixture`].
+//!
+//! ```text
+//! cargo run --release -- 1 5 > ../test/fixtures/seed-1.json
+//! cargo run --release -- 1 --player 0x1111111111111111111111111111111111111111 --chain-id 20994
+//! ```
+
+use crate::{Fixture, Player, Solver, DEFAULT_DEPTH};
+use fluentbase_sdk::Address;
+
+pub const USAGE: &str =
+    "usage: solve <seed> [depth] [max moves] [--player <address> --chain-id <chain id>]";
+
+/// Takes the seed, and optionally the search depth and the most moves to play, as decimal
+/// strings. The most moves defaults to `validator::MAX_MOVES`, the most `getScore` accepts.
+///
+/// `--player` and `--chain-id` solve the board that player gets for the seed on that chain,
+/// see [`Solver::solve_for`]. They go together, and can come anywhere in `args`.
+pub fn run(args: &[String]) -> Result<String, String> {
+    let (args, player) = split_player(args)?;
+    let (seed, depth, max_moves) = match args.as_slice() {
+        [seed] => (seed, None, None),
+        [seed, depth] => (seed, Some(depth), None),
+        [seed, depth, max_moves] => (seed, Some(depth), Some(max_moves)),
+        _ => return Err(USAGE.to_string()),
+    };
+
+    let seed: u64 = seed
+        .parse()
+        .map_err(|error| format!("invalid seed {seed:?}: {error}"))?;
+    let depth: u32 = match depth {
+        Some(depth) => depth
+            .parse()
+            .map_err(|error| format!("invalid depth {depth:?}: {error}"))?,
+        None => DEFAULT_DEPTH,
+    };
+    let max_moves: u64 = match max_moves {
+        Some(max_moves) => max_moves
+            .parse()
+            .map_err(|error| format!("invalid max moves {max_moves:?}: {error}"))?,
+        None => validator::MAX_MOVES,
+    };
+    if max_moves > validator::MAX_MOVES {
+        return Err(format!(
+            "max moves {max_moves} is more than the validator accepts ({})",
+            validator::MAX_MOVES
+        ));
+    }
+
+    let solver = Solver::new(depth);
+    let solution = match player {
+        Some(player) => solver.solve_for(player, seed, max_moves),
+        None => solver.solve(seed, max_moves),
+    };
+    let fixture: Fixture = solution.fixture();
+    serde_json::to_string_pretty(&fixture).map_err(|error| error.to_string())
+}
+
+// Takes the `--player` and `--chain-id` options out of `args`, leaving the positional ones
+fn split_player(args: &[String]) -> Result<(Vec<&String>, Option<Player>), String> {
+    let mut positional = Vec::new();
+    let mut address = None;
+    let mut chain_id = None;
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--player" => {
+                let value = args.next().ok_or(USAGE)?;
+                let parsed: Address = value
+                    .parse()
+                    .map_err(|error| format!("invalid player {value:?}: {error}"))?;
+                address = Some(parsed);
+            }
+            "--chain-id" => {
+                let value = args.next().ok_or(USAGE)?;
+                let parsed: u64 = value
+                    .parse()
+                    .map_err(|error| format!("invalid chain id {value:?}: {error}"))?;
+                chain_id = Some(parsed);
+            }
+            _ => positional.push(arg),
+        }
+    }
+
+    let player = match (address, chain_id) {
+        (Some(address), Some(chain_id)) => Some(Player { address, chain_id }),
+        (None, None) => None,
+        _ => return Err("--player and --chain-id must be given together".to_string()),
+    };
+    Ok((positional, player))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Value;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn test_fixture_json() {
+        let json = run(&args(&["123456789", "2", "50"])).unwrap();
+        let fixture: Value = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(fixture["seed"], 123456789);
+        assert_eq!(fixture["movesLen"], 50);
+        assert!(fixture["moves"].as_str().unwrap().starts_with("0x"));
+        assert!(fixture["score"].as_u64().unwrap() > 0);
+    }
+
+    #[test]
+    fn test_player_fixture_json() {
+        let json = run(&args(&[
+            "123456789",
+            "--player",
+            "0x1111111111111111111111111111111111111111",
+            "2",
+            "--chain-id",
+            "20994",
+            "50",
+        ]))
+        .unwrap();
+        let fixture: Value = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(fixture["seed"], 123456789);
+        assert_eq!(
+            fixture["player"],
+            "0x1111111111111111111111111111111111111111"
+        );
+        assert_eq!(fixture["chainId"], 20994);
+        assert_eq!(fixture["movesLen"], 50);
+    }
+
+    #[test]
+    fn test_bad_arguments() {
+        assert_eq!(run(&args(&[])), Err(USAGE.to_string()));
+        assert!(run(&args(&["-1"])).unwrap_err().starts_with("invalid seed"));
+        assert!(run(&args(&["1", "deep"]))
+            .unwrap_err()
+            .starts_with("invalid depth"));
+        assert!(run(&args(&["1", "2", "1000000"]))
+            .unwrap_err()
+            .starts_with("max moves"));
+        assert!(run(&args(&["1", "--player", "0x12"]))
+            .unwrap_err()
+            .starts_with("invalid player"));
+        assert!(run(&args(&["1", "--chain-id", "1"]))
+            .unwrap_err()
+            .starts_with("--player and --chain-id"));
+        assert_eq!(run(&args(&["1", "--player"])), Err(USAGE.to_string()));
+    }
+}
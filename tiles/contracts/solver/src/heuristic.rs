@@ -0,0 +1,106 @@
+//! Board evaluation for the search, after nneonneo's 2048-ai: each row and column scores for
+//! its empty cells and merges, and loses for tiles out of order and for large tiles. The score
+//! of every 16-bit row is computed once, like the validator's row table.
+
+use std::sync::OnceLock;
+
+const LOST_PENALTY: f32 = 200_000.0;
+const MONOTONICITY_POWER: f32 = 4.0;
+const MONOTONICITY_WEIGHT: f32 = 47.0;
+const SUM_POWER: f32 = 3.5;
+const SUM_WEIGHT: f32 = 11.0;
+const MERGES_WEIGHT: f32 = 700.0;
+const EMPTY_WEIGHT: f32 = 270.0;
+
+fn row_scores() -> &'static [f32] {
+    static ROW_SCORES: OnceLock<Vec<f32>> = OnceLock::new();
+    ROW_SCORES.get_or_init(|| (0..=u16::MAX).map(row_score).collect())
+}
+
+fn row_score(row: u16) -> f32 {
+    let line: [u16; 4] = std::array::from_fn(|i| (row >> (4 * i)) & 0xf);
+
+    let mut sum = 0.0;
+    let mut empty = 0;
+    let mut merges = 0;
+    let mut previous = 0;
+    let mut run = 0;
+    for &rank in &line {
+        if rank == 0 {
+            empty += 1;
+            continue;
+        }
+        sum += (rank as f32).powf(SUM_POWER);
+        if rank == previous {
+            run += 1;
+        } else if run > 0 {
+            merges += 1 + run;
+            run = 0;
+        }
+        previous = rank;
+    }
+    if run > 0 {
+        merges += 1 + run;
+    }
+
+    let mut left = 0.0;
+    let mut right = 0.0;
+    for pair in line.windows(2) {
+        let (a, b) = (
+            (pair[0] as f32).powf(MONOTONICITY_POWER),
+            (pair[1] as f32).powf(MONOTONICITY_POWER),
+        );
+        if a > b {
+            left += a - b;
+        } else {
+            right += b - a;
+        }
+    }
+
+    LOST_PENALTY + EMPTY_WEIGHT * empty as f32 + MERGES_WEIGHT * merges as f32
+        - MONOTONICITY_WEIGHT * f32::min(left, right)
+        - SUM_WEIGHT * sum
+}
+
+/// How promising `board` is, higher is better. The board is packed like `GameResult::board`.
+pub fn evaluate(board: u64) -> f32 {
+    let scores = row_scores();
+    let mut columns = 0u64;
+    for y in 0..4 {
+        for x in 0..4 {
+            let nibble = (board >> (4 * (y * 4 + x))) & 0xf;
+            columns |= nibble << (4 * (x * 4 + y));
+        }
+    }
+
+    (0..4)
+        .map(|i| {
+            scores[(board >> (16 * i)) as u16 as usize]
+                + scores[(columns >> (16 * i)) as u16 as usize]
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(tiles: [u16; 4]) -> u16 {
+        tiles[0] | tiles[1] << 4 | tiles[2] << 8 | tiles[3] << 12
+    }
+
+    #[test]
+    fn test_prefers_ordered_rows() {
+        assert!(row_score(row([4, 3, 2, 1])) > row_score(row([3, 1, 4, 2])));
+        assert!(row_score(row([1, 1, 0, 0])) > row_score(row([1, 2, 0, 0])));
+        assert!(row_score(row([1, 0, 0, 0])) > row_score(row([1, 2, 0, 0])));
+    }
+
+    #[test]
+    fn test_rows_and_columns_count_alike() {
+        // The same tiles along the first row and down the first column
+        let along = 0x4321;
+        let down = 0x0004_0003_0002_0001;
+        assert_eq!(evaluate(along), evaluate(down));
+    }
+}
@@ -0,0 +1,250 @@
+//! A 2048 solver for the Tiles validator, to produce high-scoring games for tests and demos.
+//!
+//! The search is expectimax with perfect information. A chance node would average over every
+//! tile that can spawn, but the seed already decides the spawn, so each chance node has the one
+//! outcome the validator will draw. [`Position`] clones the validator's own generator along
+//! with the board, so the search plays exactly the game `getScore` replays, and the search
+//! reduces to a depth-limited max over moves scored with [`heuristic::evaluate`].
+
+pub mod cli;
+pub mod heuristic;
+
+use fluentbase_sdk::Address;
+use serde::Serialize;
+use validator::{
+    bitboard::{self, Position},
+    direction::{encode_moves, Move},
+    seed::player_seed,
+    GameResult,
+};
+
+pub const DEFAULT_DEPTH: u32 = 5;
+
+pub struct Solver {
+    depth: u32,
+}
+
+/// The player a game is solved for. `getPlayerScore` plays the board of
+/// [`player_seed`]`(address, seed, chain_id)` rather than the board of `seed`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Player {
+    pub address: Address,
+    pub chain_id: u64,
+}
+
+impl Player {
+    /// The seed of the board this player plays when submitting `seed`.
+    pub fn board_seed(&self, seed: u64) -> u64 {
+        player_seed(self.address, seed, self.chain_id)
+    }
+}
+
+impl Solver {
+    /// Looks `depth` moves ahead before each move. Each extra move costs about 4x the time.
+    pub fn new(depth: u32) -> Self {
+        Solver {
+            depth: depth.max(1),
+        }
+    }
+
+    /// The move with the best outlook from `position`, or `None` if the game is over.
+    pub fn best_move(&self, position: &Position) -> Option<Move> {
+        let mut best = None;
+        let mut best_value = f32::NEG_INFINITY;
+        for direction in Move::ALL {
+            let mut next = position.clone();
+            if !next.play(direction) {
+                continue;
+            }
+            let value = search(&next, self.depth - 1);
+            if best.is_none() || value > best_value {
+                best = Some(direction);
+                best_value = value;
+            }
+        }
+        best
+    }
+
+    /// Plays the game of `seed` until it is over, a 32768 tile appears or `max_moves` moves
+    /// were played.
+    pub fn solve(&self, seed: u64, max_moves: u64) -> Solution {
+        let (moves, result) = self.play(seed, max_moves);
+        Solution {
+            seed,
+            player: None,
+            moves,
+            result,
+        }
+    }
+
+    /// Like [`Solver::solve`], but plays the board `player` gets for `seed`, which is what
+    /// `TilesGame.playGame` pays them for when they submit `seed`.
+    pub fn solve_for(&self, player: Player, seed: u64, max_moves: u64) -> Solution {
+        let (moves, result) = self.play(player.board_seed(seed), max_moves);
+        Solution {
+            seed,
+            player: Some(player),
+            moves,
+            result,
+        }
+    }
+
+    fn play(&self, board_seed: u64, max_moves: u64) -> (Vec<Move>, GameResult) {
+        let mut position = Position::new(board_seed);
+        let mut moves = Vec::new();
+        while (moves.len() as u64) < max_moves && !position.has_max_tile() {
+            let Some(direction) = self.best_move(&position) else {
+                break;
+            };
+            position.play(direction);
+            moves.push(direction);
+        }
+
+        // Scored by the validator's own replay rather than the search's copy of the game
+        let result = bitboard::play_or_fallback(board_seed, moves.clone());
+        (moves, result)
+    }
+}
+
+impl Default for Solver {
+    fn default() -> Self {
+        Solver::new(DEFAULT_DEPTH)
+    }
+}
+
+// The best heuristic value `depth` moves ahead of `position`. A position without moves is
+// lost, which is worse than any other
+fn search(position: &Position, depth: u32) -> f32 {
+    if depth == 0 {
+        return heuristic::evaluate(position.board());
+    }
+    Move::ALL
+        .iter()
+        .filter_map(|&direction| {
+            let mut next = position.clone();
+            next.play(direction).then(|| search(&next, depth - 1))
+        })
+        .fold(f32::NEG_INFINITY, f32::max)
+}
+
+/// A solved game: the moves, and what the validator makes of them.
+#[derive(Clone, Debug)]
+pub struct Solution {
+    /// The seed to submit.
+    pub seed: u64,
+    /// Who the board was bound to, if anyone.
+    pub player: Option<Player>,
+    pub moves: Vec<Move>,
+    pub result: GameResult,
+}
+
+impl Solution {
+    /// The `moves` argument of `getScore`.
+    pub fn moves_bytes(&self) -> Vec<u8> {
+        encode_moves(&self.moves)
+    }
+
+    /// The `movesLen` argument of `getScore`.
+    pub fn moves_len(&self) -> u64 {
+        self.moves.len() as u64
+    }
+
+    pub fn fixture(&self) -> Fixture {
+        Fixture {
+            seed: self.seed,
+            player: self.player.map(|player| player.address.to_string()),
+            chain_id: self.player.map(|player| player.chain_id),
+            moves: format!("0x{}", hex::encode(self.moves_bytes())),
+            moves_len: self.moves_len(),
+            score: self.result.score,
+            max_tile: self.result.max_tile,
+        }
+    }
+}
+
+/// A game as the Foundry tests read it: the `getScore` arguments and the expected result.
+/// A game solved for a player also names the player and chain, and its moves and result are
+/// those of `getPlayerScore`.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Fixture {
+    pub seed: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub player: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub chain_id: Option<u64>,
+    pub moves: String,
+    pub moves_len: u64,
+    pub score: u32,
+    pub max_tile: u32,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use validator::direction::decode_moves;
+
+    #[test]
+    fn test_solution_replays_on_the_validator() {
+        let solution = Solver::new(2).solve(123456789, 300);
+
+        let bytes = solution.moves_bytes();
+        let moves = decode_moves(&bytes, solution.moves_len()).unwrap();
+        assert_eq!(moves, solution.moves);
+
+        // Every move changes the board, and the search plays the validator's game
+        let mut position = Position::new(123456789);
+        assert!(moves.iter().all(|&direction| position.play(direction)));
+        assert_eq!(solution.result.score, position.score());
+        assert_eq!(solution.result.moves as u64, solution.moves_len());
+        assert_eq!(solution.result.ignored_moves, 0);
+    }
+
+    #[test]
+    fn test_stops_at_max_moves() {
+        let solution = Solver::new(1).solve(42, 10);
+        assert_eq!(solution.moves_len(), 10);
+        assert!(!solution.result.game_over);
+    }
+
+    #[test]
+    fn test_outscores_a_fixed_pattern() {
+        let solution = Solver::default().solve(7, 2_000);
+        let cycled = bitboard::play_or_fallback(7, Move::ALL.repeat(500));
+        assert!(solution.result.score > cycled.score);
+        assert!(solution.result.max_tile >= 1024);
+    }
+
+    #[test]
+    fn test_solves_the_board_bound_to_the_player() {
+        let player = Player {
+            address: Address::repeat_byte(0x11),
+            chain_id: 20994,
+        };
+        let solution = Solver::new(2).solve_for(player, 123456789, 300);
+
+        assert_eq!(solution.seed, 123456789);
+        let replayed = bitboard::play_or_fallback(
+            player_seed(player.address, 123456789, 20994),
+            solution.moves.clone(),
+        );
+        assert_eq!(solution.result, replayed);
+        assert_eq!(solution.result.ignored_moves, 0);
+
+        // The same moves are no good on the unbound board
+        let unbound = bitboard::play_or_fallback(123456789, solution.moves.clone());
+        assert!(unbound.score < solution.result.score);
+    }
+
+    #[test]
+    fn test_fixture_json() {
+        let fixture = Solver::new(1).solve(1, 3).fixture();
+        let json = serde_json::to_value(&fixture).unwrap();
+
+        assert_eq!(json["seed"], 1);
+        assert_eq!(json["movesLen"], 3);
+        assert_eq!(json["moves"].as_str().unwrap().len(), 4);
+        assert!(json.get("player").is_none());
+        assert!(json.get("chainId").is_none());
+    }
+}
@@ -16,7 +16,9 @@ contract TilesGame is ReentrancyGuard {
     address public owner;
     uint8 public tokenDecimals;
     uint256 public maxScore;
-    mapping(uint64 => bool) public usedSeeds;
+    // Seeds each player has been paid for. The validator binds a seed to the player who submits
+    // it, so a seed copied by someone else neither pays them its score nor uses it up
+    mapping(address => mapping(uint64 => bool)) public usedSeeds;
 
     event ValidatorSet(address indexed newValidator);
     event TokenSet(address indexed newToken, uint8 tokenDecimals);
@@ -70,10 +72,15 @@ contract TilesGame is ReentrancyGuard {
         bytes calldata moves,
         uint64 numMoves
     ) external nonReentrant {
-        require(!usedSeeds[seed], "Seed already used");
+        require(!usedSeeds[msg.sender][seed], "Seed already used");
         require(moves.length > 0, "Moves cannot be empty");
 
-        uint256 score = validator.getScore(seed, moves, numMoves);
+        uint256 score = validator.getPlayerScore(
+            msg.sender,
+            seed,
+            moves,
+            numMoves
+        );
         require(score > 0, "Invalid score");
 
         if (score > maxScore) {
@@ -87,7 +94,7 @@ contract TilesGame is ReentrancyGuard {
         );
         token.safeTransfer(msg.sender, tokensToTransfer);
 
-        usedSeeds[seed] = true;
+        usedSeeds[msg.sender][seed] = true;
         emit GamePlayed(msg.sender, seed, score, tokensToTransfer);
     }
 
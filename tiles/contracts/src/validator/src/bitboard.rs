@@ -0,0 +1,382 @@
+//! A 4x4 engine on a `u64` bitboard, for the classic rules that `getScore` plays.
+//!
+//! Cell `(x, y)` is nibble `y * 4 + x` counting from the least significant, and holds log2 of
+//! its tile or 0 when empty. That is the packing of [`GameResult::board`]. A move looks each row
+//! up in [`ROW_LEFT`], which holds every 16-bit row slid left and is built at compile time.
+//! Right is left on reversed rows, and up and down are left and right on the transposed board.
+//!
+//! Nothing is allocated per move, unlike `TilesGame`, but the game must play exactly the
+//! same: the same spawns from the same generator, the same merges and the same score. The
+//! differential test below checks that over thousands of random games.
+//!
+//! A nibble holds tiles up to 32768, and two of them would merge into a tile it cannot hold.
+//! [`play`] therefore gives up once a 32768 tile appears, and callers replay such a game with
+//! `TilesGame`.
+//!
+//! [`Position`] exposes a game in progress to host tools such as the solver, which search
+//! ahead by cloning it.
+
+use crate::{
+    direction::Move,
+    game::{GameResult, Rules, TilesGame},
+    prng::Xorshift128Plus,
+};
+use fluentbase_sdk::U256;
+
+type Board = u64;
+
+const NIBBLE_ONES: u64 = 0x1111_1111_1111_1111;
+
+/// Every row slid left, indexed by the row. The low nibble is the leftmost cell.
+static ROW_LEFT: [u16; 65536] = row_left_table();
+
+const fn row_left_table() -> [u16; 65536] {
+    let mut table = [0u16; 65536];
+    let mut row = 0;
+    while row < table.len() {
+        table[row] = slide_row_left(row as u16);
+        row += 1;
+    }
+    table
+}
+
+// Same merge rule as `TilesGame::move_tiles`: tiles slide left and each one merges at most
+// once. 32768s do not merge, since `play` never plays a board that has one
+const fn slide_row_left(row: u16) -> u16 {
+    let mut tiles = [0u16; 4];
+    let mut len = 0;
+    let mut last_merged = false;
+
+    let mut i = 0;
+    while i < 4 {
+        let tile = (row >> (4 * i)) & 0xf;
+        if tile != 0 {
+            if len > 0 && tiles[len - 1] == tile && tile < 0xf && !last_merged {
+                tiles[len - 1] = tile + 1;
+                last_merged = true;
+            } else {
+                tiles[len] = tile;
+                len += 1;
+                last_merged = false;
+            }
+        }
+        i += 1;
+    }
+
+    tiles[0] | tiles[1] << 4 | tiles[2] << 8 | tiles[3] << 12
+}
+
+fn reverse_row(row: u16) -> u16 {
+    (row & 0xf) << 12 | (row & 0xf0) << 4 | (row >> 4) & 0xf0 | row >> 12
+}
+
+fn transpose(board: Board) -> Board {
+    let a1 = board & 0xf0f0_0f0f_f0f0_0f0f;
+    let a2 = board & 0x0000_f0f0_0000_f0f0;
+    let a3 = board & 0x0f0f_0000_0f0f_0000;
+    let a = a1 | a2 << 12 | a3 >> 12;
+    let b1 = a & 0xff00_ff00_00ff_00ff;
+    let b2 = a & 0x00ff_00ff_0000_0000;
+    let b3 = a & 0x0000_0000_ff00_ff00;
+    b1 | b2 >> 24 | b3 << 24
+}
+
+fn slide_rows(board: Board, right: bool) -> Board {
+    let mut slid = 0;
+    for y in 0..4 {
+        let row = (board >> (16 * y)) as u16;
+        let row = if right {
+            reverse_row(ROW_LEFT[reverse_row(row) as usize])
+        } else {
+            ROW_LEFT[row as usize]
+        };
+        slid |= (row as u64) << (16 * y);
+    }
+    slid
+}
+
+fn slide(board: Board, direction: Move) -> Board {
+    match direction {
+        Move::Left => slide_rows(board, false),
+        Move::Right => slide_rows(board, true),
+        Move::Up => transpose(slide_rows(transpose(board), false)),
+        Move::Down => transpose(slide_rows(transpose(board), true)),
+    }
+}
+
+/// One bit per empty cell, in the lowest bit of its nibble.
+fn empty_cells(board: Board) -> u64 {
+    let mut occupied = board | board >> 1;
+    occupied |= occupied >> 2;
+    !occupied & NIBBLE_ONES
+}
+
+fn has_max_tile(board: Board) -> bool {
+    board & board >> 1 & board >> 2 & board >> 3 & NIBBLE_ONES != 0
+}
+
+fn nibble(board: Board, x: usize, y: usize) -> u64 {
+    (board >> (4 * (y * 4 + x))) & 0xf
+}
+
+#[derive(Clone, Debug)]
+struct Spawner {
+    rng: Xorshift128Plus,
+    two_threshold: u64,
+    fours: u32,
+}
+
+impl Spawner {
+    // Same draws as `TilesGame::add_random_tile`, which lists empty cells column by column
+    fn spawn(&mut self, board: Board) -> Board {
+        let available = empty_cells(board).count_ones() as u128;
+        if available == 0 {
+            return board;
+        }
+
+        let value_rnd = self.rng.next();
+        let cell_rnd = self.rng.next();
+        let mut index = ((cell_rnd as u128 * available) >> 64) as usize;
+
+        let exponent = if value_rnd < self.two_threshold {
+            1
+        } else {
+            self.fours += 1;
+            2
+        };
+
+        for x in 0..4 {
+            for y in 0..4 {
+                if nibble(board, x, y) == 0 {
+                    if index == 0 {
+                        return board | exponent << (4 * (y * 4 + x));
+                    }
+                    index -= 1;
+                }
+            }
+        }
+        unreachable!("spawn index out of range")
+    }
+}
+
+/// The score of a board: making a tile of `2^e` out of 2s scores `(e - 1) * 2^e`, and a 4
+/// that spawned as a 4 scored nothing.
+fn score(board: Board, fours: u32) -> u32 {
+    let mut score = 0;
+    for cell in 0..16 {
+        let exponent = (board >> (4 * cell)) & 0xf;
+        if exponent > 1 {
+            score += ((exponent - 1) << exponent) as u32;
+        }
+    }
+    score - 4 * fours
+}
+
+/// A classic game in progress, together with the generator that draws its next spawns. A clone
+/// plays on exactly as the validator would after the same moves.
+#[derive(Clone, Debug)]
+pub struct Position {
+    board: Board,
+    spawner: Spawner,
+}
+
+impl Position {
+    /// The start of the game `seed` plays under the default rules.
+    pub fn new(seed: u64) -> Self {
+        let mut spawner = Spawner {
+            rng: Xorshift128Plus::new(seed),
+            two_threshold: Rules::default().two_threshold(),
+            fours: 0,
+        };
+
+        let mut board = 0;
+        for _ in 0..Rules::default().start_tiles {
+            board = spawner.spawn(board);
+        }
+        Position { board, spawner }
+    }
+
+    /// The board, packed like [`GameResult::board`].
+    pub fn board(&self) -> u64 {
+        self.board
+    }
+
+    pub fn score(&self) -> u32 {
+        score(self.board, self.spawner.fours)
+    }
+
+    pub fn max_tile(&self) -> u32 {
+        let max_exponent = (0..16).map(|cell| (self.board >> (4 * cell)) & 0xf).max();
+        max_exponent.map_or(0, |exponent| 1 << exponent)
+    }
+
+    /// Plays `direction` and spawns the next tile. Returns `false`, and changes nothing, if
+    /// no tile moves.
+    pub fn play(&mut self, direction: Move) -> bool {
+        let slid = slide(self.board, direction);
+        if slid == self.board {
+            return false;
+        }
+        self.board = self.spawner.spawn(slid);
+        true
+    }
+
+    /// No move changes the board.
+    pub fn is_over(&self) -> bool {
+        Move::ALL
+            .iter()
+            .all(|&direction| slide(self.board, direction) == self.board)
+    }
+
+    /// The board has a 32768 tile. Playing on is not the validator's game, see the module docs.
+    pub fn has_max_tile(&self) -> bool {
+        has_max_tile(self.board)
+    }
+}
+
+/// Plays `moves` like `TilesGame::play_game` with the default rules, or returns `None` once
+/// the game has a tile too large for the bitboard.
+pub fn play(seed: u64, moves: &[Move]) -> Option<GameResult> {
+    let mut position = Position::new(seed);
+    let mut moved = 0;
+    let mut ignored_moves = 0;
+    let mut game_over = false;
+    for &direction in moves {
+        if position.play(direction) {
+            if position.has_max_tile() {
+                return None;
+            }
+            moved += 1;
+        } else {
+            ignored_moves += 1;
+            if position.is_over() {
+                game_over = true;
+                break;
+            }
+        }
+    }
+
+    Some(GameResult {
+        score: position.score(),
+        max_tile: position.max_tile(),
+        moves: moved,
+        ignored_moves,
+        game_over,
+        board: U256::from(position.board()),
+    })
+}
+
+/// [`play`], falling back to `TilesGame` for games the bitboard cannot hold.
+pub fn play_or_fallback(seed: u64, moves: alloc::vec::Vec<Move>) -> GameResult {
+    play(seed, &moves).unwrap_or_else(|| TilesGame::play_game(Rules::default(), seed, moves))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prng::SplitMix64;
+
+    fn row(tiles: [u16; 4]) -> u16 {
+        tiles[0] | tiles[1] << 4 | tiles[2] << 8 | tiles[3] << 12
+    }
+
+    fn board(rows: [[u64; 4]; 4]) -> Board {
+        let mut board = 0;
+        for (y, row) in rows.iter().enumerate() {
+            for (x, exponent) in row.iter().enumerate() {
+                board |= exponent << (4 * (y * 4 + x));
+            }
+        }
+        board
+    }
+
+    #[test]
+    fn test_row_table() {
+        for (before, after) in [
+            ([1, 1, 1, 1], [2, 2, 0, 0]),
+            ([1, 1, 2, 0], [2, 2, 0, 0]),
+            ([0, 2, 1, 1], [2, 2, 0, 0]),
+            ([3, 0, 3, 3], [4, 3, 0, 0]),
+            ([1, 2, 3, 4], [1, 2, 3, 4]),
+            ([0xf, 0xf, 0, 1], [0xf, 0xf, 1, 0]),
+        ] {
+            assert_eq!(ROW_LEFT[row(before) as usize], row(after), "{before:?}");
+        }
+        assert_eq!(reverse_row(row([1, 2, 3, 4])), row([4, 3, 2, 1]));
+    }
+
+    #[test]
+    fn test_transpose() {
+        let rows = [[1, 2, 3, 4], [5, 6, 7, 8], [9, 10, 11, 12], [13, 14, 15, 0]];
+        let mut columns = [[0; 4]; 4];
+        for y in 0..4 {
+            for x in 0..4 {
+                columns[x][y] = rows[y][x];
+            }
+        }
+        assert_eq!(transpose(board(rows)), board(columns));
+    }
+
+    #[test]
+    fn test_slides() {
+        let start = board([[1, 1, 0, 0], [0, 0, 0, 0], [1, 0, 0, 0], [0, 0, 0, 2]]);
+
+        assert_eq!(
+            slide(start, Move::Right),
+            board([[0, 0, 0, 2], [0, 0, 0, 0], [0, 0, 0, 1], [0, 0, 0, 2]])
+        );
+        assert_eq!(
+            slide(start, Move::Up),
+            board([[2, 1, 0, 2], [0, 0, 0, 0], [0, 0, 0, 0], [0, 0, 0, 0]])
+        );
+        assert_eq!(
+            slide(start, Move::Down),
+            board([[0, 0, 0, 0], [0, 0, 0, 0], [0, 0, 0, 0], [2, 1, 0, 2]])
+        );
+    }
+
+    #[test]
+    fn test_gives_up_at_max_tile() {
+        assert!(!has_max_tile(board([
+            [0xe, 1, 0, 0],
+            [0; 4],
+            [0; 4],
+            [0; 4]
+        ])));
+        assert!(has_max_tile(board([
+            [0; 4],
+            [0; 4],
+            [0, 0, 0xf, 0],
+            [0; 4]
+        ])));
+    }
+
+    #[test]
+    fn test_differential_against_grid_engine() {
+        // Random seeds and move strings, long enough for many games to end
+        let mut rng = SplitMix64::new(2048);
+        for _ in 0..3_000 {
+            let seed = rng.next();
+            let len = rng.next() % 1_500;
+            let moves: Vec<Move> = (0..len)
+                .map(|_| Move::from_bits((rng.next() & 0b11) as u8).unwrap())
+                .collect();
+
+            let expected = TilesGame::play_game(Rules::default(), seed, moves.clone());
+            assert_eq!(
+                play(seed, &moves),
+                Some(expected),
+                "seed {seed}, {len} moves"
+            );
+        }
+    }
+
+    #[test]
+    fn test_differential_on_edge_seeds() {
+        let moves = [Move::Left, Move::Up, Move::Right, Move::Down].repeat(300);
+        for seed in [0u64, 1, 42, 123456789, u64::MAX, u64::MAX - 1] {
+            let expected = TilesGame::play_game(Rules::default(), seed, moves.clone());
+            assert_eq!(play_or_fallback(seed, moves.clone()), expected);
+        }
+    }
+}
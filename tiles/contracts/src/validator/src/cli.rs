@@ -0,0 +1,83 @@
+//! The `replay` host CLI: prints the [`TilesGame::replay`] trace of a game as JSON, so a
+//! disputed score can be compared move by move with what the client showed.
+//!
+//! ```text
+//! cargo run --features cli --bin replay -- 123456789 0x2281a46986247100 29
+//! ```
+
+use crate::{direction, game::TilesGame};
+
+pub const USAGE: &str = "usage: replay <seed> <moves hex> <moves len>";
+
+/// Takes the arguments of `getScore`, as decimal and hex strings, and returns the replay trace
+/// as pretty-printed JSON.
+pub fn run(args: &[String]) -> Result<String, String> {
+    let [seed, moves, moves_len] = args else {
+        return Err(USAGE.to_string());
+    };
+
+    let seed: u64 = seed
+        .parse()
+        .map_err(|error| format!("invalid seed {seed:?}: {error}"))?;
+    let moves = hex::decode(moves.strip_prefix("0x").unwrap_or(moves))
+        .map_err(|error| format!("invalid moves {moves:?}: {error}"))?;
+    let moves_len: u64 = moves_len
+        .parse()
+        .map_err(|error| format!("invalid moves len {moves_len:?}: {error}"))?;
+
+    // Rejects what the validator rejects, rather than replaying a game it would not score
+    let moves = direction::decode_moves(&moves, moves_len)
+        .map_err(|error| format!("invalid moves encoding: {error}"))?;
+
+    let steps = TilesGame::replay(seed, moves);
+    serde_json::to_string_pretty(&steps).map_err(|error| error.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Value;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn test_trace_json() {
+        let json = run(&args(&["123456789", "0x2280", "7"])).unwrap();
+        let steps: Vec<Value> = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(steps.len(), 7);
+        assert_eq!(steps[0]["direction"], "Left");
+        assert_eq!(steps[6]["score"], 20);
+        assert_eq!(steps[6]["board"][0], serde_json::json!([8, 4, 0, 0]));
+
+        let spawned = &steps[0]["spawned"];
+        assert_eq!(spawned["kind"], "spawned");
+        assert!(spawned["at"].is_array());
+        for change in steps
+            .iter()
+            .flat_map(|step| step["changes"].as_array().unwrap())
+        {
+            assert!(["moved", "merged"].contains(&change["kind"].as_str().unwrap()));
+        }
+    }
+
+    #[test]
+    fn test_bad_arguments() {
+        assert_eq!(run(&args(&["123456789", "0x2280"])), Err(USAGE.to_string()));
+        assert!(run(&args(&["-1", "0x2280", "7"]))
+            .unwrap_err()
+            .starts_with("invalid seed"));
+        assert!(run(&args(&["1", "0xzz", "7"]))
+            .unwrap_err()
+            .starts_with("invalid moves"));
+        assert_eq!(
+            run(&args(&["1", "0x2281", "7"])),
+            Err(
+                "invalid moves encoding: padding bits after the last move are 0x01, not zero"
+                    .into()
+            )
+        );
+    }
+}
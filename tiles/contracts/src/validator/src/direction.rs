@@ -1,6 +1,7 @@
 use alloc::vec::Vec;
 
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum Move {
     Left,
     Right,
@@ -9,6 +10,8 @@ pub enum Move {
 }
 
 impl Move {
+    pub const ALL: [Move; 4] = [Move::Left, Move::Right, Move::Up, Move::Down];
+
     pub fn from_bits(bits: u8) -> Option<Self> {
         match bits {
             0b00 => Some(Move::Left),
@@ -19,6 +22,15 @@ impl Move {
         }
     }
 
+    pub fn to_bits(self) -> u8 {
+        match self {
+            Move::Left => 0b00,
+            Move::Right => 0b01,
+            Move::Up => 0b10,
+            Move::Down => 0b11,
+        }
+    }
+
     pub fn to_vector(self) -> (i8, i8) {
         match self {
             Move::Up => (0, -1),
@@ -29,6 +41,76 @@ impl Move {
     }
 }
 
+/// Why [`decode_moves`] rejected an encoding of moves.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MovesError {
+    /// `moves_len` moves need more bytes than were sent.
+    TooShort { bytes: usize, moves_len: u64 },
+    /// Bytes were sent after the last one the moves need.
+    TrailingBytes { bytes: usize, moves_len: u64 },
+    /// The bits after the last move, the low bits of the last byte, are not all zero.
+    NonZeroPadding { padding: u8 },
+}
+
+impl core::fmt::Display for MovesError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            MovesError::TooShort { bytes, moves_len } => {
+                write!(f, "{moves_len} moves do not fit in {bytes} bytes")
+            }
+            MovesError::TrailingBytes { bytes, moves_len } => {
+                write!(f, "{bytes} bytes hold more than {moves_len} moves")
+            }
+            MovesError::NonZeroPadding { padding } => {
+                write!(
+                    f,
+                    "padding bits after the last move are {padding:#04x}, not zero"
+                )
+            }
+        }
+    }
+}
+
+/// Decodes exactly `moves_len` moves, 2 bits each from the high bits of every byte. `bytes` must
+/// be the shortest encoding: `ceil(moves_len / 4)` bytes, with the bits after the last move zero.
+pub fn decode_moves(bytes: &[u8], moves_len: u64) -> Result<Vec<Move>, MovesError> {
+    let needed = moves_len.div_ceil(4);
+    if (bytes.len() as u64) < needed {
+        return Err(MovesError::TooShort {
+            bytes: bytes.len(),
+            moves_len,
+        });
+    }
+    if bytes.len() as u64 > needed {
+        return Err(MovesError::TrailingBytes {
+            bytes: bytes.len(),
+            moves_len,
+        });
+    }
+
+    let used_bits = (moves_len % 4) * 2;
+    if used_bits > 0 {
+        let padding = bytes[bytes.len() - 1] & (0xff >> used_bits);
+        if padding != 0 {
+            return Err(MovesError::NonZeroPadding { padding });
+        }
+    }
+
+    Ok(moves_from_bytes(bytes, moves_len))
+}
+
+/// The shortest encoding of `moves`, which [`decode_moves`] accepts: what clients submit as
+/// `moves`, with `moves.len()` as `moves_len`.
+pub fn encode_moves(moves: &[Move]) -> Vec<u8> {
+    let mut bytes = alloc::vec![0u8; moves.len().div_ceil(4)];
+    for (i, direction) in moves.iter().enumerate() {
+        bytes[i / 4] |= direction.to_bits() << (6 - 2 * (i % 4));
+    }
+    bytes
+}
+
+/// Decodes up to `moves_len` moves, stopping early at the end of `bytes` and ignoring whatever
+/// follows the last move. See [`decode_moves`] for the strict decoding the validator uses.
 pub fn moves_from_bytes(bytes: &[u8], moves_len: u64) -> Vec<Move> {
     let mut moves = Vec::new();
     let mut moves_handled = 0;
@@ -53,6 +135,7 @@ pub fn moves_from_bytes(bytes: &[u8], moves_len: u64) -> Vec<Move> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use proptest::prelude::*;
 
     #[test]
     fn test_moves_parsing() {
@@ -111,4 +194,86 @@ mod tests {
         let moves = moves_from_bytes(&moves_b, moves_expected.len() as u64);
         assert_eq!(moves, moves_expected);
     }
+
+    #[test]
+    fn test_strict_decoding() {
+        use Move::*;
+
+        assert_eq!(decode_moves(&[], 0), Ok(vec![]));
+        assert_eq!(
+            decode_moves(&[0x22, 0x80], 7),
+            Ok(vec![Left, Up, Left, Up, Up, Left, Left])
+        );
+        assert_eq!(decode_moves(&[0x1b], 4), Ok(vec![Left, Right, Up, Down]));
+        assert_eq!(
+            encode_moves(&[Left, Up, Left, Up, Up, Left, Left]),
+            [0x22, 0x80]
+        );
+
+        assert_eq!(
+            decode_moves(&[0x22], 7),
+            Err(MovesError::TooShort {
+                bytes: 1,
+                moves_len: 7
+            })
+        );
+        assert_eq!(
+            decode_moves(&[0x22, 0x80, 0x00], 7),
+            Err(MovesError::TrailingBytes {
+                bytes: 3,
+                moves_len: 7
+            })
+        );
+        assert_eq!(
+            decode_moves(&[0x01], 0),
+            Err(MovesError::TrailingBytes {
+                bytes: 1,
+                moves_len: 0
+            })
+        );
+        // The lenient decoder plays the same 7 moves, ignoring the 8th
+        assert_eq!(
+            decode_moves(&[0x22, 0x81], 7),
+            Err(MovesError::NonZeroPadding { padding: 0x01 })
+        );
+        assert_eq!(
+            decode_moves(&[0x7f], 1),
+            Err(MovesError::NonZeroPadding { padding: 0x3f })
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn test_strict_decoding_accepts_only_the_shortest_encoding(
+            bytes in prop::collection::vec(any::<u8>(), 0..64),
+            offset in -8i64..8,
+        ) {
+            // Around the moves the bytes can hold, where every error and valid encodings occur
+            let moves_len = (bytes.len() as i64 * 4 + offset).max(0) as u64;
+            let lenient = moves_from_bytes(&bytes, moves_len);
+            let canonical = lenient.len() as u64 == moves_len && encode_moves(&lenient) == bytes;
+
+            match decode_moves(&bytes, moves_len) {
+                Ok(moves) => {
+                    prop_assert!(canonical);
+                    prop_assert_eq!(moves, lenient);
+                }
+                Err(_) => prop_assert!(!canonical),
+            }
+        }
+
+        #[test]
+        fn test_strict_decoding_round_trips(bits in prop::collection::vec(0u8..4, 0..300)) {
+            let moves: Vec<Move> = bits.iter().map(|&bits| Move::from_bits(bits).unwrap()).collect();
+            prop_assert_eq!(decode_moves(&encode_moves(&moves), moves.len() as u64), Ok(moves));
+        }
+
+        #[test]
+        fn test_strict_decoding_never_panics(
+            bytes in prop::collection::vec(any::<u8>(), 0..64),
+            moves_len in any::<u64>(),
+        ) {
+            let _ = decode_moves(&bytes, moves_len);
+        }
+    }
 }
@@ -1,33 +1,148 @@
 use crate::{
     direction::Move,
     grid::{Grid, Tile},
-    prng::Xorshift128Plus,
+    prng::{SeedVersion, Xorshift128Plus},
 };
 use alloc::{vec, vec::Vec};
 use core::{fmt::Debug, usize};
+use fluentbase_sdk::{codec::Codec, U256};
+
+/// Denominator of [`Rules::four_probability_bps`]
+pub const BPS: u16 = 10_000;
+
+/// Board sizes a game can be played on. Coordinates are `i8`, and a 2x2 board is over
+/// before it starts.
+pub const SIZES: core::ops::RangeInclusive<usize> = 3..=8;
+
+/// Rule parameters of a game. The default is the classic 4x4 game that `getScore` plays.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Rules {
+    pub size: usize,
+    /// Chance that a spawned tile is a 4 rather than a 2, in basis points.
+    pub four_probability_bps: u16,
+    pub start_tiles: usize,
+    /// The game ends as won once a merge makes this tile. `0` plays on until no moves remain.
+    pub win_tile: u32,
+    pub seed_version: SeedVersion,
+}
+
+impl Default for Rules {
+    fn default() -> Self {
+        Self {
+            size: 4,
+            four_probability_bps: 1_000,
+            start_tiles: 2,
+            win_tile: 0,
+            seed_version: SeedVersion::V1,
+        }
+    }
+}
+
+impl Rules {
+    pub fn is_valid(&self) -> bool {
+        SIZES.contains(&self.size)
+            && self.four_probability_bps <= BPS
+            && (1..=self.size * self.size).contains(&self.start_tiles)
+            && (self.win_tile == 0 || (self.win_tile > 2 && self.win_tile.is_power_of_two()))
+    }
+
+    /// Spawned tiles are 2s below this threshold and 4s from it up. The default 10% gives
+    /// `0.9 * u64::MAX`.
+    pub(crate) fn two_threshold(&self) -> u64 {
+        let two_bps = (BPS - self.four_probability_bps) as u128;
+        (u64::MAX as u128 * two_bps / BPS as u128) as u64
+    }
+}
+
+/// Outcome of a replay, returned as is by the validator's `getGameResult`.
+#[derive(Codec, Clone, Copy, Debug, Default, PartialEq)]
+pub struct GameResult {
+    pub score: u32,
+    pub max_tile: u32,
+    /// Moves that changed the board.
+    pub moves: u32,
+    /// Moves that changed nothing. Moves after the game ended are not played, so they are
+    /// in neither count.
+    pub ignored_moves: u32,
+    pub game_over: bool,
+    /// The final board, one nibble per cell: cell `(x, y)` is nibble `y * size + x`,
+    /// counting from the least significant. A nibble holds log2 of the tile, 0 for an empty
+    /// cell, capped at 15.
+    pub board: U256,
+}
+
+/// What happened to a tile during a move. Positions are `[x, y]`, with `[0, 0]` top left.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind", rename_all = "camelCase"))]
+pub enum TileChange {
+    Moved {
+        from: [usize; 2],
+        to: [usize; 2],
+        value: u32,
+    },
+    /// The tile at `from` merged into the one at `into`, making a tile of `value`.
+    Merged {
+        from: [usize; 2],
+        into: [usize; 2],
+        value: u32,
+    },
+    /// A new tile, after a move that changed the board.
+    Spawned { at: [usize; 2], value: u32 },
+}
 
-/// 90% threshold for tile value selection (0.9 * u64::MAX)
-const TILE_VALUE_THRESHOLD: u64 = (u64::MAX as u128 * 9 / 10) as u64;
+/// One move of a [`TilesGame::replay`] trace.
+#[cfg(feature = "std")]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Step {
+    pub direction: Move,
+    /// Whether the move changed the board. A move that did not is ignored for scoring.
+    pub moved: bool,
+    /// Moves and merges, in the order the engine made them.
+    pub changes: Vec<TileChange>,
+    /// Always a [`TileChange::Spawned`], unless the move did not change the board.
+    pub spawned: Option<TileChange>,
+    /// Tile values after the move, row by row, 0 for an empty cell.
+    pub board: Vec<Vec<u32>>,
+    pub score: u32,
+}
 
 #[derive(Debug)]
 pub struct TilesGame {
     size: usize,
+    rules: Rules,
     grid: Grid,
     score: u32,
     moves: Vec<Move>,
+    ignored_moves: u32,
     game_over: bool,
+    won: bool,
     rng: Xorshift128Plus,
 }
 
 impl TilesGame {
     pub fn new(size: usize, seed: u64) -> Self {
+        Self::with_rules(
+            Rules {
+                size,
+                ..Rules::default()
+            },
+            seed,
+        )
+    }
+
+    pub fn with_rules(rules: Rules, seed: u64) -> Self {
         let mut game = Self {
-            size,
-            grid: Grid::new(size),
+            size: rules.size,
+            rules,
+            grid: Grid::new(rules.size),
             score: 0,
             moves: Vec::new(),
+            ignored_moves: 0,
             game_over: false,
-            rng: Xorshift128Plus::new(seed),
+            won: false,
+            rng: Xorshift128Plus::with_version(seed, rules.seed_version),
         };
         game.add_start_tiles();
         game
@@ -37,35 +152,116 @@ impl TilesGame {
     pub fn with_state(size: usize, seed: u64, score: u32, grid: Grid) -> Self {
         Self {
             size,
+            rules: Rules {
+                size,
+                ..Rules::default()
+            },
             grid,
             score,
             moves: Vec::new(),
+            ignored_moves: 0,
             game_over: false,
+            won: false,
             rng: Xorshift128Plus::new(seed),
         }
     }
 
+    #[cfg(test)]
     pub fn play(seed: u64, moves: Vec<Move>) -> u32 {
-        let mut game = TilesGame::new(4, seed);
+        Self::play_with_rules(Rules::default(), seed, moves)
+    }
+
+    pub fn play_with_rules(rules: Rules, seed: u64, moves: Vec<Move>) -> u32 {
+        Self::play_game(rules, seed, moves).score
+    }
+
+    pub fn play_game(rules: Rules, seed: u64, moves: Vec<Move>) -> GameResult {
+        let mut game = TilesGame::with_rules(rules, seed);
         for direction in moves.into_iter() {
             game.move_tiles(direction);
-            if game.game_over {
+            if game.game_over || game.won {
                 break;
             }
         }
-        game.score
+        game.result()
+    }
+
+    pub fn result(&self) -> GameResult {
+        let mut max_tile = 0;
+        let mut board = U256::ZERO;
+        for x in 0..self.size {
+            for y in 0..self.size {
+                if let Some(tile) = self.grid.cell_content(x, y) {
+                    max_tile = max_tile.max(tile.value);
+                    let exponent = tile.value.trailing_zeros().min(15);
+                    board |= U256::from(exponent) << (4 * (y * self.size + x));
+                }
+            }
+        }
+
+        GameResult {
+            score: self.score,
+            max_tile,
+            moves: self.moves.len() as u32,
+            ignored_moves: self.ignored_moves,
+            game_over: self.game_over,
+            board,
+        }
     }
 
     fn add_start_tiles(&mut self) {
-        for _ in 0..2 {
+        for _ in 0..self.rules.start_tiles {
             self.add_random_tile();
         }
     }
 
-    fn add_random_tile(&mut self) {
+    /// Plays `moves` like [`Self::play`], recording what each move did.
+    ///
+    /// The trace stops where scoring stops: moves after the game is over are not played and
+    /// get no step.
+    #[cfg(feature = "std")]
+    pub fn replay(seed: u64, moves: Vec<Move>) -> Vec<Step> {
+        let mut game = TilesGame::with_rules(Rules::default(), seed);
+        let mut steps = Vec::new();
+        for direction in moves.into_iter() {
+            let mut changes = Vec::new();
+            let mut spawned = None;
+            game.move_tiles_observed(direction, |change| match change {
+                TileChange::Spawned { .. } => spawned = Some(change),
+                _ => changes.push(change),
+            });
+
+            steps.push(Step {
+                direction,
+                moved: !changes.is_empty(),
+                changes,
+                spawned,
+                board: game.board_rows(),
+                score: game.score,
+            });
+            if game.game_over || game.won {
+                break;
+            }
+        }
+        steps
+    }
+
+    /// Tile values row by row, 0 for an empty cell.
+    #[cfg(feature = "std")]
+    fn board_rows(&self) -> Vec<Vec<u32>> {
+        (0..self.size)
+            .map(|y| {
+                (0..self.size)
+                    .map(|x| self.grid.cell_content(x, y).map_or(0, |tile| tile.value))
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn add_random_tile(&mut self) -> Option<Tile> {
         let available = self.grid.available_cells();
         if available.is_empty() {
-            return;
+            return None;
         }
 
         let value_rnd = self.rng.next();
@@ -77,17 +273,24 @@ impl TilesGame {
 
         let (x, y) = available[index];
 
-        // Equivalent to: (value_rnd / u64::MAX) < 0.9
-        let value = if value_rnd < TILE_VALUE_THRESHOLD {
+        // Equivalent to: (value_rnd / u64::MAX) < 0.9, for the default rules
+        let value = if value_rnd < self.rules.two_threshold() {
             2
         } else {
             4
         };
 
-        self.grid.insert_tile(Tile::new(x, y, value));
+        let tile = Tile::new(x, y, value);
+        self.grid.insert_tile(tile);
+        Some(tile)
     }
 
     fn move_tiles(&mut self, direction: Move) {
+        self.move_tiles_observed(direction, |_| {});
+    }
+
+    /// [`Self::move_tiles`], reporting every tile that moves, merges or spawns to `observe`.
+    fn move_tiles_observed(&mut self, direction: Move, mut observe: impl FnMut(TileChange)) {
         let mut moved = false;
         let vector = direction.to_vector();
         let traversals = self.build_traversals(vector);
@@ -108,11 +311,25 @@ impl TilesGame {
                             self.grid.remove_tile(&tile);
                             self.score += merged_tile.value;
                             moved = true;
+                            observe(TileChange::Merged {
+                                from: [x, y],
+                                into: [merged_tile.x, merged_tile.y],
+                                value: merged_tile.value,
+                            });
+
+                            if merged_tile.value == self.rules.win_tile {
+                                self.won = true;
+                            }
                         }
                         _ => {
                             self.move_tile(&mut tile, (farthest.0 as usize, farthest.1 as usize));
                             if (farthest.0 as usize, farthest.1 as usize) != (x, y) {
                                 moved = true;
+                                observe(TileChange::Moved {
+                                    from: [x, y],
+                                    to: [tile.x, tile.y],
+                                    value: tile.value,
+                                });
                             }
                         }
                     }
@@ -124,8 +341,14 @@ impl TilesGame {
 
         if moved {
             self.moves.push(direction);
-            self.add_random_tile();
+            if let Some(tile) = self.add_random_tile() {
+                observe(TileChange::Spawned {
+                    at: [tile.x, tile.y],
+                    value: tile.value,
+                });
+            }
         } else {
+            self.ignored_moves += 1;
             if !self.moves_available() {
                 self.game_over = true;
             }
@@ -188,8 +411,32 @@ impl TilesGame {
         self.grid.insert_tile(*tile);
     }
 
+    /// Whether any move can still change the board: there is an empty cell, or two equal
+    /// tiles sit next to each other and would merge.
     fn moves_available(&self) -> bool {
-        !self.grid.available_cells().is_empty()
+        !self.grid.available_cells().is_empty() || self.tile_matches_available()
+    }
+
+    fn tile_matches_available(&self) -> bool {
+        for x in 0..self.size {
+            for y in 0..self.size {
+                if let Some(tile) = self.grid.cell_content(x, y) {
+                    for direction in Move::ALL {
+                        let vector = direction.to_vector();
+                        let other = (x as i8 + vector.0, y as i8 + vector.1);
+                        if !self.grid.within_bounds(other.0, other.1) {
+                            continue;
+                        }
+
+                        match self.grid.cell_content(other.0 as usize, other.1 as usize) {
+                            Some(other) if other.value == tile.value => return true,
+                            _ => {}
+                        }
+                    }
+                }
+            }
+        }
+        false
     }
 }
 
@@ -244,6 +491,438 @@ mod tests {
         }
     }
 
+    /// A full board that still has a merge, the moves played on it and the expected outcome.
+    ///
+    /// Each board's first move is a no-op. Before merges counted as available moves, that
+    /// no-op ended the game with the board's starting score.
+    struct Golden {
+        name: &'static str,
+        board: [[u32; 4]; 4],
+        moves: &'static [Move],
+        score: u32,
+        game_over: bool,
+        board_after: [[u32; 4]; 4],
+    }
+
+    const GOLDEN_SEED: u64 = 123456789;
+
+    #[rustfmt::skip]
+    const GOLDEN: &[Golden] = &[
+        Golden {
+            name: "horizontal pair in the top row",
+            board: [
+                [2, 2, 4, 8],
+                [4, 8, 16, 32],
+                [8, 16, 32, 64],
+                [16, 32, 64, 128],
+            ],
+            moves: &[Move::Up, Move::Down, Move::Left],
+            score: 4,
+            game_over: false,
+            board_after: [
+                [4, 4, 8, 2],
+                [4, 8, 16, 32],
+                [8, 16, 32, 64],
+                [16, 32, 64, 128],
+            ],
+        },
+        Golden {
+            name: "vertical pair in the left column",
+            board: [
+                [2, 4, 8, 16],
+                [2, 8, 16, 32],
+                [4, 16, 32, 64],
+                [8, 32, 64, 128],
+            ],
+            moves: &[Move::Left, Move::Right, Move::Down],
+            score: 4,
+            game_over: false,
+            board_after: [
+                [2, 4, 8, 16],
+                [4, 8, 16, 32],
+                [4, 16, 32, 64],
+                [8, 32, 64, 128],
+            ],
+        },
+        Golden {
+            name: "pair in the bottom-right corner",
+            board: [
+                [2, 4, 2, 4],
+                [4, 2, 4, 2],
+                [2, 4, 2, 4],
+                [4, 2, 8, 8],
+            ],
+            moves: &[Move::Up, Move::Down, Move::Right],
+            score: 16,
+            game_over: false,
+            board_after: [
+                [2, 4, 2, 4],
+                [4, 2, 4, 2],
+                [2, 4, 2, 4],
+                [2, 4, 2, 16],
+            ],
+        },
+        Golden {
+            name: "every row merges",
+            board: [
+                [2, 2, 2, 2],
+                [4, 4, 4, 4],
+                [8, 8, 8, 8],
+                [16, 16, 16, 16],
+            ],
+            moves: &[Move::Up, Move::Left, Move::Left, Move::Up],
+            score: 240,
+            game_over: false,
+            board_after: [
+                [8, 2, 4, 0],
+                [16, 0, 2, 0],
+                [32, 0, 0, 0],
+                [64, 0, 0, 0],
+            ],
+        },
+    ];
+
+    fn grid_from_board(board: &[[u32; 4]; 4]) -> Grid {
+        grid_from_vec(board.iter().map(|row| row.to_vec()).collect())
+    }
+
+    fn play_golden(golden: &Golden) -> TilesGame {
+        let mut game = TilesGame::with_state(4, GOLDEN_SEED, 0, grid_from_board(&golden.board));
+        for &direction in golden.moves {
+            game.move_tiles(direction);
+            if game.game_over {
+                break;
+            }
+        }
+        game
+    }
+
+    #[test]
+    fn test_full_board_with_merges_is_not_over() {
+        for golden in GOLDEN {
+            let game = TilesGame::with_state(4, GOLDEN_SEED, 0, grid_from_board(&golden.board));
+            assert!(game.grid.available_cells().is_empty(), "{}", golden.name);
+            assert!(game.moves_available(), "{}", golden.name);
+        }
+    }
+
+    #[test]
+    fn test_golden_full_boards() {
+        for golden in GOLDEN {
+            let game = play_golden(golden);
+
+            assert_eq!(game.score, golden.score, "{}", golden.name);
+            assert_eq!(game.game_over, golden.game_over, "{}", golden.name);
+            assert_eq!(
+                game.grid,
+                grid_from_board(&golden.board_after),
+                "{}",
+                golden.name
+            );
+        }
+    }
+
+    #[test]
+    fn test_stuck_board_is_over() {
+        let grid = grid_from_vec(vec![
+            vec![2, 4, 2, 4],
+            vec![4, 2, 4, 2],
+            vec![2, 4, 2, 4],
+            vec![4, 2, 4, 2],
+        ]);
+        let mut game = TilesGame::with_state(4, GOLDEN_SEED, 1000, grid);
+        assert!(!game.moves_available());
+
+        game.move_tiles(Move::Left);
+        assert!(game.game_over);
+        assert_eq!(game.score, 1000);
+        assert!(game.moves.is_empty());
+
+        let result = game.result();
+        assert!(result.game_over);
+        assert_eq!((result.moves, result.ignored_moves), (0, 1));
+    }
+
+    fn occupied(game: &TilesGame) -> usize {
+        game.size * game.size - game.grid.available_cells().len()
+    }
+
+    fn tile_values(game: &TilesGame) -> Vec<u32> {
+        game.grid
+            .cells
+            .iter()
+            .flatten()
+            .flatten()
+            .map(|tile| tile.value)
+            .collect()
+    }
+
+    #[test]
+    fn test_default_rules_keep_classic_spawns() {
+        assert_eq!(
+            Rules::default().two_threshold(),
+            (u64::MAX as u128 * 9 / 10) as u64
+        );
+
+        let moves = [Move::Left, Move::Up, Move::Right, Move::Down].repeat(25);
+        for seed in [0u64, 42, 123456789, u64::MAX] {
+            assert_eq!(
+                TilesGame::play(seed, moves.clone()),
+                TilesGame::play_with_rules(Rules::default(), seed, moves.clone())
+            );
+        }
+    }
+
+    #[test]
+    fn test_board_sizes() {
+        for size in [3, 5, 6] {
+            let rules = Rules {
+                size,
+                ..Rules::default()
+            };
+            assert!(rules.is_valid());
+
+            let mut game = TilesGame::with_rules(rules, 123456789);
+            assert_eq!(game.grid.cells.len(), size);
+            assert_eq!(occupied(&game), 2);
+
+            for direction in [Move::Left, Move::Up, Move::Right, Move::Down].repeat(10) {
+                game.move_tiles(direction);
+            }
+            assert!(occupied(&game) > 2);
+        }
+    }
+
+    #[test]
+    fn test_start_tiles_and_four_probability() {
+        let all_fours = Rules {
+            size: 5,
+            four_probability_bps: BPS,
+            start_tiles: 6,
+            win_tile: 0,
+            seed_version: SeedVersion::V1,
+        };
+        let game = TilesGame::with_rules(all_fours, 123456789);
+        assert_eq!(tile_values(&game), vec![4; 6]);
+
+        let all_twos = Rules {
+            four_probability_bps: 0,
+            ..all_fours
+        };
+        let game = TilesGame::with_rules(all_twos, 123456789);
+        assert_eq!(tile_values(&game), vec![2; 6]);
+    }
+
+    #[test]
+    fn test_win_tile_ends_game() {
+        let moves = [Move::Left, Move::Up].repeat(100);
+        let classic = TilesGame::play(123456789, moves.clone());
+
+        let rules = Rules {
+            win_tile: 8,
+            ..Rules::default()
+        };
+        let mut game = TilesGame::with_rules(rules, 123456789);
+        for &direction in &moves {
+            game.move_tiles(direction);
+            if game.won {
+                break;
+            }
+        }
+        assert!(game.won);
+        assert!(tile_values(&game).contains(&8));
+        assert_eq!(
+            TilesGame::play_with_rules(rules, 123456789, moves),
+            game.score
+        );
+        assert!(game.score < classic);
+    }
+
+    #[test]
+    fn test_v2_seeding_spreads_start_tiles() {
+        // Consecutive seeds, starting from the degenerate seed 0
+        let rules = Rules {
+            seed_version: SeedVersion::V2,
+            ..Rules::default()
+        };
+        let mut cells = [0u32; 16];
+        let mut fours = 0;
+        for seed in 0..8_000 {
+            let game = TilesGame::with_rules(rules, seed);
+            for tile in game.grid.cells.iter().flatten().flatten() {
+                cells[tile.y * 4 + tile.x] += 1;
+                if tile.value == 4 {
+                    fours += 1;
+                }
+            }
+        }
+
+        // 16_000 tiles: 1_000 per cell and 1_600 fours expected, with about 5 standard
+        // deviations of slack
+        for (cell, count) in cells.into_iter().enumerate() {
+            assert!((850..=1_150).contains(&count), "cell {cell}: {count}");
+        }
+        assert!((1_440..=1_760).contains(&fours), "fours: {fours}");
+    }
+
+    #[test]
+    fn test_v1_seed_zero_is_degenerate() {
+        // An all-zero state: every spawn is a 2 in the first free cell
+        let game = TilesGame::new(4, 0);
+        assert_eq!(game.grid.cells[0][0].map(|tile| tile.value), Some(2));
+        assert_eq!(game.grid.cells[0][1].map(|tile| tile.value), Some(2));
+
+        let rules = Rules {
+            seed_version: SeedVersion::V2,
+            ..Rules::default()
+        };
+        let game = TilesGame::with_rules(rules, 0);
+        assert_ne!(
+            (game.grid.cells[0][0], game.grid.cells[0][1]),
+            (Some(Tile::new(0, 0, 2)), Some(Tile::new(0, 1, 2)))
+        );
+    }
+
+    #[test]
+    fn test_invalid_rules() {
+        let valid = Rules::default();
+        for rules in [
+            Rules { size: 2, ..valid },
+            Rules { size: 9, ..valid },
+            Rules {
+                four_probability_bps: BPS + 1,
+                ..valid
+            },
+            Rules {
+                start_tiles: 0,
+                ..valid
+            },
+            Rules {
+                start_tiles: 17,
+                ..valid
+            },
+            Rules {
+                win_tile: 2,
+                ..valid
+            },
+            Rules {
+                win_tile: 100,
+                ..valid
+            },
+        ] {
+            assert!(!rules.is_valid(), "{rules:?}");
+        }
+    }
+
+    #[test]
+    fn test_result_counts_moves() {
+        let golden = &GOLDEN[0];
+        let game = play_golden(golden);
+
+        // Up and Down change nothing, Left merges the top row
+        let result = game.result();
+        assert_eq!(result.score, golden.score);
+        assert_eq!((result.moves, result.ignored_moves), (1, 2));
+        assert_eq!(result.max_tile, 128);
+        assert!(!result.game_over);
+    }
+
+    #[test]
+    fn test_result_packs_board_as_nibbles() {
+        let grid = grid_from_vec(vec![
+            vec![2, 0, 0, 4],
+            vec![0, 0, 0, 0],
+            vec![0, 0, 0, 0],
+            vec![0, 0, 2048, 65536],
+        ]);
+        let result = TilesGame::with_state(4, GOLDEN_SEED, 0, grid).result();
+
+        // 65536 is 2^16, which does not fit a nibble
+        assert_eq!(result.board, U256::from(0xfb00_0000_0000_2001u64));
+        assert_eq!(result.max_tile, 65536);
+    }
+
+    #[test]
+    fn test_play_game_counts_ignored_moves() {
+        // Down soon stops changing the board, but Left and Right would still merge
+        let result = TilesGame::play_game(Rules::default(), 123456789, vec![Move::Down; 200]);
+
+        assert!(!result.game_over);
+        assert_eq!(result.moves + result.ignored_moves, 200);
+        assert!(result.ignored_moves > result.moves);
+        assert_eq!(
+            result.score,
+            TilesGame::play(123456789, vec![Move::Down; 200])
+        );
+    }
+
+    fn board_sum(board: &[Vec<u32>]) -> u32 {
+        board.iter().flatten().sum()
+    }
+
+    #[test]
+    fn test_replay_matches_play() {
+        // Same 7 moves as the validator's test_few_moves
+        let moves = crate::direction::moves_from_bytes(&[0x22, 0x81], 7);
+        let steps = TilesGame::replay(123456789, moves.clone());
+
+        assert_eq!(steps.len(), 7);
+        assert_eq!(
+            steps.iter().map(|step| step.direction).collect::<Vec<_>>(),
+            moves
+        );
+        assert_eq!(steps.last().unwrap().score, 20);
+        assert_eq!(
+            steps.last().unwrap().board,
+            vec![
+                vec![8, 4, 0, 0],
+                vec![4, 2, 0, 0],
+                vec![2, 0, 0, 0],
+                vec![0; 4]
+            ]
+        );
+    }
+
+    #[test]
+    fn test_replay_steps_add_up() {
+        let moves = [Move::Left, Move::Up, Move::Up, Move::Right, Move::Down].repeat(40);
+        for seed in [0u64, 42, 123456789] {
+            let steps = TilesGame::replay(seed, moves.clone());
+            let mut board = TilesGame::new(4, seed).board_rows();
+            let mut score = 0;
+
+            for step in steps {
+                // Merges score their new tile and keep the board total, spawns add to it
+                let merged: u32 = step
+                    .changes
+                    .iter()
+                    .map(|change| match change {
+                        TileChange::Merged { value, .. } => *value,
+                        _ => 0,
+                    })
+                    .sum();
+                let spawned = match step.spawned {
+                    Some(TileChange::Spawned { at, value }) => {
+                        assert_eq!(step.board[at[1]][at[0]], value);
+                        value
+                    }
+                    None => 0,
+                    Some(change) => panic!("not a spawn: {change:?}"),
+                };
+
+                assert_eq!(step.moved, step.spawned.is_some());
+                assert_eq!(step.score, score + merged);
+                assert_eq!(board_sum(&step.board), board_sum(&board) + spawned);
+                if !step.moved {
+                    assert_eq!(step.board, board);
+                }
+                board = step.board;
+                score = step.score;
+            }
+            assert_eq!(score, TilesGame::play(seed, moves.clone()));
+        }
+    }
+
     #[test]
     fn test_game_over_on_full_board() {
         let moves = vec![Move::Down; 200];
@@ -3,39 +3,236 @@
 extern crate alloc;
 extern crate fluentbase_sdk;
 
+use alloc::vec::Vec;
+use alloy_sol_types::{sol, SolError};
 use fluentbase_sdk::{
     basic_entrypoint,
+    codec::Codec,
     derive::{router, Contract},
-    Bytes, SharedAPI, U256,
+    Address, Bytes, ContextReader, ExitCode, SharedAPI, U256,
 };
 
-pub(crate) mod direction;
+#[cfg(any(feature = "bitboard", test))]
+pub mod bitboard;
+#[cfg(feature = "cli")]
+pub mod cli;
+pub mod direction;
 pub(crate) mod game;
 pub(crate) mod grid;
 pub(crate) mod prng;
+pub mod seed;
 
-use game::TilesGame;
+use direction::{Move, MovesError};
+pub use game::GameResult;
+use game::{Rules, TilesGame};
+use prng::SeedVersion;
+
+sol! {
+    error TooManyMoves(uint64 movesLen, uint64 maxMoves);
+    // Malformed `moves`, see `direction::decode_moves`
+    error MovesTooShort(uint256 movesBytes, uint64 movesLen);
+    error TrailingMoveBytes(uint256 movesBytes, uint64 movesLen);
+    error NonZeroMovePadding(uint8 padding);
+    // `getScoreWithConfig` rules outside the allowed values, echoed back field by field
+    error InvalidGameConfig(
+        uint8 size,
+        uint16 fourProbabilityBps,
+        uint8 startTiles,
+        uint32 winTile,
+        uint8 seedVersion
+    );
+}
+
+/// The most moves one call validates. Every move costs fuel, so a longer replay reverts with
+/// `TooManyMoves` up front instead of running out of fuel part way through. Set it at build time
+/// with the `TILES_MAX_MOVES` environment variable; `maxMoves()` returns it. See
+/// `tests/fuel.rs` for what a move costs.
+pub const MAX_MOVES: u64 = match option_env!("TILES_MAX_MOVES") {
+    Some(max_moves) => parse_max_moves(max_moves),
+    None => 10_000,
+};
+
+const fn parse_max_moves(digits: &str) -> u64 {
+    let digits = digits.as_bytes();
+    assert!(!digits.is_empty(), "TILES_MAX_MOVES must be a number");
+    let mut max_moves = 0u64;
+    let mut i = 0;
+    while i < digits.len() {
+        assert!(
+            digits[i].is_ascii_digit(),
+            "TILES_MAX_MOVES must be a number"
+        );
+        max_moves = max_moves * 10 + (digits[i] - b'0') as u64;
+        i += 1;
+    }
+    max_moves
+}
+
+// Revert with ABI-encoded custom error data, so callers can decode the reason
+fn revert<SDK: SharedAPI, T: SolError>(sdk: &mut SDK, error: T) -> ! {
+    sdk.write(&error.abi_encode());
+    sdk.exit(ExitCode::Panic)
+}
 
 #[derive(Contract, Default)]
 pub struct Validator<SDK> {
     sdk: SDK,
 }
 
+/// Rule parameters for `getScoreWithConfig`, the ABI form of [`game::Rules`].
+///
+/// `fourProbabilityBps` is the chance in basis points that a spawned tile is a 4. A
+/// `winTile` of 0 plays on until no moves remain. `seedVersion` picks how the seed is
+/// expanded, see [`prng::SeedVersion`]: 1 as `getScore` does, 2 through SplitMix64.
+#[derive(Codec, Clone, Copy, Debug, Default, PartialEq)]
+pub struct GameConfig {
+    pub size: u8,
+    pub four_probability_bps: u16,
+    pub start_tiles: u8,
+    pub win_tile: u32,
+    pub seed_version: u8,
+}
+
+impl GameConfig {
+    /// The rules this config describes, or `None` if they are not playable.
+    pub(crate) fn rules(&self) -> Option<Rules> {
+        let rules = Rules {
+            size: self.size as usize,
+            four_probability_bps: self.four_probability_bps,
+            start_tiles: self.start_tiles as usize,
+            win_tile: self.win_tile,
+            seed_version: SeedVersion::from_u8(self.seed_version)?,
+        };
+        rules.is_valid().then_some(rules)
+    }
+}
+
 pub trait ValidatorAPI {
-    fn get_score(&self, seed: u64, moves: Bytes, moves_len: u64) -> U256;
+    fn get_score(&mut self, seed: u64, moves: Bytes, moves_len: u64) -> U256;
+    fn get_score_with_config(
+        &mut self,
+        seed: u64,
+        moves: Bytes,
+        moves_len: u64,
+        config: GameConfig,
+    ) -> U256;
+    fn get_game_result(&mut self, seed: u64, moves: Bytes, moves_len: u64) -> GameResult;
+    fn get_player_score(
+        &mut self,
+        player: Address,
+        seed: u64,
+        moves: Bytes,
+        moves_len: u64,
+    ) -> U256;
+    fn max_moves(&self) -> u64;
 }
 
 #[router(mode = "solidity")]
 impl<SDK: SharedAPI> ValidatorAPI for Validator<SDK> {
     #[function_id("getScore(uint64,bytes,uint64)")]
-    fn get_score(&self, seed: u64, moves: Bytes, moves_len: u64) -> U256 {
-        let moves = direction::moves_from_bytes(&moves, moves_len);
-        U256::from(TilesGame::play(seed, moves))
+    fn get_score(&mut self, seed: u64, moves: Bytes, moves_len: u64) -> U256 {
+        let moves = self.decode_moves(&moves, moves_len);
+        U256::from(play_classic(seed, moves).score)
+    }
+
+    #[function_id("getScoreWithConfig(uint64,bytes,uint64,(uint8,uint16,uint8,uint32,uint8))")]
+    fn get_score_with_config(
+        &mut self,
+        seed: u64,
+        moves: Bytes,
+        moves_len: u64,
+        config: GameConfig,
+    ) -> U256 {
+        let Some(rules) = config.rules() else {
+            revert(
+                &mut self.sdk,
+                InvalidGameConfig {
+                    size: config.size,
+                    fourProbabilityBps: config.four_probability_bps,
+                    startTiles: config.start_tiles,
+                    winTile: config.win_tile,
+                    seedVersion: config.seed_version,
+                },
+            );
+        };
+        let moves = self.decode_moves(&moves, moves_len);
+        U256::from(TilesGame::play_with_rules(rules, seed, moves))
+    }
+
+    #[function_id("getGameResult(uint64,bytes,uint64)")]
+    fn get_game_result(&mut self, seed: u64, moves: Bytes, moves_len: u64) -> GameResult {
+        let moves = self.decode_moves(&moves, moves_len);
+        play_classic(seed, moves)
+    }
+
+    // `getScore` of the seed bound to `player` on this chain, see [`seed::player_seed`]
+    #[function_id("getPlayerScore(address,uint64,bytes,uint64)")]
+    fn get_player_score(
+        &mut self,
+        player: Address,
+        seed: u64,
+        moves: Bytes,
+        moves_len: u64,
+    ) -> U256 {
+        let chain_id = self.sdk.context().block_chain_id();
+        let moves = self.decode_moves(&moves, moves_len);
+        U256::from(play_classic(seed::player_seed(player, seed, chain_id), moves).score)
     }
+
+    // The limit this build was compiled with, so callers need not hard-code it
+    #[function_id("maxMoves()")]
+    fn max_moves(&self) -> u64 {
+        MAX_MOVES
+    }
+}
+
+// The default rules, on the bitboard engine when the `bitboard` feature is enabled
+#[cfg(feature = "bitboard")]
+fn play_classic(seed: u64, moves: Vec<Move>) -> GameResult {
+    bitboard::play_or_fallback(seed, moves)
+}
+
+#[cfg(not(feature = "bitboard"))]
+fn play_classic(seed: u64, moves: Vec<Move>) -> GameResult {
+    TilesGame::play_game(Rules::default(), seed, moves)
 }
 
 impl<SDK: SharedAPI> Validator<SDK> {
     pub fn deploy(&self) {}
+
+    // Reverts with `TooManyMoves` past `MAX_MOVES`, before decoding anything, and with one of
+    // the move encoding errors if `moves` is not exactly `moves_len` moves
+    fn decode_moves(&mut self, moves: &[u8], moves_len: u64) -> Vec<Move> {
+        if moves_len > MAX_MOVES {
+            revert(
+                &mut self.sdk,
+                TooManyMoves {
+                    movesLen: moves_len,
+                    maxMoves: MAX_MOVES,
+                },
+            );
+        }
+        match direction::decode_moves(moves, moves_len) {
+            Ok(moves) => moves,
+            Err(MovesError::TooShort { bytes, moves_len }) => revert(
+                &mut self.sdk,
+                MovesTooShort {
+                    movesBytes: U256::from(bytes),
+                    movesLen: moves_len,
+                },
+            ),
+            Err(MovesError::TrailingBytes { bytes, moves_len }) => revert(
+                &mut self.sdk,
+                TrailingMoveBytes {
+                    movesBytes: U256::from(bytes),
+                    movesLen: moves_len,
+                },
+            ),
+            Err(MovesError::NonZeroPadding { padding }) => {
+                revert(&mut self.sdk, NonZeroMovePadding { padding })
+            }
+        }
+    }
 }
 
 basic_entrypoint!(Validator);
@@ -45,32 +242,223 @@ mod tests {
     use super::*;
     use fluentbase_sdk::{address, codec::Encoder, ContractContextV1, U256};
     use fluentbase_testing::HostTestingContext;
+    use std::panic::{catch_unwind, AssertUnwindSafe};
 
     fn call_get_score(seed: u64, moves_hex: &str, moves_len: u64) -> U256 {
-        let caller = address!("1111111111111111111111111111111111111111");
-        let contract_addr = address!("aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa");
-
-        let moves_bytes = if moves_hex.is_empty() {
-            Bytes::new()
-        } else {
-            Bytes::from(hex::decode(moves_hex).unwrap())
-        };
-
-        let call = GetScoreCall::new((seed, moves_bytes, moves_len));
+        let call = GetScoreCall::new((seed, moves_bytes(moves_hex), moves_len));
+        let output = call_validator(call.encode()).unwrap();
+        GetScoreReturn::decode(&&output[..]).unwrap().0 .0
+    }
 
+    // Runs `input` through the router, returning the output or, if the call reverted, the
+    // revert data
+    fn call_validator(input: Vec<u8>) -> Result<Vec<u8>, Vec<u8>> {
         let sdk = HostTestingContext::default()
-            .with_input(call.encode())
+            .with_input(input)
             .with_contract_context(ContractContextV1 {
-                address: contract_addr,
-                caller,
+                address: address!("aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa"),
+                caller: address!("1111111111111111111111111111111111111111"),
                 ..Default::default()
             });
 
-        let mut contract = Validator::new(sdk.clone());
-        contract.main();
+        let mut contract = Validator::new(sdk);
+        let result = catch_unwind(AssertUnwindSafe(|| contract.main()));
+        let output = contract.sdk.take_output();
+        match result {
+            Ok(()) => Ok(output),
+            Err(_) => Err(output),
+        }
+    }
+
+    fn moves_bytes(moves_hex: &str) -> Bytes {
+        Bytes::from(hex::decode(moves_hex).unwrap())
+    }
+
+    fn call_get_score_with_config(
+        seed: u64,
+        moves_hex: &str,
+        moves_len: u64,
+        config: GameConfig,
+    ) -> U256 {
+        let call = GetScoreWithConfigCall::new((seed, moves_bytes(moves_hex), moves_len, config));
+        let output = call_validator(call.encode()).unwrap();
+        GetScoreWithConfigReturn::decode(&&output[..]).unwrap().0 .0
+    }
+
+    fn classic_config() -> GameConfig {
+        GameConfig {
+            size: 4,
+            four_probability_bps: 1_000,
+            start_tiles: 2,
+            win_tile: 0,
+            seed_version: 1,
+        }
+    }
+
+    #[test]
+    fn test_classic_config_matches_get_score() {
+        for seed in [0u64, 42, 123456789, u64::MAX] {
+            assert_eq!(
+                call_get_score_with_config(seed, "2281a46986247100", 29, classic_config()),
+                call_get_score(seed, "2281a46986247100", 29)
+            );
+        }
+    }
+
+    #[test]
+    fn test_other_board_sizes() {
+        // Left, Right, Up, Down, 25 times over
+        let moves = "1b".repeat(25);
+        for size in [3, 5, 6] {
+            let config = GameConfig {
+                size,
+                ..classic_config()
+            };
+            let score = call_get_score_with_config(123456789, &moves, 100, config);
+            assert!(score > U256::ZERO);
+        }
+    }
+
+    #[test]
+    fn test_invalid_config() {
+        let config = GameConfig {
+            size: 12,
+            ..classic_config()
+        };
+        let call = GetScoreWithConfigCall::new((123456789, moves_bytes("2280"), 7, config));
+        let output = call_validator(call.encode()).unwrap_err();
+
+        let error = InvalidGameConfig::abi_decode(&output, true).unwrap();
+        assert_eq!((error.size, error.startTiles), (12, 2));
+    }
+
+    #[test]
+    fn test_seed_versions() {
+        let v2 = GameConfig {
+            seed_version: 2,
+            ..classic_config()
+        };
+        let legacy =
+            call_get_score_with_config(123456789, "2281a46986247100", 29, classic_config());
+        let splitmix = call_get_score_with_config(123456789, "2281a46986247100", 29, v2);
+        assert_eq!((legacy, splitmix), (U256::from(116), U256::from(156)));
+    }
+
+    #[test]
+    fn test_unknown_seed_version() {
+        let config = GameConfig {
+            seed_version: 3,
+            ..classic_config()
+        };
+        let call = GetScoreWithConfigCall::new((123456789, moves_bytes("2280"), 7, config));
+        let output = call_validator(call.encode()).unwrap_err();
+
+        let error = InvalidGameConfig::abi_decode(&output, true).unwrap();
+        assert_eq!(error.seedVersion, 3);
+    }
+
+    fn call_get_game_result(seed: u64, moves_hex: &str, moves_len: u64) -> GameResult {
+        let call = GetGameResultCall::new((seed, moves_bytes(moves_hex), moves_len));
+        let output = call_validator(call.encode()).unwrap();
+        GetGameResultReturn::decode(&&output[..]).unwrap().0 .0
+    }
+
+    #[test]
+    fn test_get_game_result() {
+        let result = call_get_game_result(123456789, "2280", 7);
+
+        // Same 7 moves as test_few_moves
+        assert_eq!(result.score, 20);
+        assert_eq!((result.moves, result.ignored_moves), (7, 0));
+        assert_eq!(result.max_tile, 8);
+        assert!(!result.game_over);
+        // Rows 8 4 . . / 4 2 . . / 2 . . . / . . . .
+        assert_eq!(result.board, U256::from(0x1_0012_0023u64));
+    }
+
+    fn call_get_player_score(player: Address, seed: u64, moves_hex: &str, moves_len: u64) -> U256 {
+        let call = GetPlayerScoreCall::new((player, seed, moves_bytes(moves_hex), moves_len));
+        let output = call_validator(call.encode()).unwrap();
+        GetPlayerScoreReturn::decode(&&output[..]).unwrap().0 .0
+    }
 
-        let result = GetScoreReturn::decode(&&contract.sdk.take_output()[..]).unwrap();
-        result.0 .0
+    // The 175-move replay from the Foundry tests
+    const PROVIDED_MOVES: &str =
+        "aaaaaaaaaf34aaaaaaaf7450007072fd284850a24922469502823ccc171757372503d113407101d4c344fffc";
+
+    #[test]
+    fn test_player_score_depends_on_player() {
+        let player = address!("3333333333333333333333333333333333333333");
+        let thief = address!("4444444444444444444444444444444444444444");
+        let seed = 14898733573372438788;
+
+        let score = call_get_player_score(player, seed, PROVIDED_MOVES, 175);
+        let stolen = call_get_player_score(thief, seed, PROVIDED_MOVES, 175);
+        assert_ne!(score, stolen);
+
+        // Verify: Clients get the same board from the host-side helper
+        let chain_id = HostTestingContext::default().context().block_chain_id();
+        let bound_seed = seed::player_seed(player, seed, chain_id);
+        assert_eq!(score, call_get_score(bound_seed, PROVIDED_MOVES, 175));
+    }
+
+    #[test]
+    fn test_max_moves() {
+        // All Left, as many moves as the limit allows and then one more
+        let moves = |moves_len: u64| Bytes::from(vec![0u8; moves_len.div_ceil(4) as usize]);
+        let call = GetScoreCall::new((123456789, moves(MAX_MOVES), MAX_MOVES));
+        assert!(call_validator(call.encode()).is_ok());
+
+        let call = GetScoreCall::new((123456789, moves(MAX_MOVES + 1), MAX_MOVES + 1));
+        let output = call_validator(call.encode()).unwrap_err();
+        let error = TooManyMoves::abi_decode(&output, true).unwrap();
+        assert_eq!((error.movesLen, error.maxMoves), (MAX_MOVES + 1, MAX_MOVES));
+    }
+
+    #[test]
+    fn test_max_moves_entrypoint() {
+        let output = call_validator(MaxMovesCall::new(()).encode()).unwrap();
+        let result = MaxMovesReturn::decode(&&output[..]).unwrap();
+        assert_eq!(result.0 .0, MAX_MOVES);
+    }
+
+    #[test]
+    fn test_max_moves_applies_to_every_replay() {
+        let moves = moves_bytes("2280");
+        let calls = [
+            GetGameResultCall::new((123456789, moves.clone(), u64::MAX)).encode(),
+            GetScoreWithConfigCall::new((123456789, moves.clone(), u64::MAX, classic_config()))
+                .encode(),
+            GetPlayerScoreCall::new((Address::ZERO, 123456789, moves, u64::MAX)).encode(),
+        ];
+        for call in calls {
+            let output = call_validator(call).unwrap_err();
+            assert!(TooManyMoves::abi_decode(&output, true).is_ok());
+        }
+    }
+
+    #[test]
+    fn test_malformed_moves() {
+        let revert = |moves_hex: &str, moves_len: u64| {
+            let call = GetScoreCall::new((123456789, moves_bytes(moves_hex), moves_len));
+            call_validator(call.encode()).unwrap_err()
+        };
+
+        let error = MovesTooShort::abi_decode(&revert("22", 7), true).unwrap();
+        assert_eq!((error.movesBytes, error.movesLen), (U256::from(1), 7));
+
+        let error = TrailingMoveBytes::abi_decode(&revert("228000", 7), true).unwrap();
+        assert_eq!((error.movesBytes, error.movesLen), (U256::from(3), 7));
+
+        // Same 7 moves as test_few_moves, with the unused 8th set to Right
+        let error = NonZeroMovePadding::abi_decode(&revert("2281", 7), true).unwrap();
+        assert_eq!(error.padding, 0x01);
+    }
+
+    #[test]
+    fn test_max_moves_from_env() {
+        assert_eq!(parse_max_moves("20000"), 20_000);
+        assert_eq!(parse_max_moves("0"), 0);
     }
 
     #[test]
@@ -91,7 +479,7 @@ mod tests {
     fn test_few_moves() {
         // 7 moves: Left, Up, Left, Up, Up, Left, Left
         // From game::tests::test_simple_get_score — expected score 20
-        let score = call_get_score(123456789, "2281", 7);
+        let score = call_get_score(123456789, "2280", 7);
         assert_eq!(score, U256::from(20));
     }
 
@@ -110,7 +498,7 @@ mod tests {
         // Seeds that might produce random() == 1.0
         // Testing the clamp fix
         for seed in [0u64, u64::MAX, u64::MAX / 2] {
-            let score = call_get_score(seed, "2281", 7);
+            let score = call_get_score(seed, "2280", 7);
             assert!(score <= U256::from(u32::MAX));
         }
     }
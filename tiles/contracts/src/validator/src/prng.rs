@@ -1,6 +1,29 @@
 use core::num::Wrapping as w;
 
-#[derive(Debug)]
+/// How a seed becomes the generator state. The same seed plays a different board under each
+/// version, so the version is part of what a score means.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SeedVersion {
+    /// `seed << 1` and `seed >> 1`, as the frontend seeds its game. Seed 0 gives an all-zero
+    /// state that only ever returns 0, and nearby seeds give nearly the same state.
+    #[default]
+    V1,
+    /// The first two outputs of [`SplitMix64`] seeded with `seed`. The state is never all
+    /// zero, and every bit of the seed affects all of it.
+    V2,
+}
+
+impl SeedVersion {
+    pub fn from_u8(version: u8) -> Option<Self> {
+        match version {
+            1 => Some(SeedVersion::V1),
+            2 => Some(SeedVersion::V2),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
 pub struct Xorshift128Plus {
     s0: w<u64>,
     s1: w<u64>,
@@ -8,11 +31,28 @@ pub struct Xorshift128Plus {
 
 impl Xorshift128Plus {
     pub fn new(seed: u64) -> Self {
-        let seed1 = w(seed.wrapping_shl(1));
-        let seed2 = w(seed.wrapping_shr(1));
-        Xorshift128Plus {
-            s0: seed1,
-            s1: seed2,
+        Self::with_version(seed, SeedVersion::V1)
+    }
+
+    pub fn with_version(seed: u64, version: SeedVersion) -> Self {
+        match version {
+            SeedVersion::V1 => {
+                let seed1 = w(seed.wrapping_shl(1));
+                let seed2 = w(seed.wrapping_shr(1));
+                Xorshift128Plus {
+                    s0: seed1,
+                    s1: seed2,
+                }
+            }
+            SeedVersion::V2 => {
+                // SplitMix64 is a bijection of its state, so two outputs in a row are never
+                // both zero
+                let mut splitmix = SplitMix64::new(seed);
+                Xorshift128Plus {
+                    s0: w(splitmix.next()),
+                    s1: w(splitmix.next()),
+                }
+            }
         }
     }
 
@@ -27,3 +67,89 @@ impl Xorshift128Plus {
         (self.s1 + s0).0
     }
 }
+
+/// Sebastiano Vigna's SplitMix64, used to expand a seed into a [`Xorshift128Plus`] state.
+#[derive(Debug)]
+pub struct SplitMix64 {
+    state: w<u64>,
+}
+
+impl SplitMix64 {
+    pub fn new(seed: u64) -> Self {
+        SplitMix64 { state: w(seed) }
+    }
+
+    pub fn next(&mut self) -> u64 {
+        self.state += w(0x9e3779b97f4a7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)) * w(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)) * w(0x94d049bb133111eb);
+        (z ^ (z >> 31)).0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn outputs(seed: u64, version: SeedVersion) -> [u64; 3] {
+        let mut rng = Xorshift128Plus::with_version(seed, version);
+        [rng.next(), rng.next(), rng.next()]
+    }
+
+    #[test]
+    fn test_splitmix64_reference_vector() {
+        // Outputs of the reference implementation for seed 1234567
+        let mut splitmix = SplitMix64::new(1234567);
+        let expected = [
+            6457827717110365317,
+            3203168211198807973,
+            9817491932198370423,
+            4593380528125082431,
+            16408922859458223821,
+        ];
+        for value in expected {
+            assert_eq!(splitmix.next(), value);
+        }
+    }
+
+    #[test]
+    fn test_v1_known_answers() {
+        assert_eq!(outputs(0, SeedVersion::V1), [0, 0, 0]);
+        assert_eq!(
+            outputs(1, SeedVersion::V1),
+            [0x1000082, 0x2000104, 0x800002002082]
+        );
+        assert_eq!(
+            outputs(123456789, SeedVersion::V1),
+            [0x75bceb9aad805, 0xde90bcffb3885, 0xe761e862dcf94413]
+        );
+
+        let mut rng = Xorshift128Plus::new(123456789);
+        assert_eq!(rng.next(), 0x75bceb9aad805);
+    }
+
+    #[test]
+    fn test_v2_known_answers() {
+        assert_eq!(
+            outputs(0, SeedVersion::V2),
+            [0xff5e664aa2264ab1, 0x5cb3706844353952, 0x76f611e25a5011e3]
+        );
+        assert_eq!(
+            outputs(1, SeedVersion::V2),
+            [0x9890a08cc61d98a5, 0x91a182125b3dfdc4, 0xd3e5dbe3cb93dd47]
+        );
+        assert_eq!(
+            outputs(123456789, SeedVersion::V2),
+            [0xaec5584e164ecd1a, 0xf6f1e0c729614979, 0x2f218d270ff0efe9]
+        );
+    }
+
+    #[test]
+    fn test_seed_versions_from_abi() {
+        assert_eq!(SeedVersion::from_u8(1), Some(SeedVersion::V1));
+        assert_eq!(SeedVersion::from_u8(2), Some(SeedVersion::V2));
+        assert_eq!(SeedVersion::from_u8(0), None);
+        assert_eq!(SeedVersion::from_u8(3), None);
+    }
+}
@@ -0,0 +1,56 @@
+//! Player-bound seeds.
+//!
+//! `getPlayerScore` does not play the submitted seed itself but [`player_seed`] of it, so a
+//! `(seed, moves)` replay copied from the mempool plays a different board for anyone but the
+//! player who submitted it. Clients call the same function to generate the board they play.
+
+use fluentbase_sdk::{keccak256, Address};
+
+/// The seed a player's game is played with: the low 64 bits of
+/// `keccak256(abi.encode(player, seed, chainId))`.
+///
+/// In Solidity: `uint64(uint256(keccak256(abi.encode(player, seed, block.chainid))))`.
+pub fn player_seed(player: Address, seed: u64, chain_id: u64) -> u64 {
+    let mut input = [0u8; 96];
+    input[12..32].copy_from_slice(player.as_slice());
+    input[56..64].copy_from_slice(&seed.to_be_bytes());
+    input[88..96].copy_from_slice(&chain_id.to_be_bytes());
+
+    let hash = keccak256(input);
+    u64::from_be_bytes(hash[24..32].try_into().unwrap())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_sol_types::SolValue;
+    use fluentbase_sdk::{address, U256};
+
+    const PLAYER: Address = address!("1111111111111111111111111111111111111111");
+    const OTHER: Address = address!("2222222222222222222222222222222222222222");
+
+    #[test]
+    fn test_known_answers() {
+        assert_eq!(player_seed(PLAYER, 123456789, 1), 0x7fe5ce57576e01b8);
+        assert_eq!(player_seed(PLAYER, 123456789, 20994), 0xa7fe3b5e0cf47400);
+        assert_eq!(player_seed(PLAYER, 0, 1), 0x37a0bec3ca166d23);
+    }
+
+    #[test]
+    fn test_matches_abi_encode() {
+        for (seed, chain_id) in [(0u64, 1u64), (123456789, 20994), (u64::MAX, u64::MAX)] {
+            let hash = keccak256((PLAYER, seed, U256::from(chain_id)).abi_encode());
+            let expected = U256::from_be_bytes(hash.0).as_limbs()[0];
+            assert_eq!(player_seed(PLAYER, seed, chain_id), expected);
+        }
+    }
+
+    #[test]
+    fn test_every_input_changes_the_seed() {
+        let seed = player_seed(PLAYER, 123456789, 1);
+
+        assert_ne!(player_seed(OTHER, 123456789, 1), seed);
+        assert_ne!(player_seed(PLAYER, 123456788, 1), seed);
+        assert_ne!(player_seed(PLAYER, 123456789, 2), seed);
+    }
+}
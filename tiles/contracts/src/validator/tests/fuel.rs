@@ -0,0 +1,135 @@
+//! Fuel `getScore` costs per move, measured on the compiled validator in the Fluent runtime.
+//!
+//! The harness builds the validator for `wasm32-unknown-unknown` with `TILES_MAX_MOVES=20000`,
+//! so the 20,000-move games run by default, deploys it into an [`EvmTestingContext`] and calls
+//! `getScore` on synthetic games of increasing length:
+//!
+//! ```text
+//! cargo test --release --test fuel -- --nocapture
+//! ```
+//!
+//! The runtime meters the validator in fuel and charges the caller `fuel / FUEL_DENOM_RATE` gas,
+//! so the fuel of a call is its gas times `FUEL_DENOM_RATE`, exact to one gas. A call without
+//! moves is the baseline, which takes the transaction overhead out of the per-move numbers.
+
+use alloy_sol_types::{sol, SolCall};
+use fluentbase_sdk::{address, keccak256, Address, Bytes, FUEL_DENOM_RATE, U256};
+use fluentbase_testing::EvmTestingContext;
+use std::{path::PathBuf, process::Command};
+
+sol! {
+    function getScore(uint64 seed, bytes moves, uint64 movesLen) external returns (uint256);
+    function maxMoves() external view returns (uint64);
+}
+
+const BENCH_MAX_MOVES: u64 = 20_000;
+const LENGTHS: [u64; 6] = [100, 1_000, 2_500, 5_000, 10_000, BENCH_MAX_MOVES];
+const KNOWN_SEED: u64 = 123456789;
+
+const DEPLOYER: Address = address!("d0d0d0d0d0d0d0d0d0d0d0d0d0d0d0d0d0d0d0d0");
+const CALLER: Address = address!("cacacacacacacacacacacacacacacacacacacaca");
+
+// Builds the contract with the benchmark's move limit, in its own target directory so it
+// neither waits on nor replaces the build running this test
+fn build_wasm() -> Vec<u8> {
+    let manifest_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let target_dir = manifest_dir.join("target").join("fuel");
+    let status = Command::new(env!("CARGO"))
+        .current_dir(&manifest_dir)
+        .env("TILES_MAX_MOVES", BENCH_MAX_MOVES.to_string())
+        .args(["build", "--release", "--no-default-features"])
+        .args(["--target", "wasm32-unknown-unknown"])
+        .arg("--target-dir")
+        .arg(&target_dir)
+        .status()
+        .expect("failed to run cargo");
+    assert!(status.success(), "building validator.wasm failed");
+
+    let wasm = target_dir.join("wasm32-unknown-unknown/release/validator.wasm");
+    std::fs::read(&wasm).unwrap_or_else(|error| panic!("{}: {error}", wasm.display()))
+}
+
+struct Bench {
+    ctx: EvmTestingContext,
+    validator: Address,
+}
+
+impl Bench {
+    fn new() -> Self {
+        let mut ctx = EvmTestingContext::default();
+        let validator = ctx.deploy_evm_tx(DEPLOYER, build_wasm().into());
+        Bench { ctx, validator }
+    }
+
+    // Fuel of one `getScore` call
+    fn get_score(&mut self, moves: &[u8], moves_len: u64) -> u64 {
+        let input = getScoreCall {
+            seed: KNOWN_SEED,
+            moves: moves.to_vec().into(),
+            movesLen: moves_len,
+        }
+        .abi_encode();
+        let result = self
+            .ctx
+            .call_evm_tx(CALLER, self.validator, Bytes::from(input), None, None);
+        assert!(
+            result.is_success(),
+            "getScore of {moves_len} moves failed: {result:?}"
+        );
+        result.gas_used() * FUEL_DENOM_RATE
+    }
+
+    fn report(&mut self, name: &str, moves_of: fn(u64) -> Vec<u8>) {
+        let baseline = self.get_score(&[], 0);
+        println!("{name}");
+        for moves_len in LENGTHS {
+            let fuel = self.get_score(&moves_of(moves_len), moves_len);
+            println!(
+                "  {moves_len} moves: {} fuel per move",
+                (fuel - baseline) / moves_len
+            );
+        }
+    }
+}
+
+// `moves_len` moves, the 4 moves in `pattern` over and over
+fn repeat(pattern: u8, moves_len: u64) -> Vec<u8> {
+    vec![pattern; moves_len.div_ceil(4) as usize]
+}
+
+fn cycled_moves(moves_len: u64) -> Vec<u8> {
+    repeat(0x1b, moves_len)
+}
+
+fn stalled_moves(moves_len: u64) -> Vec<u8> {
+    repeat(0x00, moves_len)
+}
+
+// The same bytes as `_randomMoves` in `test/Validator/Fuel.t.sol`
+fn random_moves(moves_len: u64) -> Vec<u8> {
+    (0..moves_len.div_ceil(4))
+        .map(|i| keccak256(U256::from(i).to_be_bytes::<32>())[0])
+        .collect()
+}
+
+#[test]
+fn test_fuel_per_move() {
+    let mut bench = Bench::new();
+
+    let output = bench.ctx.call_evm_tx(
+        CALLER,
+        bench.validator,
+        Bytes::from(maxMovesCall {}.abi_encode()),
+        None,
+        None,
+    );
+    let max_moves = maxMovesCall::abi_decode_returns(&output.output().cloned().unwrap(), true)
+        .unwrap()
+        ._0;
+    assert_eq!(max_moves, BENCH_MAX_MOVES);
+
+    bench.report("Left, Up, Right, Down cycled", cycled_moves);
+    bench.report("random moves", random_moves);
+    // Left only stops moving tiles after a few moves, so nearly every move is ignored
+    bench.report("Left only", stalled_moves);
+}
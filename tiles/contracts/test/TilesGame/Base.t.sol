@@ -8,6 +8,9 @@ import {IValidator} from "../../out/validator.wasm/interface.sol";
 
 interface IValidatorHarness {
     function getScore(uint64 seed, bytes calldata moves, uint64 movesLen) external returns (uint256);
+    function getPlayerScore(address player, uint64 seed, bytes calldata moves, uint64 movesLen)
+        external
+        returns (uint256);
 }
 
 abstract contract BaseTilesGameTest is Test {
@@ -52,4 +55,9 @@ abstract contract BaseTilesGameTest is Test {
     function _validatorScore(uint64 seed, bytes memory moves, uint64 movesLen) internal returns (uint256) {
         return IValidatorHarness(validator).getScore(seed, moves, movesLen);
     }
+
+    // What `playGame` pays `who` for: the score of the board bound to them
+    function _playerScore(address who, uint64 seed, bytes memory moves, uint64 movesLen) internal returns (uint256) {
+        return IValidatorHarness(validator).getPlayerScore(who, seed, moves, movesLen);
+    }
 }
@@ -25,7 +25,7 @@ contract TilesGameInitializationTest is BaseTilesGameTest {
     }
 
     function test_constructor_initialUsedSeedIsFalse() public view {
-        assertFalse(game.usedSeeds(KNOWN_SEED));
-        assertFalse(game.usedSeeds(PROVIDED_SEED));
+        assertFalse(game.usedSeeds(player, KNOWN_SEED));
+        assertFalse(game.usedSeeds(player, PROVIDED_SEED));
     }
 }
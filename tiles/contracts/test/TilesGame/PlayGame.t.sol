@@ -15,7 +15,8 @@ contract TilesGamePlayGameTest is BaseTilesGameTest {
     );
 
     function test_playGame_rewardsPlayerWithRealValidatorScore() public {
-        uint256 score = _validatorScore(
+        uint256 score = _playerScore(
+            player,
             KNOWN_SEED,
             KNOWN_MOVES,
             KNOWN_NUM_MOVES
@@ -33,21 +34,22 @@ contract TilesGamePlayGameTest is BaseTilesGameTest {
         game.playGame(KNOWN_SEED, KNOWN_MOVES, KNOWN_NUM_MOVES);
 
         assertEq(token.balanceOf(player), expectedPayout);
-        assertTrue(game.usedSeeds(KNOWN_SEED));
+        assertTrue(game.usedSeeds(player, KNOWN_SEED));
     }
 
     function test_playGame_capsRewardByMaxScore() public {
-        uint256 lowCap = 10;
-        TilesGame cappedGame = _deployGame(lowCap, DEFAULT_DECIMALS);
-        token.mint(address(cappedGame), 1_000_000 ether);
-
-        uint256 score = _validatorScore(
+        uint256 score = _playerScore(
+            player,
             KNOWN_SEED,
             KNOWN_MOVES,
             KNOWN_NUM_MOVES
         );
+        uint256 lowCap = score / 2;
         assertGt(score, lowCap, "fixture must exceed cap for this test");
 
+        TilesGame cappedGame = _deployGame(lowCap, DEFAULT_DECIMALS);
+        token.mint(address(cappedGame), 1_000_000 ether);
+
         vm.prank(player);
         cappedGame.playGame(KNOWN_SEED, KNOWN_MOVES, KNOWN_NUM_MOVES);
 
@@ -70,20 +72,19 @@ contract TilesGamePlayGameTest is BaseTilesGameTest {
     }
 
     function test_playGame_revertsWhenScoreIsZero() public {
-        bytes memory nonEmptyMoves = hex"01";
-        uint64 zeroScoreNumMoves = 0;
+        // A single Left, which merges nothing unless both starting tiles pair up in a row
+        bytes memory nonEmptyMoves = hex"00";
+        uint64 zeroScoreNumMoves = 1;
 
-        uint256 score = _validatorScore(
-            KNOWN_SEED,
-            nonEmptyMoves,
-            zeroScoreNumMoves
-        );
-        assertEq(score, 0, "fixture should produce zero score");
+        uint64 seed = KNOWN_SEED;
+        while (_playerScore(player, seed, nonEmptyMoves, zeroScoreNumMoves) != 0) {
+            seed++;
+        }
 
         vm.prank(player);
         vm.expectRevert(bytes("Invalid score"));
-        game.playGame(KNOWN_SEED, nonEmptyMoves, zeroScoreNumMoves);
-        assertFalse(game.usedSeeds(KNOWN_SEED));
+        game.playGame(seed, nonEmptyMoves, zeroScoreNumMoves);
+        assertFalse(game.usedSeeds(player, seed));
     }
 
     function test_playGame_revertsWhenGameHasInsufficientTokenBalance() public {
@@ -96,7 +97,8 @@ contract TilesGamePlayGameTest is BaseTilesGameTest {
     }
 
     function test_playGame_reproducesProvidedInputTuple() public {
-        uint256 score = _validatorScore(
+        uint256 score = _playerScore(
+            player,
             PROVIDED_SEED,
             PROVIDED_MOVES,
             PROVIDED_NUM_MOVES
@@ -106,7 +108,7 @@ contract TilesGamePlayGameTest is BaseTilesGameTest {
             vm.prank(player);
             vm.expectRevert(bytes("Invalid score"));
             game.playGame(PROVIDED_SEED, PROVIDED_MOVES, PROVIDED_NUM_MOVES);
-            assertFalse(game.usedSeeds(PROVIDED_SEED));
+            assertFalse(game.usedSeeds(player, PROVIDED_SEED));
             return;
         }
 
@@ -121,7 +123,48 @@ contract TilesGamePlayGameTest is BaseTilesGameTest {
         game.playGame(PROVIDED_SEED, PROVIDED_MOVES, PROVIDED_NUM_MOVES);
 
         assertEq(token.balanceOf(player), balanceBefore + expectedPayout);
-        assertTrue(game.usedSeeds(PROVIDED_SEED));
+        assertTrue(game.usedSeeds(player, PROVIDED_SEED));
+    }
+
+    function test_playGame_copiedReplayScoresByTheCopiersBoard() public {
+        uint256 score = _playerScore(
+            player,
+            PROVIDED_SEED,
+            PROVIDED_MOVES,
+            PROVIDED_NUM_MOVES
+        );
+        uint256 copiedScore = _playerScore(
+            other,
+            PROVIDED_SEED,
+            PROVIDED_MOVES,
+            PROVIDED_NUM_MOVES
+        );
+        assertGt(score, 0, "provided replay should score for its player");
+        assertNotEq(
+            copiedScore,
+            score,
+            "a copied replay plays another board"
+        );
+
+        // Execute: `other` front-runs with the copied replay
+        vm.prank(other);
+        if (copiedScore == 0) {
+            vm.expectRevert(bytes("Invalid score"));
+        }
+        game.playGame(PROVIDED_SEED, PROVIDED_MOVES, PROVIDED_NUM_MOVES);
+
+        // Verify: The copy paid for its own board, and left the seed to the player
+        assertEq(token.balanceOf(other), _payout(copiedScore));
+        assertFalse(game.usedSeeds(player, PROVIDED_SEED));
+
+        vm.prank(player);
+        game.playGame(PROVIDED_SEED, PROVIDED_MOVES, PROVIDED_NUM_MOVES);
+        assertEq(token.balanceOf(player), _payout(score));
+    }
+
+    function _payout(uint256 score) internal view returns (uint256) {
+        uint256 appliedScore = score > game.maxScore() ? game.maxScore() : score;
+        return appliedScore * (10 ** uint256(game.tokenDecimals()));
     }
 
     function test_playGameSelectorFailsWhenSentToValidatorAddress() public {
@@ -0,0 +1,62 @@
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.24;
+
+import {TilesGame} from "../../src/TilesGame.sol";
+import {BaseTilesGameTest} from "./Base.t.sol";
+
+/// @notice Payout caps on a realistic high-scoring game, generated with
+/// `cd solver && cargo run --release -- 2 --player <player> --chain-id 31337`, where `<player>`
+/// is `makeAddr("player")`, into `test/fixtures/solved-seed-2.json`.
+/// @dev The fixture is solved for the board `player` gets for seed 2 on the fixture's chain.
+contract TilesGameSolvedGamesTest is BaseTilesGameTest {
+    string internal constant FIXTURE = "test/fixtures/solved-seed-2.json";
+
+    uint64 internal seed;
+    bytes internal moves;
+    uint64 internal movesLen;
+    uint256 internal expectedScore;
+
+    function setUp() public override {
+        super.setUp();
+        string memory json = vm.readFile(FIXTURE);
+        seed = uint64(vm.parseJsonUint(json, ".seed"));
+        assertEq(vm.parseJsonAddress(json, ".player"), player, "fixture is solved for another player");
+        vm.chainId(vm.parseJsonUint(json, ".chainId"));
+        moves = vm.parseJsonBytes(json, ".moves");
+        movesLen = uint64(vm.parseJsonUint(json, ".movesLen"));
+        expectedScore = vm.parseJsonUint(json, ".score");
+    }
+
+    function test_solvedGame_scoresAsFixture() public {
+        assertEq(_playerScore(player, seed, moves, movesLen), expectedScore);
+        assertGt(expectedScore, DEFAULT_MAX_SCORE, "fixture must exceed the default cap");
+    }
+
+    function test_playGame_capsSolvedGameAtMaxScore() public {
+        vm.prank(player);
+        game.playGame(seed, moves, movesLen);
+
+        assertEq(token.balanceOf(player), DEFAULT_MAX_SCORE * 10 ** uint256(DEFAULT_DECIMALS));
+    }
+
+    function test_playGame_paysSolvedGameInFullUnderHighCap() public {
+        TilesGame uncapped = _deployGame(expectedScore * 2, DEFAULT_DECIMALS);
+        token.mint(address(uncapped), expectedScore * 10 ** uint256(DEFAULT_DECIMALS));
+
+        vm.prank(player);
+        uncapped.playGame(seed, moves, movesLen);
+
+        assertEq(token.balanceOf(player), expectedScore * 10 ** uint256(DEFAULT_DECIMALS));
+        assertEq(token.balanceOf(address(uncapped)), 0);
+    }
+
+    function test_solvedGame_onlyFitsItsSeed() public {
+        uint256 otherScore = _playerScore(player, seed + 1, moves, movesLen);
+        assertLt(otherScore, expectedScore, "the moves only fit the board of their seed");
+    }
+
+    function test_solvedGame_onlyFitsItsPlayer() public {
+        uint256 copierScore = _playerScore(other, seed, moves, movesLen);
+        assertLt(copierScore, expectedScore, "the moves only fit the board of their player");
+    }
+}
@@ -0,0 +1,121 @@
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.24;
+
+import {console} from "forge-std/console.sol";
+import {BaseTilesGameTest} from "../TilesGame/Base.t.sol";
+
+struct GameResult {
+    uint32 score;
+    uint32 maxTile;
+    uint32 moves;
+    uint32 ignoredMoves;
+    bool gameOver;
+    uint256 board;
+}
+
+interface IValidatorResults {
+    function getGameResult(uint64 seed, bytes calldata moves, uint64 movesLen) external returns (GameResult memory);
+    function maxMoves() external view returns (uint64);
+}
+
+/// @notice Gas `getScore` costs on synthetic games of increasing length.
+/// @dev `gblend test --match-contract ValidatorGasTest -vv` prints the table. The numbers are gas
+/// as the caller is charged, not the validator's fuel, which `src/validator/tests/fuel.rs`
+/// measures. Games longer than the validator's `maxMoves()` are skipped, so build with
+/// `TILES_MAX_MOVES=20000` to measure 20,000 moves here.
+contract ValidatorGasTest is BaseTilesGameTest {
+    error TooManyMoves(uint64 movesLen, uint64 maxMoves);
+
+    uint64 internal constant LONG_GAME = 20_000;
+
+    // `MAX_MOVES` of the compiled validator
+    uint64 internal maxMoves;
+
+    function setUp() public override {
+        super.setUp();
+        maxMoves = IValidatorResults(validator).maxMoves();
+    }
+
+    function test_gas_cycledMoves() public {
+        _report("Left, Up, Right, Down cycled", _cycledMoves);
+    }
+
+    function test_gas_randomMoves() public {
+        _report("random moves", _randomMoves);
+    }
+
+    function test_gas_stalledMoves() public {
+        // Left only stops moving tiles after a few moves, so nearly every move is ignored
+        _report("Left only", _stalledMoves);
+    }
+
+    function test_getScore_acceptsMaxMoves() public {
+        bytes memory moves = _repeat(hex"00", maxMoves);
+        _validatorScore(KNOWN_SEED, moves, maxMoves);
+    }
+
+    function test_getScore_revertsPastMaxMoves() public {
+        bytes memory moves = _repeat(hex"00", maxMoves + 1);
+        vm.expectRevert(abi.encodeWithSelector(TooManyMoves.selector, maxMoves + 1, maxMoves));
+        _validatorScore(KNOWN_SEED, moves, maxMoves + 1);
+    }
+
+    function test_playGame_bubblesTooManyMoves() public {
+        uint64 movesLen = maxMoves + 1;
+        bytes memory moves = _repeat(hex"1b", movesLen);
+        vm.prank(player);
+        vm.expectRevert(abi.encodeWithSelector(TooManyMoves.selector, movesLen, maxMoves));
+        game.playGame(KNOWN_SEED, moves, movesLen);
+    }
+
+    function _report(string memory name, function(uint64) pure returns (bytes memory) movesOf) internal {
+        uint64[6] memory lengths = [uint64(100), 1_000, 2_500, 5_000, 10_000, LONG_GAME];
+        console.log(name);
+        for (uint256 i = 0; i < lengths.length; i++) {
+            if (lengths[i] > maxMoves) {
+                console.log("  %d moves: over maxMoves (%d), skipped", uint256(lengths[i]), uint256(maxMoves));
+                continue;
+            }
+            _measure(movesOf(lengths[i]), lengths[i]);
+        }
+    }
+
+    function _measure(bytes memory moves, uint64 movesLen) internal {
+        // Also warms the validator account, so the measured call pays no cold access
+        GameResult memory result = IValidatorResults(validator).getGameResult(KNOWN_SEED, moves, movesLen);
+
+        uint256 gasBefore = gasleft();
+        _validatorScore(KNOWN_SEED, moves, movesLen);
+        uint256 gasUsed = gasBefore - gasleft();
+
+        console.log(
+            "  %d moves, %d played before the game ended, %d gas per move",
+            uint256(movesLen),
+            uint256(result.moves) + result.ignoredMoves,
+            gasUsed / movesLen
+        );
+    }
+
+    // `movesLen` moves, the 4 moves in `pattern` over and over
+    function _repeat(bytes1 pattern, uint64 movesLen) internal pure returns (bytes memory moves) {
+        moves = new bytes((movesLen + 3) / 4);
+        for (uint256 i = 0; i < moves.length; i++) {
+            moves[i] = pattern;
+        }
+    }
+
+    function _cycledMoves(uint64 movesLen) internal pure returns (bytes memory) {
+        return _repeat(hex"1b", movesLen);
+    }
+
+    function _stalledMoves(uint64 movesLen) internal pure returns (bytes memory) {
+        return _repeat(hex"00", movesLen);
+    }
+
+    function _randomMoves(uint64 movesLen) internal pure returns (bytes memory moves) {
+        moves = new bytes((movesLen + 3) / 4);
+        for (uint256 i = 0; i < moves.length; i++) {
+            moves[i] = keccak256(abi.encode(i))[0];
+        }
+    }
+}
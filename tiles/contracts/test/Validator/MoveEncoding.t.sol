@@ -0,0 +1,37 @@
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.24;
+
+import {BaseTilesGameTest} from "../TilesGame/Base.t.sol";
+
+/// @notice The validator only scores the shortest encoding of `movesLen` moves.
+contract ValidatorMoveEncodingTest is BaseTilesGameTest {
+    error MovesTooShort(uint256 movesBytes, uint64 movesLen);
+    error TrailingMoveBytes(uint256 movesBytes, uint64 movesLen);
+    error NonZeroMovePadding(uint8 padding);
+
+    function test_getScore_acceptsShortestEncoding() public {
+        assertEq(_validatorScore(KNOWN_SEED, hex"2280", 7), 20);
+    }
+
+    function test_getScore_revertsWhenMovesTooShort() public {
+        vm.expectRevert(abi.encodeWithSelector(MovesTooShort.selector, uint256(1), uint64(7)));
+        _validatorScore(KNOWN_SEED, hex"22", 7);
+    }
+
+    function test_getScore_revertsOnTrailingBytes() public {
+        vm.expectRevert(abi.encodeWithSelector(TrailingMoveBytes.selector, uint256(3), uint64(7)));
+        _validatorScore(KNOWN_SEED, hex"228000", 7);
+    }
+
+    function test_getScore_revertsOnNonZeroPadding() public {
+        vm.expectRevert(abi.encodeWithSelector(NonZeroMovePadding.selector, uint8(0x01)));
+        _validatorScore(KNOWN_SEED, hex"2281", 7);
+    }
+
+    function test_playGame_revertsOnMalformedMoves() public {
+        vm.prank(player);
+        vm.expectRevert(abi.encodeWithSelector(TrailingMoveBytes.selector, uint256(8), uint64(28)));
+        game.playGame(KNOWN_SEED, KNOWN_MOVES, 28);
+        assertFalse(game.usedSeeds(player, KNOWN_SEED));
+    }
+}
@@ -8,6 +8,8 @@ import {
 import type { PropsWithChildren } from "react"
 
 import { isNil, throttle } from "lodash"
+import { zeroAddress } from "viem"
+import { useAccount } from "wagmi"
 
 import gameReducer, {
   initState,
@@ -49,7 +51,10 @@ export const GameContext = createContext<ContextType>({
 })
 
 export default function GameProvider({ children }: PropsWithChildren) {
-  const [gameState, dispatch] = useReducer(gameReducer, initState())
+  const { address } = useAccount()
+  // Boards are bound to the player, see `playerSeed`; before a wallet connects, to nobody
+  const player = address ?? zeroAddress
+  const [gameState, dispatch] = useReducer(gameReducer, player, initState)
 
   const getEmptyCells = () => {
     const results: [number, number][] = []
@@ -103,12 +108,18 @@ export default function GameProvider({ children }: PropsWithChildren) {
   )
 
   const startGame = useCallback(async () => {
-    dispatch({ type: "reset_game" })
-  }, [])
+    dispatch({ type: "reset_game", player })
+  }, [player])
 
   const newGame = useCallback(async () => {
-    dispatch({ type: "new_game" })
-  }, [])
+    dispatch({ type: "new_game", player })
+  }, [player])
+
+  useEffect(() => {
+    if (gameState.player !== player) {
+      dispatch({ type: "new_game", player })
+    }
+  }, [player])
 
   const checkGameState = () => {
     const isWon =
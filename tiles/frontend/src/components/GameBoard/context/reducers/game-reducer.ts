@@ -1,5 +1,9 @@
 import { flattenDeep, isEqual, isNil } from "lodash"
 import { uid } from "uid"
+import { encodeAbiParameters, hexToBigInt, keccak256 } from "viem"
+import type { Address } from "viem"
+
+import { FLUENT_TESTNET_CHAIN_ID } from "@/shared/config/consts"
 
 import { tileCountPerDimension } from "../../constants";
 import type { Tile, TileMap } from "../../models/tile";
@@ -41,6 +45,21 @@ export class Xorshift128Plus {
   }
 }
 
+// The seed the validator actually plays for `player`: the low 64 bits of
+// keccak256(abi.encode(player, seed, chainId)), see `seed::player_seed` in the validator.
+// The board is generated from it, while `playGame` is sent the raw seed.
+export function playerSeed(
+  player: Address,
+  seed: bigint,
+  chainId: number = FLUENT_TESTNET_CHAIN_ID,
+): bigint {
+  const encoded = encodeAbiParameters(
+    [{ type: "address" }, { type: "uint64" }, { type: "uint256" }],
+    [player, seed, BigInt(chainId)],
+  )
+  return hexToBigInt(keccak256(encoded)) & BigInt("0xFFFFFFFFFFFFFFFF")
+}
+
 function generateU64Seed(): bigint {
   const maxU64 = BigInt("0xFFFFFFFFFFFFFFFF")
   const randomBigInt =
@@ -60,6 +79,7 @@ export type State = {
   status: GameStatus
   moves: (1 | 2 | 3 | 0)[]
   seed: bigint
+  player: Address
   range: Xorshift128Plus
 }
 type Action =
@@ -69,8 +89,8 @@ type Action =
   | { type: "move_down" }
   | { type: "move_left" }
   | { type: "move_right" }
-  | { type: "reset_game" }
-  | { type: "new_game" }
+  | { type: "reset_game"; player: Address }
+  | { type: "new_game"; player: Address }
   | { type: "update_status"; status: GameStatus }
 
 function createBoard() {
@@ -81,17 +101,18 @@ function createBoard() {
   return board
 }
 
-const generateSeedAndRange = () => {
+const generateSeedAndRange = (player: Address) => {
   const seed = generateU64Seed()
-  const range = new Xorshift128Plus(seed)
+  const range = new Xorshift128Plus(playerSeed(player, seed))
 
   return {
     seed,
+    player,
     range,
   }
 }
 
-export const createNewState = (): State => {
+export const createNewState = (player: Address): State => {
   const savedState = localStorage.getItem(LOCAL_STORAGE_KEY)
   const savedBestScore = savedState ? JSON.parse(savedState).bestScore : 0
 
@@ -104,19 +125,21 @@ export const createNewState = (): State => {
     bestScore: savedBestScore,
     status: "ongoing",
     moves: [],
-    ...generateSeedAndRange(),
+    ...generateSeedAndRange(player),
   }
 }
 
-export const loadStateFromLocalStorage = () => {
+export const loadStateFromLocalStorage = (player: Address) => {
   const savedState = localStorage.getItem(LOCAL_STORAGE_KEY)
-  return savedState ? (JSON.parse(savedState) as State) : createNewState()
+  const state = savedState ? (JSON.parse(savedState) as State) : undefined
+  // A saved board was generated for the player who started it, and replays for nobody else
+  return state && state.player === player ? state : createNewState(player)
 }
 
-export const initState = (): State => {
-  const state = loadStateFromLocalStorage()
+export const initState = (player: Address): State => {
+  const state = loadStateFromLocalStorage(player)
   const seed = BigInt(state.seed)
-  const range = new Xorshift128Plus(seed)
+  const range = new Xorshift128Plus(playerSeed(player, seed))
 
   return {
     ...state,
@@ -407,7 +430,7 @@ export default function gameReducer(state: State, action: Action): State {
       }
     }
     case "new_game": {
-      let newState = createNewState()
+      let newState = createNewState(action.player)
       for (let i = 0; i < 2; i++) {
         const tileId = uid()
         const emptyCells = getEmptyCells(newState)
@@ -441,7 +464,7 @@ export default function gameReducer(state: State, action: Action): State {
       return newState
     }
     case "reset_game": {
-      return initState()
+      return initState(action.player)
     }
     case "update_status":
       return {
@@ -2,7 +2,7 @@ export const tilesAbiABI = [
   {
     inputs: [
       {
-        internalType: "address",
+        internalType: "contract IValidator",
         name: "_validator",
         type: "address",
       },
@@ -16,14 +16,114 @@ export const tilesAbiABI = [
         name: "_tokenDecimals",
         type: "uint8",
       },
+      {
+        internalType: "uint256",
+        name: "_maxScore",
+        type: "uint256",
+      },
     ],
     stateMutability: "nonpayable",
     type: "constructor",
   },
+  {
+    anonymous: false,
+    inputs: [
+      {
+        indexed: true,
+        internalType: "address",
+        name: "player",
+        type: "address",
+      },
+      {
+        indexed: false,
+        internalType: "uint64",
+        name: "seed",
+        type: "uint64",
+      },
+      {
+        indexed: false,
+        internalType: "uint256",
+        name: "score",
+        type: "uint256",
+      },
+      {
+        indexed: false,
+        internalType: "uint256",
+        name: "tokensTransferred",
+        type: "uint256",
+      },
+    ],
+    name: "GamePlayed",
+    type: "event",
+  },
+  {
+    anonymous: false,
+    inputs: [
+      {
+        indexed: true,
+        internalType: "address",
+        name: "newToken",
+        type: "address",
+      },
+      {
+        indexed: false,
+        internalType: "uint8",
+        name: "tokenDecimals",
+        type: "uint8",
+      },
+    ],
+    name: "TokenSet",
+    type: "event",
+  },
+  {
+    anonymous: false,
+    inputs: [
+      {
+        indexed: true,
+        internalType: "address",
+        name: "to",
+        type: "address",
+      },
+      {
+        indexed: false,
+        internalType: "uint256",
+        name: "amount",
+        type: "uint256",
+      },
+    ],
+    name: "TokenTransferred",
+    type: "event",
+  },
+  {
+    anonymous: false,
+    inputs: [
+      {
+        indexed: true,
+        internalType: "address",
+        name: "newValidator",
+        type: "address",
+      },
+    ],
+    name: "ValidatorSet",
+    type: "event",
+  },
   {
     stateMutability: "payable",
     type: "fallback",
   },
+  {
+    inputs: [],
+    name: "maxScore",
+    outputs: [
+      {
+        internalType: "uint256",
+        name: "",
+        type: "uint256",
+      },
+    ],
+    stateMutability: "view",
+    type: "function",
+  },
   {
     inputs: [],
     name: "owner",
@@ -135,12 +235,36 @@ export const tilesAbiABI = [
     stateMutability: "nonpayable",
     type: "function",
   },
+  {
+    inputs: [
+      {
+        internalType: "address",
+        name: "",
+        type: "address",
+      },
+      {
+        internalType: "uint64",
+        name: "",
+        type: "uint64",
+      },
+    ],
+    name: "usedSeeds",
+    outputs: [
+      {
+        internalType: "bool",
+        name: "",
+        type: "bool",
+      },
+    ],
+    stateMutability: "view",
+    type: "function",
+  },
   {
     inputs: [],
     name: "validator",
     outputs: [
       {
-        internalType: "address",
+        internalType: "contract IValidator",
         name: "",
         type: "address",
       },